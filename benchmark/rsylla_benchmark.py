@@ -3,7 +3,7 @@ import asyncio
 import random
 import time
 
-from rsylla import Session
+from rsylla import AsyncSession
 
 MAX_NUMBER_OF_KEYS = 65536
 
@@ -141,7 +141,7 @@ async def main():
     )
     args = parser.parse_args()
 
-    session = await Session.connect([args.host + ":9042"])
+    session = await AsyncSession.connect([args.host + ":9042"])
     await session.use_keyspace(args.keyspace, case_sensitive=False)
 
     prepared_statement_write = await session.prepare("INSERT INTO test (id, value) values(?, ?)")
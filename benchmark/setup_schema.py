@@ -8,13 +8,13 @@ Creates the keyspace and table needed for running benchmarks.
 import argparse
 import asyncio
 
-from rsylla import Session
+from rsylla import AsyncSession
 
 
 async def setup_schema(host: str, keyspace: str, replication_factor: int = 1):
     """Create keyspace and table for benchmarks."""
     print(f"Connecting to {host}...")
-    session = await Session.connect([host + ":9042"])
+    session = await AsyncSession.connect([host + ":9042"])
 
     print(f"Creating keyspace '{keyspace}' with replication factor {replication_factor}...")
     try:
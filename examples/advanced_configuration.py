@@ -17,7 +17,7 @@ def main():
         .compression("lz4")  # Enable LZ4 compression
         .tcp_nodelay(True)  # Disable Nagle's algorithm
         .tcp_keepalive(60000)  # 60 seconds keepalive
-        .build()
+        .build_blocking()
     )
 
     print("Session created successfully!")
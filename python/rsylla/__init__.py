@@ -6,25 +6,193 @@ using the official Rust driver.
 """
 
 from ._rsylla import (
+    AggregateMetadata,
+    AlreadyExists,
+    AsyncSession,
+    AuthenticationError,
     Batch,
+    BigInt,
+    BulkResult,
+    CircuitBreakerStats,
+    ClusterInfo,
+    ColumnSpec,
+    ConfigurationError,
+    CqlSyntaxError,
+    CqlType,
+    DeserializationError,
+    EventStream,
+    EventSubscription,
+    ExecutionInfo,
+    ExecutionProfile,
+    FunctionFailure,
+    ForkedSessionError,
+    FullScan,
+    FunctionMetadata,
+    HealthReport,
+    HostInfo,
+    Int,
+    InvalidQuery,
+    IsBootstrapping,
+    KeyspaceMetadata,
+    Mapper,
+    Metrics,
+    Migration,
+    MigrationRunner,
+    NodeHealth,
+    NodeInfo,
+    NoHostAvailable,
+    OperationTimedOut,
+    Overloaded,
+    Page,
+    PoolStats,
+    PrepareCacheStats,
     PreparedStatement,
+    ProtocolError,
     Query,
+    QueryBuilder,
     QueryResult,
+    RateLimitReached,
+    ReadFailure,
+    ReadTimeout,
+    RequestContext,
+    ResultTooLarge,
+    SchemaChange,
     Row,
+    RowStream,
     ScyllaError,
+    ServerError,
     Session,
     SessionBuilder,
+    SessionConfig,
+    SlowQuery,
+    SmallInt,
+    Statement,
+    StatusChange,
+    StrictModeViolation,
+    TableMetadata,
+    Timeout,
+    TinyInt,
+    TokenRange,
+    TopologyChange,
+    TracingEvent,
+    TracingInfo,
+    TruncateError,
+    Unauthorized,
+    Unavailable,
+    UserType,
+    WriteFailure,
+    WriteTimeout,
+    connect,
+    disable_logging,
+    enable_logging,
+    get_duration_mode,
+    get_float_policy,
+    max_timeuuid,
+    min_timeuuid,
+    register_converter,
+    set_duration_mode,
+    set_float_policy,
+    shard_for,
+    timeuuid_from_datetime,
+    timeuuid_now,
+    timeuuid_to_datetime,
+    token_for,
+    unregister_converter,
 )
 
 __version__ = "0.1.1"
 
 __all__ = [
     "Session",
+    "AsyncSession",
     "SessionBuilder",
+    "SessionConfig",
+    "ExecutionProfile",
     "Query",
+    "QueryBuilder",
+    "Statement",
     "PreparedStatement",
     "QueryResult",
+    "Page",
     "Row",
+    "RowStream",
+    "ColumnSpec",
+    "CqlType",
+    "ExecutionInfo",
+    "BulkResult",
+    "HostInfo",
+    "TokenRange",
+    "FullScan",
+    "HealthReport",
+    "NodeHealth",
+    "ClusterInfo",
+    "NodeInfo",
+    "EventSubscription",
+    "EventStream",
+    "TopologyChange",
+    "StatusChange",
+    "SchemaChange",
+    "KeyspaceMetadata",
+    "TableMetadata",
+    "UserType",
+    "FunctionMetadata",
+    "AggregateMetadata",
+    "Metrics",
+    "PrepareCacheStats",
+    "PoolStats",
+    "CircuitBreakerStats",
+    "SlowQuery",
+    "RequestContext",
+    "TracingInfo",
+    "TracingEvent",
     "Batch",
+    "Mapper",
+    "MigrationRunner",
+    "Migration",
+    "TinyInt",
+    "SmallInt",
+    "Int",
+    "BigInt",
+    "connect",
+    "enable_logging",
+    "disable_logging",
+    "token_for",
+    "shard_for",
+    "register_converter",
+    "unregister_converter",
+    "set_float_policy",
+    "get_float_policy",
+    "set_duration_mode",
+    "get_duration_mode",
+    "timeuuid_now",
+    "timeuuid_from_datetime",
+    "timeuuid_to_datetime",
+    "min_timeuuid",
+    "max_timeuuid",
     "ScyllaError",
+    "NoHostAvailable",
+    "AuthenticationError",
+    "DeserializationError",
+    "ResultTooLarge",
+    "ForkedSessionError",
+    "StrictModeViolation",
+    "Unavailable",
+    "Overloaded",
+    "RateLimitReached",
+    "IsBootstrapping",
+    "TruncateError",
+    "ServerError",
+    "ProtocolError",
+    "Timeout",
+    "ReadTimeout",
+    "WriteTimeout",
+    "OperationTimedOut",
+    "ReadFailure",
+    "WriteFailure",
+    "FunctionFailure",
+    "InvalidQuery",
+    "CqlSyntaxError",
+    "Unauthorized",
+    "ConfigurationError",
+    "AlreadyExists",
 ]
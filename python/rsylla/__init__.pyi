@@ -1,44 +1,715 @@
 """Type stubs for rsylla"""
 
-from collections.abc import Iterator
-from typing import Any
+import os
+from collections.abc import Awaitable, Callable, Iterable, Iterator
+from concurrent.futures import Future
+from datetime import datetime, timedelta
+from typing import Any, Literal, Self, TypeAlias, overload
+from uuid import UUID
+
+# Values of the string settings, as returned by the properties reading them back. They're
+# accepted in any case.
+ConsistencyLevel: TypeAlias = Literal[
+    "ANY",
+    "ONE",
+    "TWO",
+    "THREE",
+    "QUORUM",
+    "ALL",
+    "LOCAL_QUORUM",
+    "EACH_QUORUM",
+    "LOCAL_ONE",
+    "SERIAL",
+    "LOCAL_SERIAL",
+]
+SerialConsistencyLevel: TypeAlias = Literal["SERIAL", "LOCAL_SERIAL"]
+RetryPolicy: TypeAlias = Literal["default", "fallthrough", "downgrading_consistency"]
+BatchType: TypeAlias = Literal["logged", "unlogged", "counter"]
+ResultKind: TypeAlias = Literal["rows", "void", "set_keyspace", "schema_change"]
+
+def enable_logging(
+    level: str = "INFO",
+    logger_name: str = "rsylla",
+    level_map: dict[str, int] | None = None,
+) -> None: ...
+def disable_logging() -> None: ...
+def connect(url: str) -> Awaitable[AsyncSession]: ...
+def token_for(partition_key: dict[str, Any], table: TableMetadata) -> int: ...
+def shard_for(token: int, shard_count: int, msb_ignore: int = 12) -> int: ...
+def register_converter(
+    cql_type: str,
+    encode: Callable[[Any], Any] | None = None,
+    decode: Callable[[Any], Any] | None = None,
+) -> None: ...
+def unregister_converter(cql_type: str) -> bool: ...
+def set_float_policy(policy: Literal["store", "raise"]) -> None: ...
+def get_float_policy() -> Literal["store", "raise"]: ...
+def set_duration_mode(mode: Literal["dict", "timedelta", "timedelta_strict"]) -> None: ...
+def get_duration_mode() -> Literal["dict", "timedelta", "timedelta_strict"]: ...
+def timeuuid_now() -> UUID: ...
+def timeuuid_from_datetime(dt: datetime | int) -> UUID: ...
+def timeuuid_to_datetime(u: UUID | str) -> datetime: ...
+def min_timeuuid(dt: datetime | int) -> UUID: ...
+def max_timeuuid(dt: datetime | int) -> UUID: ...
+
+class TinyInt:
+    """An int bound as a CQL tinyint"""
+
+    def __init__(self, value: int) -> None: ...
+    @property
+    def value(self) -> int: ...
+    def __int__(self) -> int: ...
+    def __index__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+class SmallInt:
+    """An int bound as a CQL smallint"""
+
+    def __init__(self, value: int) -> None: ...
+    @property
+    def value(self) -> int: ...
+    def __int__(self) -> int: ...
+    def __index__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+class Int:
+    """An int bound as a CQL int"""
+
+    def __init__(self, value: int) -> None: ...
+    @property
+    def value(self) -> int: ...
+    def __int__(self) -> int: ...
+    def __index__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+class BigInt:
+    """An int bound as a CQL bigint"""
+
+    def __init__(self, value: int) -> None: ...
+    @property
+    def value(self) -> int: ...
+    def __int__(self) -> int: ...
+    def __index__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
 
 class ScyllaError(Exception):
     """Exception raised for ScyllaDB errors"""
 
     pass
 
+class NoHostAvailable(ScyllaError):
+    """No node could be reached to execute the request"""
+
+class AuthenticationError(ScyllaError):
+    """Authentication with the cluster failed"""
+
+class DeserializationError(ScyllaError):
+    """A result row could not be converted to Python values"""
+
+class ResultTooLarge(ScyllaError):
+    """A result has more rows than the session's max_rows_in_memory"""
+
+class ForkedSessionError(ScyllaError):
+    """The driver was used in a child process forked after it started"""
+
+class StrictModeViolation(ScyllaError):
+    """A statement broke a rule of the session's strict mode and was not sent"""
+
+class Unavailable(ScyllaError):
+    """Not enough live replicas to satisfy the consistency level"""
+
+    consistency: str
+    required: int
+    alive: int
+
+class Overloaded(ScyllaError):
+    """The coordinator is overloaded"""
+
+class RateLimitReached(Overloaded):
+    """The operation exceeded a per-partition rate limit"""
+
+    op_type: str
+    rejected_by_coordinator: bool
+
+class IsBootstrapping(ScyllaError):
+    """The coordinator is still bootstrapping"""
+
+class TruncateError(ScyllaError):
+    """A TRUNCATE operation failed"""
+
+class ServerError(ScyllaError):
+    """Internal server error"""
+
+class ProtocolError(ScyllaError):
+    """The server reported a protocol violation"""
+
+class Timeout(ScyllaError):
+    """Base class for timeouts"""
+
+class ReadTimeout(Timeout):
+    """Replicas did not respond to a read in time"""
+
+    consistency: str
+    received: int
+    required: int
+    data_present: bool
+
+class WriteTimeout(Timeout):
+    """Replicas did not acknowledge a write in time"""
+
+    consistency: str
+    received: int
+    required: int
+    write_type: str
+
+class OperationTimedOut(Timeout):
+    """The client-side request timeout elapsed"""
+
+    timeout_ms: int
+
+class ReadFailure(ScyllaError):
+    """A non-timeout failure occurred on replicas during a read"""
+
+    consistency: str
+    received: int
+    required: int
+    failures: int
+    data_present: bool
+
+class WriteFailure(ScyllaError):
+    """A non-timeout failure occurred on replicas during a write"""
+
+    consistency: str
+    received: int
+    required: int
+    failures: int
+    write_type: str
+
+class FunctionFailure(ScyllaError):
+    """A user-defined function failed during execution"""
+
+    keyspace: str
+    function: str
+    arg_types: list[str]
+
+class InvalidQuery(ScyllaError):
+    """The request was rejected as invalid"""
+
+class CqlSyntaxError(InvalidQuery):
+    """The statement has a CQL syntax error"""
+
+class Unauthorized(InvalidQuery):
+    """The logged-in user lacks permission for the request"""
+
+class ConfigurationError(InvalidQuery):
+    """The request is invalid because of a configuration issue"""
+
+class AlreadyExists(InvalidQuery):
+    """The keyspace or table being created already exists"""
+
+    keyspace: str
+    table: str | None
+
 class SessionBuilder:
     """Builder for creating ScyllaDB sessions with configuration"""
 
     def __init__(self) -> None: ...
+    @staticmethod
+    def from_url(url: str) -> SessionBuilder: ...
     def known_node(self, hostname: str) -> SessionBuilder: ...
     def known_nodes(self, hostnames: list[str]) -> SessionBuilder: ...
-    def use_keyspace(self, keyspace_name: str, case_sensitive: bool) -> SessionBuilder: ...
+    def use_keyspace(self, keyspace_name: str, case_sensitive: bool = False) -> SessionBuilder: ...
     def connection_timeout(self, duration_ms: int) -> SessionBuilder: ...
+    def schema_agreement(
+        self,
+        timeout_ms: int | None = None,
+        interval_ms: int | None = None,
+        auto_await: bool = True,
+    ) -> SessionBuilder: ...
     def pool_size(self, size: int) -> SessionBuilder: ...
     def user(self, username: str, password: str) -> SessionBuilder: ...
-    def compression(self, compression: str | None) -> SessionBuilder: ...
+    def compression(
+        self, compression: Literal["lz4", "snappy"] | None = None
+    ) -> SessionBuilder: ...
     def tcp_nodelay(self, nodelay: bool) -> SessionBuilder: ...
-    def tcp_keepalive(self, keepalive_ms: int | None) -> SessionBuilder: ...
-    def build(self) -> Session: ...
+    def timestamp_generator(self, generator: str | None = None) -> SessionBuilder: ...
+    def tcp_keepalive(self, keepalive_ms: int | None = None) -> SessionBuilder: ...
+    def keepalive(
+        self, interval_ms: int | None = 30000, timeout_ms: int | None = 30000
+    ) -> SessionBuilder: ...
+    def host_metrics(self, enabled: bool = True) -> SessionBuilder: ...
+    def pool_monitoring(self, enabled: bool = True) -> SessionBuilder: ...
+    def auto_prepare(self, enabled: bool = True, capacity: int = 1000) -> SessionBuilder: ...
+    def max_rows_in_memory(self, max_rows: int | None = None) -> SessionBuilder: ...
+    def max_inflight_requests(self, max_requests: int | None = None) -> SessionBuilder: ...
+    def rate_limit(
+        self, requests_per_second: float | None = None, burst: int | None = None
+    ) -> SessionBuilder: ...
+    def circuit_breaker(
+        self, enabled: bool = True, failure_threshold: int = 5, open_period_ms: int = 30000
+    ) -> SessionBuilder: ...
+    def strict_mode(
+        self,
+        enabled: bool = True,
+        unprepared: bool = True,
+        allow_filtering: bool = True,
+        unbounded_selects: bool = True,
+    ) -> SessionBuilder: ...
+    def enum_binding(self, binding: Literal["value", "name"]) -> SessionBuilder: ...
+    def tracer(self, tracer: Any | None = None) -> SessionBuilder: ...
+    def slow_query_log(
+        self,
+        threshold_ms: int,
+        callback: Callable[[SlowQuery], Any] | None = None,
+        include_values: bool = False,
+    ) -> SessionBuilder: ...
+    def query_log(
+        self,
+        logger: str | Any | None = None,
+        level: int = 10,
+        include_values: bool = True,
+        redact: Iterable[str] | Callable[[str, Any], Any] | None = None,
+    ) -> SessionBuilder: ...
+    def interceptor(
+        self,
+        before: Callable[[RequestContext], Any] | None = None,
+        after: Callable[[RequestContext, ExecutionInfo | None, BaseException | None], Any]
+        | None = None,
+    ) -> SessionBuilder: ...
+    def load_balancing(
+        self,
+        local_dc: str | None = None,
+        permit_dc_failover: bool = False,
+        local_rack: str | None = None,
+        token_aware: bool = True,
+    ) -> SessionBuilder: ...
+    def latency_awareness(
+        self,
+        exclusion_threshold: float = 2.0,
+        update_rate_ms: int = 100,
+        minimum_measurements: int = 50,
+        retry_period_ms: int = 10000,
+        scale_ms: int = 100,
+    ) -> SessionBuilder: ...
+    def host_filter(
+        self,
+        allow_hosts: list[str] | None = None,
+        deny_hosts: list[str] | None = None,
+        allow_dcs: list[str] | None = None,
+        deny_dcs: list[str] | None = None,
+        predicate: Callable[[HostInfo], bool] | None = None,
+    ) -> SessionBuilder: ...
+    def default_execution_profile(self, profile: ExecutionProfile) -> SessionBuilder: ...
+    async def build(self) -> AsyncSession: ...
+    def build_blocking(self) -> Session: ...
 
-class Session:
-    """ScyllaDB session for executing queries"""
+class SessionConfig:
+    """Session settings loaded from a configuration file or environment variables"""
 
     @staticmethod
-    def connect(nodes: list[str]) -> Session: ...
-    def execute(self, query: str, values: dict[str, Any] | None = None) -> QueryResult: ...
+    def from_file(path: str | os.PathLike[str]) -> SessionConfig: ...
+    @staticmethod
+    def from_env(prefix: str = "RSYLLA_") -> SessionConfig: ...
+    def builder(self) -> SessionBuilder: ...
+    async def build(self) -> AsyncSession: ...
+    def build_blocking(self) -> Session: ...
+    def profile(self, name: str) -> ExecutionProfile: ...
+    @property
+    def profiles(self) -> dict[str, ExecutionProfile]: ...
+    @property
+    def contact_points(self) -> list[str]: ...
+    @property
+    def keyspace(self) -> str | None: ...
+    @property
+    def username(self) -> str | None: ...
+    def __repr__(self) -> str: ...
+
+class _SessionBase:
+    """Methods shared by Session and AsyncSession
+
+    The methods that talk to the cluster are declared on each: AsyncSession
+    returns awaitables from them, Session returns their results and releases
+    the GIL while waiting.
+    """
+
+    def execute_future(
+        self,
+        query: str | Statement | Query | QueryBuilder,
+        values: dict[str, Any] | None = None,
+        timestamp: int | None = None,
+        page_size: int | None = None,
+        timeout_ms: int | None = None,
+        raw: bool = False,
+    ) -> Future[QueryResult]: ...
+    def get_cluster_data(self) -> str: ...
+    def subscribe(
+        self,
+        callback: Callable[[TopologyChange | StatusChange | SchemaChange], Any],
+        events: list[str] | None = None,
+        interval_ms: int = 1000,
+    ) -> EventSubscription: ...
+    def events(
+        self, events: list[str] | None = None, interval_ms: int = 1000
+    ) -> EventStream: ...
+    def get_replicas(
+        self, keyspace: str, table: str, partition_key: dict[str, Any]
+    ) -> list[HostInfo]: ...
+    def token_ring(self, keyspace: str | None = None) -> list[TokenRange]: ...
+    def token_ranges(
+        self, keyspace: str | None = None, splits_per_node: int = 1
+    ) -> list[TokenRange]: ...
+    def full_scan(
+        self, table_or_query: str, parallelism: int = 16, page_size: int | None = None
+    ) -> FullScan: ...
+    def check_connectivity(self, per_node: bool = False) -> HealthReport: ...
+    def get_keyspace(self) -> str | None: ...
+    @property
+    def keyspace(self) -> str | None: ...
+    @property
+    def default_execution_profile(self) -> ExecutionProfile: ...
+    def metrics(self) -> Metrics: ...
+    def metrics_prometheus(self) -> str: ...
+    def prepare_cache_stats(self) -> PrepareCacheStats | None: ...
+    def pool_stats(self) -> list[PoolStats] | None: ...
+    def circuit_breaker_stats(self) -> list[CircuitBreakerStats] | None: ...
+    def close(self) -> None: ...
+    @property
+    def closed(self) -> bool: ...
+    @property
+    def in_flight(self) -> int: ...
+    @property
+    def waiting_requests(self) -> int: ...
+    def __enter__(self) -> Self: ...
+    def __exit__(self, exc_type: Any, exc_value: Any, traceback: Any) -> bool: ...
+    async def __aenter__(self) -> Self: ...
+    async def __aexit__(self, exc_type: Any, exc_value: Any, traceback: Any) -> bool: ...
+
+class AsyncSession(_SessionBase):
+    """ScyllaDB session for asyncio and trio applications"""
+
+    @classmethod
+    async def connect(cls, nodes: list[str] | str) -> AsyncSession: ...
+    async def execute(
+        self,
+        query: str | Statement | Query | QueryBuilder,
+        values: dict[str, Any] | None = None,
+        timestamp: int | None = None,
+        page_size: int | None = None,
+        timeout_ms: int | None = None,
+        raw: bool = False,
+    ) -> QueryResult: ...
+    async def query(self, query: Query, values: dict[str, Any] | None = None) -> QueryResult: ...
+    async def insert_json(
+        self,
+        table: str,
+        values: dict[str, Any],
+        if_not_exists: bool = False,
+        default_unset: bool = False,
+        timeout_ms: int | None = None,
+    ) -> QueryResult: ...
+    async def select_json(
+        self,
+        query: str | Statement | Query | QueryBuilder,
+        values: dict[str, Any] | None = None,
+        timeout_ms: int | None = None,
+    ) -> list[dict[str, Any]]: ...
+    async def prepare(self, query: str) -> PreparedStatement: ...
+    async def execute_prepared(
+        self,
+        prepared: PreparedStatement,
+        values: dict[str, Any] | None = None,
+        timeout_ms: int | None = None,
+        raw: bool = False,
+    ) -> QueryResult: ...
+    async def execute_concurrent(
+        self,
+        statements_and_params: Iterable[
+            str
+            | Statement
+            | Query
+            | QueryBuilder
+            | PreparedStatement
+            | tuple[str | Statement | Query | PreparedStatement, dict[str, Any] | None]
+        ],
+        concurrency: int = 100,
+    ) -> list[tuple[bool, QueryResult | Exception]]: ...
+    async def execute_many(
+        self,
+        prepared: PreparedStatement,
+        params_list: Iterable[dict[str, Any]],
+        concurrency: int = 100,
+    ) -> BulkResult: ...
+    async def copy_from(
+        self,
+        path: str | os.PathLike[str],
+        table: str,
+        format: str = "csv",
+        columns: list[str] | None = None,
+        header: bool = True,
+        delimiter: str = ",",
+        null: str = "",
+        concurrency: int = 100,
+    ) -> BulkResult: ...
+    async def batch(
+        self, batch: Batch, values: list[dict[str, Any]], timestamp: int | None = None
+    ) -> QueryResult: ...
+    async def use_keyspace(self, keyspace_name: str, case_sensitive: bool = False) -> None: ...
+    async def create_keyspace(
+        self,
+        name: str,
+        replication: dict[str, str | int],
+        durable_writes: bool = True,
+        if_not_exists: bool = True,
+    ) -> None: ...
+    async def await_schema_agreement(self, timeout_ms: int | None = None) -> str: ...
+    async def check_schema_agreement(self) -> str | None: ...
+    async def get_keyspace_metadata(
+        self, keyspace: str, refresh: bool = False
+    ) -> KeyspaceMetadata | None: ...
+    async def wait_until_ready(
+        self, min_hosts: int | None = None, timeout_ms: int | None = None
+    ) -> HealthReport: ...
+    async def cluster_info(self) -> ClusterInfo: ...
+    async def refresh_nodes(self) -> None: ...
+    async def shutdown(self, grace_period_ms: int | None = None) -> bool: ...
+
+class Session(_SessionBase):
+    """Blocking ScyllaDB session"""
+
+    @classmethod
+    def connect(cls, nodes: list[str] | str) -> Session: ...
+    def execute(
+        self,
+        query: str | Statement | Query | QueryBuilder,
+        values: dict[str, Any] | None = None,
+        timestamp: int | None = None,
+        page_size: int | None = None,
+        timeout_ms: int | None = None,
+        raw: bool = False,
+    ) -> QueryResult: ...
     def query(self, query: Query, values: dict[str, Any] | None = None) -> QueryResult: ...
+    def insert_json(
+        self,
+        table: str,
+        values: dict[str, Any],
+        if_not_exists: bool = False,
+        default_unset: bool = False,
+        timeout_ms: int | None = None,
+    ) -> QueryResult: ...
+    def select_json(
+        self,
+        query: str | Statement | Query | QueryBuilder,
+        values: dict[str, Any] | None = None,
+        timeout_ms: int | None = None,
+    ) -> list[dict[str, Any]]: ...
     def prepare(self, query: str) -> PreparedStatement: ...
     def execute_prepared(
-        self, prepared: PreparedStatement, values: dict[str, Any] | None = None
+        self,
+        prepared: PreparedStatement,
+        values: dict[str, Any] | None = None,
+        timeout_ms: int | None = None,
+        raw: bool = False,
     ) -> QueryResult: ...
-    def batch(self, batch: Batch, values: list[dict[str, Any]]) -> QueryResult: ...
-    def use_keyspace(self, keyspace_name: str, case_sensitive: bool) -> None: ...
-    def await_schema_agreement(self) -> bool: ...
-    def get_cluster_data(self) -> str: ...
-    def get_keyspace(self) -> str | None: ...
+    def execute_concurrent(
+        self,
+        statements_and_params: Iterable[
+            str
+            | Statement
+            | Query
+            | QueryBuilder
+            | PreparedStatement
+            | tuple[str | Statement | Query | PreparedStatement, dict[str, Any] | None]
+        ],
+        concurrency: int = 100,
+    ) -> list[tuple[bool, QueryResult | Exception]]: ...
+    def execute_many(
+        self,
+        prepared: PreparedStatement,
+        params_list: Iterable[dict[str, Any]],
+        concurrency: int = 100,
+    ) -> BulkResult: ...
+    def copy_from(
+        self,
+        path: str | os.PathLike[str],
+        table: str,
+        format: str = "csv",
+        columns: list[str] | None = None,
+        header: bool = True,
+        delimiter: str = ",",
+        null: str = "",
+        concurrency: int = 100,
+    ) -> BulkResult: ...
+    def batch(
+        self, batch: Batch, values: list[dict[str, Any]], timestamp: int | None = None
+    ) -> QueryResult: ...
+    def use_keyspace(self, keyspace_name: str, case_sensitive: bool = False) -> None: ...
+    def create_keyspace(
+        self,
+        name: str,
+        replication: dict[str, str | int],
+        durable_writes: bool = True,
+        if_not_exists: bool = True,
+    ) -> None: ...
+    def await_schema_agreement(self, timeout_ms: int | None = None) -> str: ...
+    def check_schema_agreement(self) -> str | None: ...
+    def get_keyspace_metadata(
+        self, keyspace: str, refresh: bool = False
+    ) -> KeyspaceMetadata | None: ...
+    def wait_until_ready(
+        self, min_hosts: int | None = None, timeout_ms: int | None = None
+    ) -> HealthReport: ...
+    def cluster_info(self) -> ClusterInfo: ...
+    def refresh_nodes(self) -> None: ...
+    def shutdown(self, grace_period_ms: int | None = None) -> bool: ...
+
+class RequestContext:
+    """A request seen by interceptor hooks"""
+
+    statement: str
+    values: dict[str, Any] | None
+    keyspace: str | None
+    annotations: dict[str, Any]
+    def __repr__(self) -> str: ...
+
+class SlowQuery:
+    """A request that exceeded the slow query threshold"""
+
+    statement: str
+    values: dict[str, Any] | None
+    latency_ms: float
+    coordinator: str | None
+    error: str | None
+    def __repr__(self) -> str: ...
+
+class Metrics:
+    """Snapshot of driver metrics for a session"""
+
+    requests: int
+    paged_requests: int
+    errors: int
+    paged_errors: int
+    retries: int
+    request_timeouts: int
+    connection_timeouts: int
+    total_connections: int
+    mean_rate: float
+    one_minute_rate: float
+    five_minute_rate: float
+    fifteen_minute_rate: float
+    latency_min_ms: int | None
+    latency_max_ms: int | None
+    latency_mean_ms: int | None
+    latency_stddev_ms: int | None
+    p50: int | None
+    p75: int | None
+    p95: int | None
+    p98: int | None
+    p99: int | None
+    p999: int | None
+    def percentile(self, percentile: float) -> int | None: ...
+    def __repr__(self) -> str: ...
+
+class PrepareCacheStats:
+    """Snapshot of the auto-prepare statement cache"""
+
+    hits: int
+    misses: int
+    evictions: int
+    reprepares: int
+    size: int
+    capacity: int
+    @property
+    def hit_rate(self) -> float: ...
+    def __repr__(self) -> str: ...
+
+class PoolStats:
+    """Connection pool state of a node"""
+
+    host: HostInfo
+    connected: bool
+    shards: int | None
+    def __repr__(self) -> str: ...
+
+class CircuitBreakerStats:
+    """Circuit breaker state of a node"""
+
+    host: HostInfo
+    state: Literal["closed", "open", "half_open"]
+    consecutive_failures: int
+    opened: int
+    retry_in_ms: int | None
+    def __repr__(self) -> str: ...
+
+class ExecutionProfile:
+    """Reusable bundle of execution settings shared by statements"""
+
+    def __init__(
+        self,
+        consistency: str | None = None,
+        serial_consistency: str | None = None,
+        timeout_ms: int | None = None,
+        retry_policy: str | None = None,
+        speculative_executions: int | None = None,
+        speculative_delay_ms: int | None = None,
+        speculative_percentile: float | None = None,
+        local_dc: str | None = None,
+        local_rack: str | None = None,
+        permit_dc_failover: bool | None = None,
+        token_aware: bool | None = None,
+        query_log: bool | None = None,
+        rate_limit: float | None = None,
+    ) -> None: ...
+    def derive(
+        self,
+        consistency: str | None = None,
+        serial_consistency: str | None = None,
+        timeout_ms: int | None = None,
+        retry_policy: str | None = None,
+        speculative_executions: int | None = None,
+        speculative_delay_ms: int | None = None,
+        speculative_percentile: float | None = None,
+        local_dc: str | None = None,
+        local_rack: str | None = None,
+        permit_dc_failover: bool | None = None,
+        token_aware: bool | None = None,
+        query_log: bool | None = None,
+        rate_limit: float | None = None,
+    ) -> ExecutionProfile: ...
+    @property
+    def consistency(self) -> ConsistencyLevel | None: ...
+    @property
+    def serial_consistency(self) -> SerialConsistencyLevel | None: ...
+    @property
+    def timeout_ms(self) -> int | None: ...
+    @property
+    def retry_policy(self) -> RetryPolicy | None: ...
+    @property
+    def speculative_executions(self) -> int | None: ...
+    @property
+    def speculative_delay_ms(self) -> int | None: ...
+    @property
+    def speculative_percentile(self) -> float | None: ...
+    @property
+    def local_dc(self) -> str | None: ...
+    @property
+    def local_rack(self) -> str | None: ...
+    @property
+    def permit_dc_failover(self) -> bool | None: ...
+    @property
+    def token_aware(self) -> bool | None: ...
+    @property
+    def query_log(self) -> bool | None: ...
+    @property
+    def rate_limit(self) -> float | None: ...
+    def __repr__(self) -> str: ...
 
 class Query:
     """CQL query with configuration options"""
@@ -50,9 +721,96 @@ class Query:
     def with_timestamp(self, timestamp: int) -> Query: ...
     def with_timeout(self, timeout_ms: int) -> Query: ...
     def with_tracing(self, tracing: bool) -> Query: ...
+    def with_routing_key(self, routing_key: bytes, keyspace: str, table: str) -> Query: ...
+    def with_profile(self, profile: ExecutionProfile) -> Query: ...
+    def get_routing_token(self) -> int | None: ...
     def is_idempotent(self) -> bool: ...
     def set_idempotent(self, idempotent: bool) -> None: ...
     def get_contents(self) -> str: ...
+    @staticmethod
+    def select(table: str, columns: list[str] | None = None) -> QueryBuilder: ...
+    @staticmethod
+    def insert_into(table: str) -> QueryBuilder: ...
+    @staticmethod
+    def update(table: str) -> QueryBuilder: ...
+    @staticmethod
+    def delete_from(table: str, columns: list[str] | None = None) -> QueryBuilder: ...
+
+class QueryBuilder:
+    """Builder generating parameterized CQL"""
+
+    cql: str
+    values: dict[str, Any]
+
+    def where_eq(self, column: str, value: Any) -> QueryBuilder: ...
+    def where_in(self, column: str, values: Iterable[Any]) -> QueryBuilder: ...
+    def where_op(self, column: str, op: str, value: Any) -> QueryBuilder: ...
+    def value(self, column: str, value: Any) -> QueryBuilder: ...
+    def set(self, column: str, value: Any) -> QueryBuilder: ...
+    def increment(self, column: str, amount: int | None = None) -> QueryBuilder: ...
+    def decrement(self, column: str, amount: int | None = None) -> QueryBuilder: ...
+    def order_by(self, column: str, descending: bool = False) -> QueryBuilder: ...
+    def limit(self, limit: int) -> QueryBuilder: ...
+    def per_partition_limit(self, limit: int) -> QueryBuilder: ...
+    def allow_filtering(self) -> QueryBuilder: ...
+    def select_writetime(self, column: str) -> QueryBuilder: ...
+    def select_ttl(self, column: str) -> QueryBuilder: ...
+    def if_exists(self) -> QueryBuilder: ...
+    def if_not_exists(self) -> QueryBuilder: ...
+    def if_condition(self, column: str, value: Any, op: str = "=") -> QueryBuilder: ...
+    def using_ttl(self, ttl: int) -> QueryBuilder: ...
+    def using_timestamp(self, timestamp: int) -> QueryBuilder: ...
+    def build(self) -> tuple[Query, dict[str, Any]]: ...
+    def __repr__(self) -> str: ...
+
+class Statement:
+    """CQL statement bundled with its execution options"""
+
+    def __init__(
+        self,
+        query: str,
+        consistency: str | None = None,
+        serial_consistency: str | None = None,
+        timeout_ms: int | None = None,
+        page_size: int | None = None,
+        idempotent: bool = False,
+        tracing: bool = False,
+        timestamp: int | None = None,
+        routing_key: tuple[bytes, str, str] | None = None,
+        profile: ExecutionProfile | None = None,
+        ttl: int | None = None,
+        server_timeout: timedelta | None = None,
+        bypass_cache: bool = False,
+    ) -> None: ...
+    @property
+    def query(self) -> str: ...
+    @property
+    def consistency(self) -> ConsistencyLevel | None: ...
+    @property
+    def serial_consistency(self) -> SerialConsistencyLevel | None: ...
+    @property
+    def timeout_ms(self) -> int | None: ...
+    @property
+    def page_size(self) -> int: ...
+    @property
+    def idempotent(self) -> bool: ...
+    @property
+    def tracing(self) -> bool: ...
+    @property
+    def timestamp(self) -> int | None: ...
+    @property
+    def ttl(self) -> int | None: ...
+    @property
+    def server_timeout(self) -> timedelta | None: ...
+    @property
+    def bypass_cache(self) -> bool: ...
+    @property
+    def cql(self) -> str: ...
+    @property
+    def profile(self) -> ExecutionProfile | None: ...
+    @property
+    def routing_token(self) -> int | None: ...
+    def __repr__(self) -> str: ...
 
 class PreparedStatement:
     """Prepared CQL statement for efficient repeated execution"""
@@ -62,10 +820,16 @@ class PreparedStatement:
     def with_page_size(self, page_size: int) -> PreparedStatement: ...
     def with_timestamp(self, timestamp: int) -> PreparedStatement: ...
     def with_tracing(self, tracing: bool) -> PreparedStatement: ...
+    def with_profile(self, profile: ExecutionProfile) -> PreparedStatement: ...
     def is_idempotent(self) -> bool: ...
     def set_idempotent(self, idempotent: bool) -> PreparedStatement: ...
     def get_id(self) -> bytes: ...
     def get_statement(self) -> str: ...
+    def is_lwt(self) -> bool: ...
+    def get_variable_col_specs(self) -> list[ColumnSpec]: ...
+    def get_variable_names(self) -> list[str]: ...
+    def get_partition_key_indexes(self) -> list[int]: ...
+    def get_result_col_specs(self) -> list[ColumnSpec]: ...
 
 class QueryResult:
     """Result of a CQL query"""
@@ -73,30 +837,337 @@ class QueryResult:
     def rows(self) -> list[Row]: ...
     def first_row(self) -> Row | None: ...
     def single_row(self) -> Row: ...
+    def one(self) -> Row: ...
+    def scalar(self) -> Any: ...
     def first_row_typed(self) -> dict[str, Any] | None: ...
     def rows_typed(self) -> list[dict[str, Any]]: ...
-    def col_specs(self) -> list[dict[str, Any]]: ...
+    @overload
+    def all(self, as_dict: Literal[True] = True) -> list[dict[str, Any]]: ...
+    @overload
+    def all(self, as_dict: Literal[False]) -> list[tuple[Any, ...]]: ...
+    @overload
+    def all(self, as_dict: bool) -> list[dict[str, Any]] | list[tuple[Any, ...]]: ...
+    def pages(self) -> Iterator[Page]: ...
+    def stream(self, buffer: int = 10000) -> RowStream: ...
+    @property
+    def kind(self) -> ResultKind: ...
+    @property
+    def set_keyspace(self) -> str | None: ...
+    @property
+    def schema_change(self) -> SchemaChange | None: ...
+    @property
+    def was_applied(self) -> bool: ...
+    def existing(self) -> dict[str, Any] | None: ...
+    def col_specs(self) -> list[ColumnSpec]: ...
     def tracing_id(self) -> str | None: ...
     def warnings(self) -> list[str]: ...
+    def execution_info(self) -> ExecutionInfo: ...
+    def get_tracing_info(self) -> Awaitable[TracingInfo] | TracingInfo: ...
     def __iter__(self) -> Iterator[Row]: ...
     def __next__(self) -> Row: ...
     def __len__(self) -> int: ...
     def __bool__(self) -> bool: ...
 
+class Page:
+    """Rows received in one response of a paged query"""
+
+    rows: list[Row]
+    paging_state: bytes | None
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Iterator[Row]: ...
+    def __repr__(self) -> str: ...
+
+class RowStream:
+    """Rows of a result deserialized on a worker thread while they are consumed"""
+
+    def __aiter__(self) -> RowStream: ...
+    async def __anext__(self) -> Row: ...
+    def __iter__(self) -> RowStream: ...
+    def __next__(self) -> Row: ...
+
+class BulkResult:
+    """Aggregated outcome of execute_many"""
+
+    succeeded: int
+    failed: int
+    rows: int
+    errors: list[tuple[int, Exception]]
+    def __bool__(self) -> bool: ...
+    def __repr__(self) -> str: ...
+
+class ExecutionInfo:
+    """Information about how a request was executed"""
+
+    coordinator: str
+    coordinator_is_replica: bool | None
+    attempts: int
+    retries: int
+    speculative_executions: int
+    latency_ms: float
+    request_size: int
+    response_size: int
+    pages: int
+    queue_ms: float
+    network_ms: float
+    def __repr__(self) -> str: ...
+
+class HostInfo:
+    """Information about a cluster node"""
+
+    address: str
+    port: int
+    datacenter: str | None
+    rack: str | None
+    host_id: str
+    def __repr__(self) -> str: ...
+
+class TokenRange:
+    """Range of tokens (start, end] and the nodes owning it"""
+
+    start: int
+    end: int
+    replicas: list[HostInfo]
+    def contains(self, token: int) -> bool: ...
+    def __iter__(self) -> Iterator[Any]: ...
+    def __repr__(self) -> str: ...
+
+class FullScan:
+    """Iterator over the rows of a parallel full table scan"""
+
+    def __aiter__(self) -> FullScan: ...
+    async def __anext__(self) -> Row: ...
+    def __iter__(self) -> FullScan: ...
+    def __next__(self) -> Row: ...
+    def cancel(self) -> None: ...
+    def __repr__(self) -> str: ...
+
+class NodeHealth:
+    """Connection state of a single node"""
+
+    host: HostInfo
+    connected: bool
+    def __repr__(self) -> str: ...
+
+class HealthReport:
+    """Connectivity of the session to the cluster"""
+
+    healthy: bool
+    nodes: list[NodeHealth]
+    datacenters: dict[str, tuple[int, int]]
+    def __bool__(self) -> bool: ...
+    def __repr__(self) -> str: ...
+
+class NodeInfo:
+    """Address, location, version and state of a node"""
+
+    host: HostInfo
+    up: bool
+    shards: int | None
+    release_version: str | None
+    scylla_version: str | None
+    def __repr__(self) -> str: ...
+
+class ClusterInfo:
+    """Name, protocol version and nodes of the cluster"""
+
+    name: str | None
+    protocol_version: int
+    nodes: list[NodeInfo]
+    def __repr__(self) -> str: ...
+
+class EventSubscription:
+    """Handle of a cluster event subscription"""
+
+    active: bool
+    def cancel(self) -> None: ...
+    def __repr__(self) -> str: ...
+
+class EventStream:
+    """Iterator over cluster events"""
+
+    active: bool
+    def __aiter__(self) -> EventStream: ...
+    async def __anext__(self) -> TopologyChange | StatusChange | SchemaChange: ...
+    def __iter__(self) -> EventStream: ...
+    def __next__(self) -> TopologyChange | StatusChange | SchemaChange: ...
+    def cancel(self) -> None: ...
+    def __repr__(self) -> str: ...
+
+class TopologyChange:
+    """Node added to, removed from or moved within the cluster"""
+
+    change: str
+    host: HostInfo
+    def __repr__(self) -> str: ...
+
+class StatusChange:
+    """Node went up or down"""
+
+    change: str
+    host: HostInfo
+    def __repr__(self) -> str: ...
+
+class SchemaChange:
+    """Keyspace, table, user-defined type, function or aggregate created, updated or dropped"""
+
+    change: str
+    target: str
+    keyspace: str
+    name: str | None
+    def __repr__(self) -> str: ...
+
+class KeyspaceMetadata:
+    """Schema of a keyspace"""
+
+    name: str
+    strategy: str
+    replication: dict[str, str]
+    durable_writes: bool
+    tablets: bool
+    tables: dict[str, TableMetadata]
+    views: dict[str, TableMetadata]
+    user_types: dict[str, UserType]
+    functions: list[FunctionMetadata]
+    aggregates: list[AggregateMetadata]
+    def __repr__(self) -> str: ...
+
+class TableMetadata:
+    """Columns and primary key of a table or materialized view"""
+
+    name: str
+    columns: dict[str, str]
+    partition_key: list[str]
+    clustering_key: list[str]
+    def __repr__(self) -> str: ...
+
+class UserType:
+    """User-defined type with its fields in declaration order"""
+
+    keyspace: str
+    name: str
+    field_names: list[str]
+    field_types: list[str]
+    def __repr__(self) -> str: ...
+
+class CqlType:
+    """Structured CQL type, str() gives it as written in CQL"""
+
+    kind: str
+    frozen: bool
+    parameters: list[CqlType]
+    dimensions: int | None
+    keyspace: str | None
+    name: str | None
+    field_names: list[str]
+    def __str__(self) -> str: ...
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class ColumnSpec:
+    """Column of a result set or bind marker of a prepared statement"""
+
+    name: str
+    keyspace: str
+    table: str
+    cql_type: CqlType
+    def __repr__(self) -> str: ...
+
+class FunctionMetadata:
+    """User-defined function"""
+
+    keyspace: str
+    name: str
+    argument_names: list[str]
+    argument_types: list[str]
+    return_type: str | None
+    language: str | None
+    body: str | None
+    called_on_null_input: bool | None
+    def __repr__(self) -> str: ...
+
+class AggregateMetadata:
+    """User-defined aggregate"""
+
+    keyspace: str
+    name: str
+    argument_types: list[str]
+    state_func: str | None
+    state_type: str | None
+    final_func: str | None
+    initcond: str | None
+    return_type: str | None
+    def __repr__(self) -> str: ...
+
+class TracingEvent:
+    """Single event of a traced request"""
+
+    event_id: str
+    activity: str | None
+    source: str | None
+    source_elapsed: int | None
+    thread: str | None
+    def __repr__(self) -> str: ...
+
+class TracingInfo:
+    """Tracing session of a request, read from system_traces"""
+
+    client: str | None
+    command: str | None
+    coordinator: str | None
+    duration: int | None
+    parameters: dict[str, str]
+    request: str | None
+    started_at: int | None
+    events: list[TracingEvent]
+    def nodes(self) -> list[str]: ...
+    def __repr__(self) -> str: ...
+
 class Row:
     """A row from a query result"""
 
     def columns(self) -> list[Any]: ...
     def as_dict(self) -> dict[str, Any]: ...
-    def get(self, index: int) -> Any: ...
+    def get(self, key: str | int, default: Any = None) -> Any: ...
+    def keys(self) -> list[str]: ...
+    def values(self) -> list[Any]: ...
+    def items(self) -> list[tuple[str, Any]]: ...
+    def writetime(self, column: str) -> datetime | None: ...
+    def ttl(self, column: str) -> int | None: ...
+    def __contains__(self, key: object) -> bool: ...
     def __len__(self) -> int: ...
-    def __getitem__(self, index: int) -> Any: ...
+    def __getitem__(self, key: str | int) -> Any: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
 
 class Batch:
     """Batch operation for executing multiple statements atomically"""
 
-    def __init__(self, batch_type: str = "logged") -> None: ...
+    def __init__(
+        self,
+        batch_type: str = "logged",
+        consistency: str | None = None,
+        serial_consistency: str | None = None,
+        timestamp: int | None = None,
+        timeout_ms: int | None = None,
+        idempotent: bool = False,
+        tracing: bool = False,
+    ) -> None: ...
+    @staticmethod
+    def counter() -> Batch: ...
+    @property
+    def batch_type(self) -> BatchType: ...
+    @property
+    def consistency(self) -> ConsistencyLevel | None: ...
+    @property
+    def serial_consistency(self) -> SerialConsistencyLevel | None: ...
+    @property
+    def timestamp(self) -> int | None: ...
+    @property
+    def timeout_ms(self) -> int | None: ...
+    @property
+    def tracing(self) -> bool: ...
     def append_statement(self, query: str) -> None: ...
     def append_query(self, query: Query) -> None: ...
     def append_prepared(self, prepared: PreparedStatement) -> None: ...
@@ -104,8 +1175,77 @@ class Batch:
     def with_serial_consistency(self, serial_consistency: str) -> Batch: ...
     def with_timestamp(self, timestamp: int) -> Batch: ...
     def with_timeout(self, timeout_ms: int) -> Batch: ...
+    def with_profile(self, profile: ExecutionProfile) -> Batch: ...
     def with_tracing(self, tracing: bool) -> Batch: ...
     def is_idempotent(self) -> bool: ...
     def set_idempotent(self, idempotent: bool) -> None: ...
     def statements_count(self) -> int: ...
+    @property
+    def statements(self) -> list[str]: ...
+    def __repr__(self) -> str: ...
+
+class Migration:
+    """A CQL migration file split into statements"""
+
+    version: int
+    name: str
+    path: str
+    statements: list[str]
+
+    def __repr__(self) -> str: ...
+
+class MigrationRunner:
+    """Applies CQL migration files in order and records them in a versions table"""
+
+    directory: os.PathLike[str]
+    keyspace: str
+    table: str
+
+    def __init__(
+        self,
+        session: AsyncSession | Session,
+        directory: str | os.PathLike[str],
+        keyspace: str | None = None,
+        table: str = "schema_migrations",
+    ) -> None: ...
+    def migrations(self) -> list[Migration]: ...
+    def applied(self) -> list[int]: ...
+    def pending(self) -> list[Migration]: ...
+    def run(self, dry_run: bool = False, target: int | None = None) -> list[Migration]: ...
+    def __repr__(self) -> str: ...
+
+class Mapper:
+    """Table mapping with prepared statements cached per CQL text"""
+
+    table: str
+    columns: list[str]
+    partition_key: list[str]
+    clustering_key: list[str]
+    prepared_statements: int
+
+    def __init__(
+        self,
+        table: str,
+        columns: list[str],
+        partition_key: list[str],
+        clustering_key: list[str] | None = None,
+    ) -> None: ...
+    def save(
+        self,
+        session: AsyncSession | Session,
+        values: dict[str, Any],
+        ttl: int | None = None,
+        timestamp: int | None = None,
+    ) -> None: ...
+    def get(
+        self, session: AsyncSession | Session, key: dict[str, Any]
+    ) -> dict[str, Any] | None: ...
+    def delete(self, session: AsyncSession | Session, key: dict[str, Any]) -> None: ...
+    def find(
+        self,
+        session: AsyncSession | Session,
+        conditions: dict[str, Any] | None = None,
+        limit: int | None = None,
+        allow_filtering: bool = False,
+    ) -> list[dict[str, Any]]: ...
     def __repr__(self) -> str: ...
@@ -0,0 +1,57 @@
+"""
+Awaiting driver requests under trio
+
+Under an asyncio event loop, uvloop included, AsyncSession methods return asyncio futures.
+Without one they start the request as a concurrent.futures.Future and return wait(future),
+which trio code, and anyio code running on trio, can await.
+"""
+
+import sys
+from concurrent.futures import Future
+from types import ModuleType
+from typing import Any, TypeVar
+
+T = TypeVar("T")
+
+
+def check_event_loop() -> ModuleType:
+    """The trio module, when called from a trio task"""
+    trio = sys.modules.get("trio")
+    try:
+        if trio is not None:
+            trio.lowlevel.current_trio_token()
+            return trio
+    except RuntimeError:
+        pass
+    raise RuntimeError(
+        "AsyncSession needs a running asyncio or trio event loop, use Session in synchronous code"
+    )
+
+
+async def wait(future: "Future[T]") -> T:
+    """Wait for the future without blocking the event loop, cancelling it when cancelled"""
+    trio = check_event_loop()
+    task = trio.lowlevel.current_task()
+    token = trio.lowlevel.current_trio_token()
+    waiting = True
+
+    def wake() -> None:
+        if waiting:
+            trio.lowlevel.reschedule(task)
+
+    def on_done(_: Any) -> None:
+        # Runs on a driver thread
+        try:
+            token.run_sync_soon(wake)
+        except trio.RunFinishedError:
+            pass
+
+    def abort(_: Any) -> Any:
+        nonlocal waiting
+        waiting = False
+        future.cancel()
+        return trio.lowlevel.Abort.SUCCEEDED
+
+    future.add_done_callback(on_done)
+    await trio.lowlevel.wait_task_rescheduled(abort)
+    return future.result()
@@ -0,0 +1,99 @@
+"""Type stubs for rsylla._rsylla
+
+Generated by scripts/generate_stubs.py from the stubs of the rsylla package, do not edit.
+"""
+
+from . import (
+    AggregateMetadata as AggregateMetadata,
+    AlreadyExists as AlreadyExists,
+    AsyncSession as AsyncSession,
+    AuthenticationError as AuthenticationError,
+    Batch as Batch,
+    BigInt as BigInt,
+    BulkResult as BulkResult,
+    CircuitBreakerStats as CircuitBreakerStats,
+    ClusterInfo as ClusterInfo,
+    ColumnSpec as ColumnSpec,
+    ConfigurationError as ConfigurationError,
+    CqlSyntaxError as CqlSyntaxError,
+    CqlType as CqlType,
+    DeserializationError as DeserializationError,
+    EventStream as EventStream,
+    EventSubscription as EventSubscription,
+    ExecutionInfo as ExecutionInfo,
+    ExecutionProfile as ExecutionProfile,
+    ForkedSessionError as ForkedSessionError,
+    FullScan as FullScan,
+    FunctionFailure as FunctionFailure,
+    FunctionMetadata as FunctionMetadata,
+    HealthReport as HealthReport,
+    HostInfo as HostInfo,
+    Int as Int,
+    InvalidQuery as InvalidQuery,
+    IsBootstrapping as IsBootstrapping,
+    KeyspaceMetadata as KeyspaceMetadata,
+    Mapper as Mapper,
+    Metrics as Metrics,
+    Migration as Migration,
+    MigrationRunner as MigrationRunner,
+    NoHostAvailable as NoHostAvailable,
+    NodeHealth as NodeHealth,
+    NodeInfo as NodeInfo,
+    OperationTimedOut as OperationTimedOut,
+    Overloaded as Overloaded,
+    Page as Page,
+    PoolStats as PoolStats,
+    PrepareCacheStats as PrepareCacheStats,
+    PreparedStatement as PreparedStatement,
+    ProtocolError as ProtocolError,
+    Query as Query,
+    QueryBuilder as QueryBuilder,
+    QueryResult as QueryResult,
+    RateLimitReached as RateLimitReached,
+    ReadFailure as ReadFailure,
+    ReadTimeout as ReadTimeout,
+    RequestContext as RequestContext,
+    ResultTooLarge as ResultTooLarge,
+    Row as Row,
+    RowStream as RowStream,
+    SchemaChange as SchemaChange,
+    ScyllaError as ScyllaError,
+    ServerError as ServerError,
+    Session as Session,
+    SessionBuilder as SessionBuilder,
+    SessionConfig as SessionConfig,
+    SlowQuery as SlowQuery,
+    SmallInt as SmallInt,
+    Statement as Statement,
+    StatusChange as StatusChange,
+    StrictModeViolation as StrictModeViolation,
+    TableMetadata as TableMetadata,
+    Timeout as Timeout,
+    TinyInt as TinyInt,
+    TokenRange as TokenRange,
+    TopologyChange as TopologyChange,
+    TracingEvent as TracingEvent,
+    TracingInfo as TracingInfo,
+    TruncateError as TruncateError,
+    Unauthorized as Unauthorized,
+    Unavailable as Unavailable,
+    UserType as UserType,
+    WriteFailure as WriteFailure,
+    WriteTimeout as WriteTimeout,
+    connect as connect,
+    disable_logging as disable_logging,
+    enable_logging as enable_logging,
+    get_duration_mode as get_duration_mode,
+    get_float_policy as get_float_policy,
+    max_timeuuid as max_timeuuid,
+    min_timeuuid as min_timeuuid,
+    register_converter as register_converter,
+    set_duration_mode as set_duration_mode,
+    set_float_policy as set_float_policy,
+    shard_for as shard_for,
+    timeuuid_from_datetime as timeuuid_from_datetime,
+    timeuuid_now as timeuuid_now,
+    timeuuid_to_datetime as timeuuid_to_datetime,
+    token_for as token_for,
+    unregister_converter as unregister_converter,
+)
@@ -0,0 +1,407 @@
+"""
+Object mapper for rsylla
+
+Maps annotated classes to tables. Primary key columns are marked with
+``Annotated[..., PartitionKey]`` and ``Annotated[..., ClusteringKey]``, and
+``Column("name")`` maps an attribute to a differently named column.
+Classes decorated with ``@user_type`` map to user-defined types.
+"""
+
+import dataclasses
+import re
+import uuid
+from collections.abc import Awaitable, Callable
+from datetime import date, datetime, time
+from decimal import Decimal
+from ipaddress import IPv4Address, IPv6Address
+from types import NoneType, UnionType
+from typing import Annotated, Any, TypeVar, Union, get_args, get_origin, get_type_hints
+
+from ._rsylla import AsyncSession, Mapper, Session
+
+__all__ = ["ClusteringKey", "Column", "PartitionKey", "model", "user_type"]
+
+T = TypeVar("T")
+
+
+class PartitionKey:
+    """Marks an attribute as a partition key column, in declaration order"""
+
+
+class ClusteringKey:
+    """Marks an attribute as a clustering key column, in declaration order"""
+
+
+class Column:
+    """Maps an attribute to a column with a different name or CQL type"""
+
+    def __init__(self, name: str | None = None, cql_type: str | None = None) -> None:
+        self.name = name
+        self.cql_type = cql_type
+
+
+_NATIVE_TYPES: dict[Any, str] = {
+    bool: "boolean",
+    int: "bigint",
+    float: "double",
+    str: "text",
+    bytes: "blob",
+    uuid.UUID: "uuid",
+    datetime: "timestamp",
+    date: "date",
+    time: "time",
+    Decimal: "decimal",
+    IPv4Address: "inet",
+    IPv6Address: "inet",
+}
+
+_OPTION_NAME = re.compile(r"^[a-z_]+$")
+
+
+def _quote(name: str) -> str:
+    return '"' + name.replace('"', '""') + '"'
+
+
+def _string(value: str) -> str:
+    return "'" + value.replace("'", "''") + "'"
+
+
+def _option(value: Any) -> str:
+    if isinstance(value, bool):
+        return "true" if value else "false"
+    if isinstance(value, int | float):
+        return str(value)
+    if isinstance(value, str):
+        return _string(value)
+    if isinstance(value, dict):
+        entries = (f"{_string(str(key))}: {_string(_text(item))}" for key, item in value.items())
+        return "{" + ", ".join(entries) + "}"
+    raise TypeError(f"Unsupported table option value: {value!r}")
+
+
+def _text(value: Any) -> str:
+    if isinstance(value, bool):
+        return "true" if value else "false"
+    return str(value)
+
+
+def _unwrap(hint: Any) -> Any:
+    """Strips ``Annotated`` and ``Optional`` from a type hint"""
+    if get_origin(hint) is Annotated:
+        hint = get_args(hint)[0]
+    if get_origin(hint) in (Union, UnionType):
+        args = [arg for arg in get_args(hint) if arg is not NoneType]
+        if len(args) == 1:
+            return _unwrap(args[0])
+    return hint
+
+
+def _is_user_type(hint: Any) -> bool:
+    return isinstance(hint, type) and hasattr(hint, "__user_type__")
+
+
+def _field_types(cls: type, user_types: dict[str, list[tuple[str, str]]]) -> list[tuple[str, str]]:
+    hints = get_type_hints(cls, include_extras=True)
+    fields = []
+    for field in dataclasses.fields(cls):
+        hint = hints[field.name]
+        markers = get_args(hint)[1:] if get_origin(hint) is Annotated else ()
+        override = next((m.cql_type for m in markers if isinstance(m, Column)), None)
+        fields.append((field.name, override or _cql_type(hint, user_types)))
+    return fields
+
+
+def _cql_type(
+    hint: Any, user_types: dict[str, list[tuple[str, str]]], frozen: bool = False
+) -> str:
+    """CQL type of a hint; user types it refers to are added to ``user_types``, innermost first"""
+    hint = _unwrap(hint)
+    if _is_user_type(hint):
+        name = hint.__user_type__
+        if name not in user_types:
+            user_types[name] = _field_types(hint, user_types)
+        return f"frozen<{_quote(name)}>"
+    if hint in _NATIVE_TYPES:
+        return _NATIVE_TYPES[hint]
+
+    origin, args = get_origin(hint), get_args(hint)
+    if origin is list and len(args) == 1:
+        cql = f"list<{_cql_type(args[0], user_types, True)}>"
+    elif origin in (set, frozenset) and len(args) == 1:
+        cql = f"set<{_cql_type(args[0], user_types, True)}>"
+    elif origin is dict and len(args) == 2:
+        key, value = (_cql_type(arg, user_types, True) for arg in args)
+        cql = f"map<{key}, {value}>"
+    elif origin is tuple and args and ... not in args:
+        # Tuples are always frozen
+        return f"tuple<{', '.join(_cql_type(arg, user_types, True) for arg in args)}>"
+    else:
+        raise TypeError(f"Can't map {hint!r} to a CQL type, use Column(cql_type=...)")
+    return f"frozen<{cql}>" if frozen else cql
+
+
+def _to_cql(value: Any) -> Any:
+    """Turns user type instances, also inside collections, into dicts"""
+    if dataclasses.is_dataclass(value) and not isinstance(value, type):
+        fields = dataclasses.fields(value)
+        return {field.name: _to_cql(getattr(value, field.name)) for field in fields}
+    if isinstance(value, list | tuple):
+        return type(value)(_to_cql(item) for item in value)
+    if isinstance(value, set | frozenset):
+        # User types become dicts, which can't be members of a set
+        return [_to_cql(item) for item in value]
+    if isinstance(value, dict):
+        return {_to_cql(key): _to_cql(item) for key, item in value.items()}
+    return value
+
+
+def _from_cql(hint: Any, value: Any) -> Any:
+    """Turns dicts read from user type columns back into instances, and lists into sets"""
+    hint = _unwrap(hint)
+    if value is None:
+        return None
+    if _is_user_type(hint):
+        hints = get_type_hints(hint, include_extras=True)
+        return hint(**{name: _from_cql(hints.get(name), item) for name, item in value.items()})
+    origin, args = get_origin(hint), get_args(hint)
+    if origin is list and args:
+        return [_from_cql(args[0], item) for item in value]
+    if origin is tuple and args and ... not in args:
+        return tuple(_from_cql(arg, item) for arg, item in zip(args, value, strict=False))
+    if origin is dict and len(args) == 2:
+        return {key: _from_cql(args[1], item) for key, item in value.items()}
+    if origin in (set, frozenset) and args:
+        items = [_from_cql(args[0], item) for item in value]
+        try:
+            return origin(items)
+        except TypeError:
+            return items
+    return value
+
+
+def _then(session: AsyncSession | Session, result: Any, convert: Callable[[Any], Any]) -> Any:
+    """Convert the result of a ``Mapper`` call, awaiting it first for an ``AsyncSession``"""
+    if isinstance(session, AsyncSession):
+
+        async def converted() -> Any:
+            return convert(await result)
+
+        return converted()
+    return convert(result)
+
+
+def _needs_conversion(hint: Any) -> bool:
+    """Whether values of the hint are read as something else (dicts or lists)"""
+    hint = _unwrap(hint)
+    if _is_user_type(hint) or get_origin(hint) in (set, frozenset):
+        return True
+    return any(_needs_conversion(arg) for arg in get_args(hint))
+
+
+def user_type(name: str) -> Callable[[type[T]], type[T]]:
+    """Map a class to the user-defined type ``name`` in the keyspace of the tables using it
+
+    The class is turned into a dataclass if it isn't one already. Fields are
+    mapped by name, and ``Column(cql_type=...)`` overrides the inferred type.
+    """
+
+    def decorate(cls: type[T]) -> type[T]:
+        if not dataclasses.is_dataclass(cls):
+            cls = dataclasses.dataclass(cls)
+        cls.__user_type__ = name  # type: ignore[attr-defined]
+        return cls
+
+    return decorate
+
+
+def model(
+    table: str,
+    compaction: dict[str, Any] | None = None,
+    clustering_order: dict[str, str] | None = None,
+    options: dict[str, Any] | None = None,
+) -> Callable[[type[T]], type[T]]:
+    """Map a class to ``table`` (optionally ``keyspace.table``)
+
+    The class is turned into a dataclass if it isn't one already and gains
+    ``save``, ``delete``, ``get``, ``find`` and ``sync_table`` methods.
+    Statements are prepared on first use and cached by the class's ``Mapper``.
+    ``compaction``, ``clustering_order`` (attribute to ``ASC`` or ``DESC``) and
+    ``options`` are only used by ``sync_table`` when it creates the table.
+    """
+
+    def decorate(cls: type[T]) -> type[T]:
+        if not dataclasses.is_dataclass(cls):
+            cls = dataclasses.dataclass(cls)
+
+        attributes: dict[str, str] = {}
+        partition_key: list[str] = []
+        clustering_key: list[str] = []
+        column_hints: dict[str, Any] = {}
+        cql_types: dict[str, str] = {}
+        hints = get_type_hints(cls, include_extras=True)
+        for field in dataclasses.fields(cls):
+            attribute = column = field.name
+            hint = hints.get(attribute)
+            markers = get_args(hint)[1:] if get_origin(hint) is Annotated else ()
+            for marker in markers:
+                if isinstance(marker, Column):
+                    column = marker.name or column
+                    if marker.cql_type:
+                        cql_types[column] = marker.cql_type
+            attributes[attribute] = column
+            column_hints[column] = hint
+            if PartitionKey in markers:
+                partition_key.append(column)
+            if ClusteringKey in markers:
+                clustering_key.append(column)
+
+        mapper = Mapper(table, list(attributes.values()), partition_key, clustering_key)
+        columns = {column: attribute for attribute, column in attributes.items()}
+        # Only columns holding user types or sets need converting
+        converted = {column for column, hint in column_hints.items() if _needs_conversion(hint)}
+
+        def to_values(obj: Any) -> dict[str, Any]:
+            values = {column: getattr(obj, attribute) for attribute, column in attributes.items()}
+            for column in converted:
+                values[column] = _to_cql(values[column])
+            return values
+
+        def from_row(row: dict[str, Any]) -> Any:
+            return cls(
+                **{
+                    columns[column]: (
+                        _from_cql(column_hints[column], value) if column in converted else value
+                    )
+                    for column, value in row.items()
+                }
+            )
+
+        def schema() -> tuple[dict[str, list[tuple[str, str]]], dict[str, str]]:
+            user_types: dict[str, list[tuple[str, str]]] = {}
+            types = {
+                column: cql_types.get(column) or _cql_type(hint, user_types)
+                for column, hint in column_hints.items()
+            }
+            return user_types, types
+
+        def create_table(name: str, types: dict[str, str]) -> str:
+            definitions = [f"{_quote(column)} {cql}" for column, cql in types.items()]
+            key = ", ".join(_quote(column) for column in partition_key)
+            primary_key = ", ".join([f"({key})", *(_quote(c) for c in clustering_key)])
+            cql = f"CREATE TABLE IF NOT EXISTS {name} ({', '.join(definitions)}, "
+            cql += f"PRIMARY KEY ({primary_key}))"
+
+            properties = []
+            if clustering_order:
+                unknown = set(clustering_order) - {columns[c] for c in clustering_key}
+                if unknown:
+                    raise ValueError(f"Not clustering key attributes: {', '.join(sorted(unknown))}")
+                order = []
+                for column in clustering_key:
+                    direction = clustering_order.get(columns[column], "ASC").upper()
+                    if direction not in ("ASC", "DESC"):
+                        raise ValueError(f"Invalid clustering order: {direction}")
+                    order.append(f"{_quote(column)} {direction}")
+                properties.append(f"CLUSTERING ORDER BY ({', '.join(order)})")
+            if compaction:
+                properties.append(f"compaction = {_option(compaction)}")
+            for option, value in (options or {}).items():
+                if not _OPTION_NAME.match(option):
+                    raise ValueError(f"Invalid table option: {option}")
+                properties.append(f"{option} = {_option(value)}")
+            if properties:
+                cql += " WITH " + " AND ".join(properties)
+            return cql
+
+        def migration(keyspace: str, metadata: Any) -> list[str]:
+            if metadata is None:
+                raise ValueError(f"Unknown keyspace: {keyspace}")
+
+            user_types, types = schema()
+            statements = []
+            for type_name, fields in user_types.items():
+                qualified = f"{_quote(keyspace)}.{_quote(type_name)}"
+                existing = metadata.user_types.get(type_name)
+                if existing is None:
+                    definitions = ", ".join(f"{_quote(field)} {cql}" for field, cql in fields)
+                    statements.append(f"CREATE TYPE IF NOT EXISTS {qualified} ({definitions})")
+                    continue
+                for field, cql in fields:
+                    if field not in existing.field_names:
+                        statements.append(f"ALTER TYPE {qualified} ADD {_quote(field)} {cql}")
+
+            _, _, name = table.rpartition(".")
+            qualified = f"{_quote(keyspace)}.{_quote(name)}"
+            existing = metadata.tables.get(name)
+            if existing is None:
+                statements.append(create_table(qualified, types))
+            else:
+                for column, cql in types.items():
+                    if column not in existing.columns:
+                        statements.append(f"ALTER TABLE {qualified} ADD {_quote(column)} {cql}")
+            return statements
+
+        def sync_table(
+            klass: type, session: AsyncSession | Session
+        ) -> list[str] | Awaitable[list[str]]:
+            keyspace = table.rpartition(".")[0] or session.keyspace or ""
+            if not keyspace:
+                raise ValueError(f"No keyspace given for table {table} and the session has none")
+
+            if isinstance(session, AsyncSession):
+
+                async def sync() -> list[str]:
+                    metadata = await session.get_keyspace_metadata(keyspace, refresh=True)
+                    statements = migration(keyspace, metadata)
+                    for statement in statements:
+                        await session.execute(statement)
+                    return statements
+
+                return sync()
+
+            statements = migration(keyspace, session.get_keyspace_metadata(keyspace, refresh=True))
+            for statement in statements:
+                session.execute(statement)
+            return statements
+
+        def key_values(key: dict[str, Any]) -> dict[str, Any]:
+            unknown = set(key) - set(attributes)
+            if unknown:
+                raise ValueError(f"Unknown attributes for {table}: {', '.join(sorted(unknown))}")
+            return {attributes[attribute]: value for attribute, value in key.items()}
+
+        # Awaitables for an AsyncSession, results for a blocking Session
+        def save(
+            self: Any,
+            session: AsyncSession | Session,
+            ttl: int | None = None,
+            timestamp: int | None = None,
+        ) -> Any:
+            return mapper.save(session, to_values(self), ttl, timestamp)
+
+        def delete(self: Any, session: AsyncSession | Session) -> Any:
+            return mapper.delete(session, to_values(self))
+
+        def get(klass: type, session: AsyncSession | Session, **key: Any) -> Any:
+            row = mapper.get(session, key_values(key))
+            return _then(session, row, lambda row: None if row is None else from_row(row))
+
+        def find(
+            klass: type,
+            session: AsyncSession | Session,
+            limit: int | None = None,
+            allow_filtering: bool = False,
+            **conditions: Any,
+        ) -> Any:
+            rows = mapper.find(session, key_values(conditions), limit, allow_filtering)
+            return _then(session, rows, lambda rows: [from_row(row) for row in rows])
+
+        cls.__mapper__ = mapper  # type: ignore[attr-defined]
+        cls.save = save  # type: ignore[attr-defined]
+        cls.delete = delete  # type: ignore[attr-defined]
+        cls.get = classmethod(get)  # type: ignore[attr-defined]
+        cls.find = classmethod(find)  # type: ignore[attr-defined]
+        cls.sync_table = classmethod(sync_table)  # type: ignore[attr-defined]
+        return cls
+
+    return decorate
@@ -0,0 +1,32 @@
+"""
+Prometheus integration for rsylla sessions
+
+Requires the optional ``prometheus_client`` package.
+"""
+
+from collections.abc import Iterator
+from typing import Any
+
+from prometheus_client.parser import text_string_to_metric_families
+from prometheus_client.registry import REGISTRY, CollectorRegistry
+
+from ._rsylla import AsyncSession, Session
+
+
+class RsyllaCollector:
+    """Collector exposing the driver metrics of a session"""
+
+    def __init__(self, session: Session | AsyncSession) -> None:
+        self._session = session
+
+    def collect(self) -> Iterator[Any]:
+        yield from text_string_to_metric_families(self._session.metrics_prometheus())
+
+
+def register(
+    session: Session | AsyncSession, registry: CollectorRegistry = REGISTRY
+) -> RsyllaCollector:
+    """Register the metrics of ``session`` with a Prometheus registry"""
+    collector = RsyllaCollector(session)
+    registry.register(collector)
+    return collector
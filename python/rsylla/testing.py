@@ -0,0 +1,693 @@
+"""
+Test doubles for rsylla sessions
+
+``MockSession`` and ``AsyncMockSession`` answer statements from expectations
+registered with ``expect()`` instead of a cluster, and record every statement
+they execute, so code using a session can be unit tested without ScyllaDB.
+
+``RecordingSession`` saves the statements run through a real session with
+their results, which ``ReplaySession`` and ``AsyncReplaySession`` serve back.
+"""
+
+import inspect
+import os
+import pickle
+import re
+from collections.abc import Awaitable, Callable, Iterable
+from concurrent.futures import Future
+from dataclasses import dataclass
+from typing import Any
+
+from ._rsylla import (
+    Batch,
+    ExecutionInfo,
+    PreparedStatement,
+    Query,
+    QueryBuilder,
+    QueryResult,
+    Row,
+    Statement,
+)
+
+__all__ = [
+    "AsyncMockSession",
+    "AsyncReplaySession",
+    "Call",
+    "Expectation",
+    "MockBulkResult",
+    "MockPreparedStatement",
+    "MockSession",
+    "Recording",
+    "RecordingSession",
+    "ReplaySession",
+    "UnexpectedQuery",
+    "load_recordings",
+]
+
+_ANY: Any = object()
+_WHITESPACE = re.compile(r"\s+")
+_NAMED_MARKER = re.compile(r"(?<!:):([A-Za-z_][A-Za-z0-9_]*)")
+# Bumped when the layout of recording files changes
+_RECORDING_VERSION = 1
+
+
+def _normalize(query: str) -> str:
+    return _WHITESPACE.sub(" ", query).strip().rstrip(";").strip()
+
+
+class UnexpectedQuery(AssertionError):
+    """A statement matched no expectation of a strict mock session"""
+
+
+@dataclass(frozen=True)
+class Call:
+    """A statement executed by a mock session"""
+
+    query: str
+    values: dict[str, Any] | None
+    method: str
+
+
+class Expectation:
+    """Rows or an error a mock session answers matching statements with"""
+
+    def __init__(
+        self,
+        query: str | re.Pattern[str],
+        rows: list[dict[str, Any]] | None,
+        values: dict[str, Any] | Callable[[dict[str, Any] | None], bool] | None,
+        error: BaseException | type[BaseException] | None,
+        times: int | None,
+        result: QueryResult | None = None,
+    ) -> None:
+        self.query = query
+        self.rows = rows
+        self.values = values
+        self.error = error
+        self.times = times
+        self.result = result
+        self.calls: list[Call] = []
+
+    @property
+    def exhausted(self) -> bool:
+        return self.times is not None and len(self.calls) >= self.times
+
+    def matches(self, query: str, values: dict[str, Any] | None) -> bool:
+        if isinstance(self.query, re.Pattern):
+            if self.query.search(query) is None:
+                return False
+        elif _normalize(self.query) != _normalize(query):
+            return False
+        if self.values is None:
+            return True
+        if callable(self.values):
+            return bool(self.values(values))
+        return self.values == (values or {})
+
+    def answer(self, call: Call) -> QueryResult:
+        self.calls.append(call)
+        if self.error is not None:
+            raise self.error
+        if self.result is not None:
+            # A copy, so every call iterates the rows from the start
+            return pickle.loads(pickle.dumps(self.result))
+        return _result(self.rows)
+
+    def __repr__(self) -> str:
+        query = self.query.pattern if isinstance(self.query, re.Pattern) else self.query
+        return f"Expectation({query!r}, calls={len(self.calls)}, times={self.times})"
+
+
+class MockPreparedStatement:
+    """Stands in for the PreparedStatement returned by a mock session's prepare()"""
+
+    def __init__(self, query: str) -> None:
+        self._query = query
+        self._idempotent = False
+
+    def get_statement(self) -> str:
+        return self._query
+
+    def get_variable_names(self) -> list[str]:
+        return list(dict.fromkeys(_NAMED_MARKER.findall(self._query)))
+
+    def is_lwt(self) -> bool:
+        return re.search(r"\bIF\b", self._query, re.IGNORECASE) is not None
+
+    def is_idempotent(self) -> bool:
+        return self._idempotent
+
+    def set_idempotent(self, idempotent: bool) -> "MockPreparedStatement":
+        self._idempotent = idempotent
+        return self
+
+    # Execution options have no effect on a mock session
+    def with_consistency(self, consistency: str) -> "MockPreparedStatement":
+        return self
+
+    def with_serial_consistency(self, serial_consistency: str) -> "MockPreparedStatement":
+        return self
+
+    def with_page_size(self, page_size: int) -> "MockPreparedStatement":
+        return self
+
+    def with_timestamp(self, timestamp: int) -> "MockPreparedStatement":
+        return self
+
+    def with_tracing(self, tracing: bool) -> "MockPreparedStatement":
+        return self
+
+    def with_profile(self, profile: Any) -> "MockPreparedStatement":
+        return self
+
+    def __repr__(self) -> str:
+        return f"MockPreparedStatement({self._query!r})"
+
+
+@dataclass
+class MockBulkResult:
+    """Outcome of execute_many() on a mock session, shaped like BulkResult"""
+
+    succeeded: int
+    failed: int
+    rows: int
+    errors: list[tuple[int, BaseException]]
+
+    def __bool__(self) -> bool:
+        return self.failed == 0
+
+
+def _result(rows: list[dict[str, Any]] | None) -> QueryResult:
+    info = ExecutionInfo._from_snapshot("mock", None, 1, 0, 0, 0.0, 0, 0, 1, 0.0, 0.0)
+    if rows is None:
+        return QueryResult._from_snapshot([], None, [], info, "void", None, None)
+    snapshot = [Row._from_snapshot(list(row), list(row.values())) for row in rows]
+    return QueryResult._from_snapshot(snapshot, None, [], info, "rows", None, None)
+
+
+def _statement(query: Any, values: dict[str, Any] | None) -> tuple[str, dict[str, Any] | None]:
+    if isinstance(query, str):
+        return query, values
+    if isinstance(query, Statement):
+        return query.query, values
+    if isinstance(query, Query):
+        return query.get_contents(), values
+    if isinstance(query, QueryBuilder):
+        return query.cql, {**query.values, **(values or {})}
+    if isinstance(query, PreparedStatement | MockPreparedStatement):
+        return query.get_statement(), values
+    raise TypeError(f"Unsupported statement for a mock session: {type(query).__name__}")
+
+
+class _MockSessionBase:
+    def __init__(self, keyspace: str | None = None, strict: bool = True) -> None:
+        self._keyspace = keyspace
+        self._strict = strict
+        self._closed = False
+        self.expectations: list[Expectation] = []
+        self.calls: list[Call] = []
+
+    def expect(
+        self,
+        query: str | re.Pattern[str],
+        rows: list[dict[str, Any]] | None = None,
+        *,
+        values: dict[str, Any] | Callable[[dict[str, Any] | None], bool] | None = None,
+        error: BaseException | type[BaseException] | None = None,
+        times: int | None = None,
+        result: QueryResult | None = None,
+    ) -> Expectation:
+        """Answer statements matching ``query`` with ``rows``, or raise ``error``
+
+        A string matches statements equal to it up to whitespace and a trailing
+        semicolon, a compiled pattern those it's found in. ``values``, a dict or
+        a predicate, narrows the match to the bound values. Expectations are
+        tried in the order they were registered, skipping those already used
+        ``times`` times. ``result`` answers with a copy of a ``QueryResult``
+        instead of ``rows``.
+        """
+        expectation = Expectation(query, rows, values, error, times, result)
+        self.expectations.append(expectation)
+        return expectation
+
+    def calls_to(self, query: str | re.Pattern[str]) -> list[Call]:
+        """Calls executing statements matching ``query``, as with ``expect()``"""
+        matcher = Expectation(query, None, None, None, None)
+        return [call for call in self.calls if matcher.matches(call.query, call.values)]
+
+    def assert_executed(
+        self, query: str | re.Pattern[str], values: dict[str, Any] | None = _ANY
+    ) -> Call:
+        """Last call executing ``query``, with ``values`` bound if given"""
+        calls = self.calls_to(query)
+        if values is not _ANY:
+            calls = [call for call in calls if (call.values or {}) == (values or {})]
+        if not calls:
+            executed = "\n".join(f"  {call.query} {call.values}" for call in self.calls)
+            raise AssertionError(
+                f"{query!r} was not executed"
+                + ("" if values is _ANY else f" with {values}")
+                + (f", executed:\n{executed}" if executed else ", nothing was executed")
+            )
+        return calls[-1]
+
+    def assert_not_executed(self, query: str | re.Pattern[str]) -> None:
+        calls = self.calls_to(query)
+        if calls:
+            raise AssertionError(f"{query!r} was executed {len(calls)} time(s)")
+
+    def assert_expectations_met(self) -> None:
+        """Check every expectation was used, and those with ``times`` that often"""
+        unmet = [
+            expectation
+            for expectation in self.expectations
+            if not expectation.calls
+            or (expectation.times is not None and len(expectation.calls) != expectation.times)
+        ]
+        if unmet:
+            raise AssertionError(f"Unmet expectations: {unmet}")
+
+    def reset(self) -> None:
+        """Forget the expectations and recorded calls"""
+        self.expectations.clear()
+        self.calls.clear()
+
+    def _run(self, method: str, query: str, values: dict[str, Any] | None) -> QueryResult:
+        if self._closed:
+            raise RuntimeError("Session is closed")
+        call = Call(query, values, method)
+        self.calls.append(call)
+        for expectation in self.expectations:
+            if not expectation.exhausted and expectation.matches(query, values):
+                return expectation.answer(call)
+        if self._strict:
+            raise UnexpectedQuery(f"No expectation matches {query!r} with values {values}")
+        return _result(None)
+
+    def _execute(self, query: Any, values: dict[str, Any] | None) -> QueryResult:
+        return self._run("execute", *_statement(query, values))
+
+    def _execute_prepared(
+        self, prepared: MockPreparedStatement, values: dict[str, Any] | None
+    ) -> QueryResult:
+        return self._run("execute_prepared", prepared.get_statement(), values)
+
+    def _execute_concurrent(
+        self, statements_and_params: Iterable[Any]
+    ) -> list[tuple[bool, QueryResult | Exception]]:
+        results: list[tuple[bool, QueryResult | Exception]] = []
+        for item in statements_and_params:
+            query, values = item if isinstance(item, tuple) else (item, None)
+            try:
+                results.append((True, self._execute(query, values)))
+            except UnexpectedQuery:
+                raise
+            except Exception as err:
+                results.append((False, err))
+        return results
+
+    def _execute_many(
+        self, prepared: MockPreparedStatement, params_list: Iterable[dict[str, Any]]
+    ) -> MockBulkResult:
+        params_list = list(params_list)
+        for index, values in enumerate(params_list):
+            if not isinstance(values, dict):
+                raise TypeError(f"params_list[{index}] must be a dict, got {type(values).__name__}")
+        succeeded = rows = 0
+        errors: list[tuple[int, BaseException]] = []
+        for index, values in enumerate(params_list):
+            try:
+                result = self._run("execute_many", prepared.get_statement(), values)
+            except UnexpectedQuery:
+                raise
+            except Exception as err:
+                errors.append((index, err))
+            else:
+                succeeded += 1
+                rows += len(result)
+        return MockBulkResult(succeeded, len(errors), rows, errors)
+
+    def _batch(self, batch: Batch, values: list[dict[str, Any]]) -> QueryResult:
+        statements = batch.statements
+        if len(values) != len(statements):
+            raise ValueError(
+                f"Batch has {len(statements)} statements but {len(values)} value sets"
+            )
+        for query, bound in zip(statements, values, strict=True):
+            self._run("batch", query, bound)
+        return _result(None)
+
+    def prepare(self, query: str) -> MockPreparedStatement:
+        if self._closed:
+            raise RuntimeError("Session is closed")
+        return MockPreparedStatement(query)
+
+    def get_keyspace(self) -> str | None:
+        return self._keyspace
+
+    @property
+    def keyspace(self) -> str | None:
+        return self._keyspace
+
+    @property
+    def closed(self) -> bool:
+        return self._closed
+
+    @property
+    def in_flight(self) -> int:
+        return 0
+
+    @property
+    def waiting_requests(self) -> int:
+        return 0
+
+    def close(self) -> None:
+        self._closed = True
+
+    def shutdown(self, grace_period_ms: int | None = None) -> bool:
+        self._closed = True
+        return True
+
+    def __enter__(self) -> Any:
+        return self
+
+    def __exit__(self, exc_type: Any, exc_value: Any, traceback: Any) -> bool:
+        self.close()
+        return False
+
+    async def __aenter__(self) -> Any:
+        return self
+
+    async def __aexit__(self, exc_type: Any, exc_value: Any, traceback: Any) -> bool:
+        self.close()
+        return False
+
+
+class MockSession(_MockSessionBase):
+    """In-memory stand-in for a blocking Session
+
+    Statements matching no expectation raise ``UnexpectedQuery``, or return an
+    empty result with ``strict=False``.
+    """
+
+    def execute(
+        self,
+        query: str | Statement | Query | QueryBuilder,
+        values: dict[str, Any] | None = None,
+        timestamp: int | None = None,
+        page_size: int | None = None,
+        timeout_ms: int | None = None,
+        raw: bool = False,
+    ) -> QueryResult:
+        return self._execute(query, values)
+
+    def execute_future(
+        self,
+        query: str | Statement | Query | QueryBuilder,
+        values: dict[str, Any] | None = None,
+        timestamp: int | None = None,
+        page_size: int | None = None,
+        timeout_ms: int | None = None,
+        raw: bool = False,
+    ) -> "Future[QueryResult]":
+        future: Future[QueryResult] = Future()
+        try:
+            future.set_result(self._execute(query, values))
+        except Exception as err:
+            future.set_exception(err)
+        return future
+
+    def query(self, query: Query, values: dict[str, Any] | None = None) -> QueryResult:
+        return self._execute(query, values)
+
+    def execute_prepared(
+        self,
+        prepared: MockPreparedStatement,
+        values: dict[str, Any] | None = None,
+        timeout_ms: int | None = None,
+        raw: bool = False,
+    ) -> QueryResult:
+        return self._execute_prepared(prepared, values)
+
+    def execute_concurrent(
+        self, statements_and_params: Iterable[Any], concurrency: int = 100
+    ) -> list[tuple[bool, QueryResult | Exception]]:
+        return self._execute_concurrent(statements_and_params)
+
+    def execute_many(
+        self,
+        prepared: MockPreparedStatement,
+        params_list: Iterable[dict[str, Any]],
+        concurrency: int = 100,
+    ) -> MockBulkResult:
+        return self._execute_many(prepared, params_list)
+
+    def batch(
+        self, batch: Batch, values: list[dict[str, Any]], timestamp: int | None = None
+    ) -> QueryResult:
+        return self._batch(batch, values)
+
+    def use_keyspace(self, keyspace_name: str, case_sensitive: bool = False) -> None:
+        self._keyspace = keyspace_name
+
+
+class AsyncMockSession(_MockSessionBase):
+    """In-memory stand-in for an AsyncSession, with the same behavior as MockSession"""
+
+    async def execute(
+        self,
+        query: str | Statement | Query | QueryBuilder,
+        values: dict[str, Any] | None = None,
+        timestamp: int | None = None,
+        page_size: int | None = None,
+        timeout_ms: int | None = None,
+        raw: bool = False,
+    ) -> QueryResult:
+        return self._execute(query, values)
+
+    def execute_future(
+        self,
+        query: str | Statement | Query | QueryBuilder,
+        values: dict[str, Any] | None = None,
+        timestamp: int | None = None,
+        page_size: int | None = None,
+        timeout_ms: int | None = None,
+        raw: bool = False,
+    ) -> "Future[QueryResult]":
+        future: Future[QueryResult] = Future()
+        try:
+            future.set_result(self._execute(query, values))
+        except Exception as err:
+            future.set_exception(err)
+        return future
+
+    async def query(self, query: Query, values: dict[str, Any] | None = None) -> QueryResult:
+        return self._execute(query, values)
+
+    async def prepare(self, query: str) -> MockPreparedStatement:  # type: ignore[override]
+        return super().prepare(query)
+
+    async def execute_prepared(
+        self,
+        prepared: MockPreparedStatement,
+        values: dict[str, Any] | None = None,
+        timeout_ms: int | None = None,
+        raw: bool = False,
+    ) -> QueryResult:
+        return self._execute_prepared(prepared, values)
+
+    async def execute_concurrent(
+        self, statements_and_params: Iterable[Any], concurrency: int = 100
+    ) -> list[tuple[bool, QueryResult | Exception]]:
+        return self._execute_concurrent(statements_and_params)
+
+    async def execute_many(
+        self,
+        prepared: MockPreparedStatement,
+        params_list: Iterable[dict[str, Any]],
+        concurrency: int = 100,
+    ) -> MockBulkResult:
+        return self._execute_many(prepared, params_list)
+
+    async def batch(
+        self, batch: Batch, values: list[dict[str, Any]], timestamp: int | None = None
+    ) -> QueryResult:
+        return self._batch(batch, values)
+
+    async def use_keyspace(self, keyspace_name: str, case_sensitive: bool = False) -> None:
+        self._keyspace = keyspace_name
+
+
+@dataclass
+class Recording:
+    """A statement run through a RecordingSession, with its result or error"""
+
+    method: str
+    query: str
+    values: dict[str, Any] | None
+    result: QueryResult | None
+    error: BaseException | None
+
+
+class RecordingSession:
+    """Runs statements on a Session or AsyncSession, recording them with their results
+
+    Recordings are written to ``path`` by ``save()``, which ``close()`` and
+    leaving the context manager call. Methods other than ``execute()``,
+    ``query()``, ``execute_prepared()`` and ``batch()`` are passed on to the
+    session unrecorded.
+    """
+
+    def __init__(self, session: Any, path: str | os.PathLike[str]) -> None:
+        self._session = session
+        self._path = path
+        self.recordings: list[Recording] = []
+
+    def __getattr__(self, name: str) -> Any:
+        return getattr(self._session, name)
+
+    def _record(
+        self,
+        method: str,
+        statements: list[tuple[str, dict[str, Any] | None]],
+        run: Callable[[], Any],
+    ) -> Any:
+        try:
+            result = run()
+        except Exception as err:
+            self._add(method, statements, None, err)
+            raise
+        if inspect.isawaitable(result):
+            return self._record_awaited(method, statements, result)
+        self._add(method, statements, result, None)
+        return result
+
+    async def _record_awaited(
+        self,
+        method: str,
+        statements: list[tuple[str, dict[str, Any] | None]],
+        awaitable: Awaitable[Any],
+    ) -> Any:
+        try:
+            result = await awaitable
+        except Exception as err:
+            self._add(method, statements, None, err)
+            raise
+        self._add(method, statements, result, None)
+        return result
+
+    def _add(
+        self,
+        method: str,
+        statements: list[tuple[str, dict[str, Any] | None]],
+        result: QueryResult | None,
+        error: BaseException | None,
+    ) -> None:
+        for query, values in statements:
+            self.recordings.append(Recording(method, query, values, result, error))
+
+    def execute(
+        self,
+        query: str | Statement | Query | QueryBuilder,
+        values: dict[str, Any] | None = None,
+        **kwargs: Any,
+    ) -> Any:
+        return self._record(
+            "execute",
+            [_statement(query, values)],
+            lambda: self._session.execute(query, values, **kwargs),
+        )
+
+    def query(self, query: Query, values: dict[str, Any] | None = None) -> Any:
+        return self._record(
+            "execute", [_statement(query, values)], lambda: self._session.query(query, values)
+        )
+
+    def execute_prepared(
+        self, prepared: PreparedStatement, values: dict[str, Any] | None = None, **kwargs: Any
+    ) -> Any:
+        return self._record(
+            "execute_prepared",
+            [_statement(prepared, values)],
+            lambda: self._session.execute_prepared(prepared, values, **kwargs),
+        )
+
+    # Recorded as one recording per statement, as a replaying session runs them
+    def batch(self, batch: Batch, values: list[dict[str, Any]], **kwargs: Any) -> Any:
+        return self._record(
+            "batch",
+            list(zip(batch.statements, values, strict=False)),
+            lambda: self._session.batch(batch, values, **kwargs),
+        )
+
+    def save(self) -> None:
+        with open(self._path, "wb") as file:
+            pickle.dump({"version": _RECORDING_VERSION, "recordings": self.recordings}, file)
+
+    def close(self) -> None:
+        self.save()
+        self._session.close()
+
+    def __enter__(self) -> "RecordingSession":
+        return self
+
+    def __exit__(self, exc_type: Any, exc_value: Any, traceback: Any) -> bool:
+        self.close()
+        return False
+
+    async def __aenter__(self) -> "RecordingSession":
+        return self
+
+    async def __aexit__(self, exc_type: Any, exc_value: Any, traceback: Any) -> bool:
+        self.close()
+        return False
+
+
+def load_recordings(path: str | os.PathLike[str]) -> list[Recording]:
+    """Recordings saved by a RecordingSession
+
+    Files are pickles, so only recordings from trusted sources may be loaded.
+    """
+    with open(path, "rb") as file:
+        contents = pickle.load(file)
+    if not isinstance(contents, dict) or contents.get("version") != _RECORDING_VERSION:
+        raise ValueError(f"{os.fspath(path)} is not a recording of this version of rsylla")
+    recordings: list[Recording] = contents["recordings"]
+    return recordings
+
+
+# Each recording answers one statement, in order, with the last one of a statement repeated
+# once the others are used up
+def _replay(session: _MockSessionBase, recordings: list[Recording]) -> None:
+    last: list[Recording] = []
+    for recording in reversed(recordings):
+        if not any(
+            other.query == recording.query and other.values == recording.values for other in last
+        ):
+            last.append(recording)
+    for recording in recordings:
+        session.expect(
+            recording.query,
+            values=recording.values or {},
+            error=recording.error,
+            times=None if any(recording is other for other in last) else 1,
+            result=recording.result,
+        )
+
+
+class ReplaySession(MockSession):
+    """MockSession answering statements with the results recorded by a RecordingSession"""
+
+    def __init__(self, path: str | os.PathLike[str], keyspace: str | None = None) -> None:
+        super().__init__(keyspace)
+        self.recordings = load_recordings(path)
+        _replay(self, self.recordings)
+
+
+class AsyncReplaySession(AsyncMockSession):
+    """AsyncMockSession answering statements with the results recorded by a RecordingSession"""
+
+    def __init__(self, path: str | os.PathLike[str], keyspace: str | None = None) -> None:
+        super().__init__(keyspace)
+        self.recordings = load_recordings(path)
+        _replay(self, self.recordings)
@@ -0,0 +1,516 @@
+"""
+Keep the type stubs of rsylla in sync with the compiled extension module
+
+The annotations of python/rsylla/__init__.pyi are written by hand. This script
+compares the stubs with the classes, methods and functions of the built
+module, regenerating every signature whose parameters differ, adding missing
+members and dropping removed ones, annotated with ``Any`` where there's no
+annotation to reuse. It also writes python/rsylla/_rsylla.pyi, re-exporting the
+stubs for the modules importing from the extension directly.
+
+Run it after ``maturin develop``:
+
+    python scripts/generate_stubs.py          # update the stubs
+    python scripts/generate_stubs.py --check  # fail if they're out of date
+"""
+
+import argparse
+import ast
+import inspect
+import json
+import sys
+from dataclasses import dataclass, field
+from pathlib import Path
+from types import ModuleType
+from typing import Any
+
+ROOT = Path(__file__).resolve().parent.parent
+STUB = ROOT / "python" / "rsylla" / "__init__.pyi"
+EXTENSION_STUB = ROOT / "python" / "rsylla" / "_rsylla.pyi"
+LINE_LENGTH = 100
+INDENT = "    "
+
+# Runtime classes declared in the stubs under another name, or split over several classes
+STUB_CLASSES = {"SessionBase": ["_SessionBase", "AsyncSession", "Session"]}
+
+# Dunder methods worth declaring, with their signatures
+DUNDERS = {
+    "__repr__": "(self) -> str",
+    "__str__": "(self) -> str",
+    "__len__": "(self) -> int",
+    "__bool__": "(self) -> bool",
+    "__int__": "(self) -> int",
+    "__index__": "(self) -> int",
+    "__float__": "(self) -> float",
+    "__hash__": "(self) -> int",
+    "__eq__": "(self, other: object) -> bool",
+    "__contains__": "(self, key: Any) -> bool",
+    "__getitem__": "(self, key: Any) -> Any",
+    "__iter__": "(self) -> Any",
+    "__next__": "(self) -> Any",
+    "__aiter__": "(self) -> Any",
+    "__anext__": "(self) -> Any",
+    "__enter__": "(self) -> Self",
+    "__exit__": "(self, exc_type: Any, exc_value: Any, traceback: Any) -> bool",
+    "__aenter__": "(self) -> Self",
+    "__aexit__": "(self, exc_type: Any, exc_value: Any, traceback: Any) -> bool",
+}
+
+GENERATED_HEADER = '''"""Type stubs for rsylla._rsylla
+
+Generated by scripts/generate_stubs.py from the stubs of the rsylla package, do not edit.
+"""
+'''
+
+
+@dataclass
+class Member:
+    """A method, property or attribute of a runtime class, or a module function"""
+
+    name: str
+    kind: str  # "method", "staticmethod", "classmethod", "property" or "dunder"
+    signature: inspect.Signature | None = None
+
+
+@dataclass
+class RuntimeClass:
+    name: str
+    bases: list[str]
+    exception: bool
+    constructor: inspect.Signature | None
+    members: dict[str, Member] = field(default_factory=dict)
+
+
+def _signature(obj: Any) -> inspect.Signature | None:
+    try:
+        return inspect.signature(obj)
+    except (TypeError, ValueError):
+        return None
+
+
+def introspect(module: ModuleType) -> tuple[dict[str, Member], dict[str, RuntimeClass]]:
+    functions: dict[str, Member] = {}
+    classes: dict[str, RuntimeClass] = {}
+    for name, obj in vars(module).items():
+        if name.startswith("_"):
+            continue
+        if isinstance(obj, type):
+            classes[name] = _introspect_class(obj)
+        elif callable(obj):
+            functions[name] = Member(name, "function", _signature(obj))
+    # Base classes the module doesn't export, like SessionBase
+    for cls in list(classes.values()):
+        for base in cls.bases:
+            if base not in classes and base in STUB_CLASSES:
+                runtime = getattr(module, cls.name).__mro__[1]
+                classes[base] = _introspect_class(runtime)
+    return functions, classes
+
+
+def _introspect_class(cls: type) -> RuntimeClass:
+    exception = issubclass(cls, BaseException)
+    bases = [base.__name__ for base in cls.__bases__ if base is not object]
+    constructor = None
+    if not exception and cls.__text_signature__ is not None:  # type: ignore[attr-defined]
+        constructor = _signature(cls)
+    runtime = RuntimeClass(cls.__name__, bases, exception, constructor)
+    if exception:
+        return runtime
+    for name, value in vars(cls).items():
+        if name in DUNDERS:
+            if value is not None:
+                runtime.members[name] = Member(name, "dunder")
+        elif name.startswith("_"):
+            continue
+        elif isinstance(value, staticmethod):
+            runtime.members[name] = Member(name, "staticmethod", _signature(value.__func__))
+        elif type(value).__name__ == "classmethod_descriptor":
+            runtime.members[name] = Member(name, "classmethod", _signature(getattr(cls, name)))
+        elif callable(value):
+            runtime.members[name] = Member(name, "method", _signature(value))
+        else:
+            runtime.members[name] = Member(name, "property")
+    return runtime
+
+
+def _default(value: Any) -> str:
+    if isinstance(value, str):
+        return json.dumps(value)
+    return repr(value)
+
+
+def _annotation(node: ast.arg | None) -> str:
+    if node is None or node.annotation is None:
+        return "Any"
+    return ast.unparse(node.annotation)
+
+
+def _stub_args(node: ast.FunctionDef | ast.AsyncFunctionDef) -> dict[str, ast.arg]:
+    args = node.args
+    return {arg.arg: arg for arg in [*args.posonlyargs, *args.args, *args.kwonlyargs]}
+
+
+def _parameters(
+    signature: inspect.Signature, stub: ast.FunctionDef | ast.AsyncFunctionDef | None, first: str
+) -> list[str]:
+    stub_args = _stub_args(stub) if stub is not None else {}
+    parameters = [first] if first else []
+    keyword_only = False
+    for parameter in signature.parameters.values():
+        if parameter.name in ("self", "cls") and parameter.kind in (
+            parameter.POSITIONAL_ONLY,
+            parameter.POSITIONAL_OR_KEYWORD,
+        ):
+            continue
+        if parameter.kind == parameter.VAR_POSITIONAL:
+            keyword_only = True
+            parameters.append(f"*{parameter.name}: {_annotation(stub_args.get(parameter.name))}")
+            continue
+        if parameter.kind == parameter.VAR_KEYWORD:
+            parameters.append(f"**{parameter.name}: {_annotation(stub_args.get(parameter.name))}")
+            continue
+        if parameter.kind == parameter.KEYWORD_ONLY and not keyword_only:
+            keyword_only = True
+            parameters.append("*")
+        text = f"{parameter.name}: {_annotation(stub_args.get(parameter.name))}"
+        if parameter.default is not parameter.empty:
+            text += f" = {_default(parameter.default)}"
+        parameters.append(text)
+    return parameters
+
+
+def _stub_parameters(node: ast.FunctionDef | ast.AsyncFunctionDef) -> list[tuple[str, str, str]]:
+    """(kind, name, default) of the parameters of a stub, without self and cls"""
+    args = node.args
+    parameters: list[tuple[str, str, str]] = []
+    positional = [*args.posonlyargs, *args.args]
+    defaults = [None] * (len(positional) - len(args.defaults)) + list(args.defaults)
+    for arg, default in zip(positional, defaults, strict=True):
+        if arg.arg in ("self", "cls"):
+            continue
+        parameters.append(("positional", arg.arg, ast.unparse(default) if default else ""))
+    if args.vararg is not None:
+        parameters.append(("var_positional", args.vararg.arg, ""))
+    for arg, default in zip(args.kwonlyargs, args.kw_defaults, strict=True):
+        parameters.append(("keyword", arg.arg, ast.unparse(default) if default else ""))
+    if args.kwarg is not None:
+        parameters.append(("var_keyword", args.kwarg.arg, ""))
+    return parameters
+
+
+def _runtime_parameters(signature: inspect.Signature) -> list[tuple[str, str, str]]:
+    kinds = {
+        inspect.Parameter.POSITIONAL_ONLY: "positional",
+        inspect.Parameter.POSITIONAL_OR_KEYWORD: "positional",
+        inspect.Parameter.VAR_POSITIONAL: "var_positional",
+        inspect.Parameter.KEYWORD_ONLY: "keyword",
+        inspect.Parameter.VAR_KEYWORD: "var_keyword",
+    }
+    parameters = []
+    for parameter in signature.parameters.values():
+        if parameter.name in ("self", "cls") and kinds[parameter.kind] == "positional":
+            continue
+        default = "" if parameter.default is parameter.empty else _default(parameter.default)
+        parameters.append((kinds[parameter.kind], parameter.name, default))
+    return parameters
+
+
+def _matches(signature: inspect.Signature, node: ast.FunctionDef | ast.AsyncFunctionDef) -> bool:
+    return _runtime_parameters(signature) == [
+        (kind, name, _normalize_default(default)) for kind, name, default in _stub_parameters(node)
+    ]
+
+
+def _normalize_default(default: str) -> str:
+    if not default:
+        return ""
+    try:
+        return _default(ast.literal_eval(default))
+    except ValueError:
+        return default
+
+
+def _format_def(
+    indent: str,
+    name: str,
+    parameters: list[str],
+    returns: str,
+    is_async: bool = False,
+    decorators: tuple[str, ...] = (),
+) -> str:
+    """A stub definition wrapped as black would"""
+    prefix = f"{indent}{'async ' if is_async else ''}def {name}("
+    suffix = f") -> {returns}: ..."
+    lines = [f"{indent}@{decorator}" for decorator in decorators]
+    one_line = prefix + ", ".join(parameters) + suffix
+    inner = indent + INDENT
+    if len(one_line) <= LINE_LENGTH:
+        lines.append(one_line)
+    elif len(inner + ", ".join(parameters)) <= LINE_LENGTH and "*" not in parameters:
+        lines += [prefix, inner + ", ".join(parameters), indent + suffix]
+    else:
+        lines += [prefix, *(f"{inner}{parameter}," for parameter in parameters), indent + suffix]
+    return "\n".join(lines)
+
+
+def _decorators(node: ast.FunctionDef | ast.AsyncFunctionDef) -> tuple[str, ...]:
+    return tuple(ast.unparse(decorator) for decorator in node.decorator_list)
+
+
+def _is_overload(node: ast.AST) -> bool:
+    return isinstance(node, ast.FunctionDef | ast.AsyncFunctionDef) and any(
+        ast.unparse(decorator) in ("overload", "typing.overload")
+        for decorator in node.decorator_list
+    )
+
+
+def _is_property(node: ast.AST) -> bool:
+    return isinstance(node, ast.FunctionDef) and any(
+        ast.unparse(decorator) == "property" for decorator in node.decorator_list
+    )
+
+
+def _start(node: ast.stmt) -> int:
+    decorators = getattr(node, "decorator_list", [])
+    return min([node.lineno, *(decorator.lineno for decorator in decorators)])
+
+
+class Stubs:
+    """The stub file, with the edits to bring it in line with the runtime"""
+
+    def __init__(self, text: str) -> None:
+        self.lines = text.splitlines()
+        self.tree = ast.parse(text)
+        self.classes = {
+            node.name: node for node in self.tree.body if isinstance(node, ast.ClassDef)
+        }
+        self.functions: dict[str, list[ast.FunctionDef | ast.AsyncFunctionDef]] = {}
+        for node in self.tree.body:
+            if isinstance(node, ast.FunctionDef | ast.AsyncFunctionDef):
+                self.functions.setdefault(node.name, []).append(node)
+        # (first line, last line, replacement lines), 1-based and inclusive
+        self.edits: list[tuple[int, int, list[str]]] = []
+        self.problems: list[str] = []
+
+    def members(self, cls: ast.ClassDef) -> dict[str, list[ast.stmt]]:
+        members: dict[str, list[ast.stmt]] = {}
+        for node in cls.body:
+            if isinstance(node, ast.FunctionDef | ast.AsyncFunctionDef):
+                members.setdefault(node.name, []).append(node)
+            elif isinstance(node, ast.AnnAssign) and isinstance(node.target, ast.Name):
+                members.setdefault(node.target.id, []).append(node)
+        return members
+
+    def replace(self, node: ast.stmt, text: str, problem: str) -> None:
+        assert node.end_lineno is not None
+        self.edits.append((_start(node), node.end_lineno, text.splitlines()))
+        self.problems.append(problem)
+
+    def remove(self, node: ast.stmt, problem: str) -> None:
+        self.replace(node, "", problem)
+
+    def insert(self, after: int, text: str, problem: str) -> None:
+        self.edits.append((after + 1, after, text.splitlines()))
+        self.problems.append(problem)
+
+    def render(self) -> str:
+        lines = list(self.lines)
+        for first, last, replacement in sorted(self.edits, key=lambda edit: -edit[0]):
+            lines[first - 1 : last] = replacement
+        return "\n".join(lines) + "\n"
+
+
+def _sync_function(
+    stubs: Stubs,
+    where: str,
+    indent: str,
+    member: Member,
+    nodes: list[ast.FunctionDef | ast.AsyncFunctionDef],
+) -> None:
+    if member.signature is None or any(_is_overload(node) for node in nodes):
+        return
+    first = {"method": "self", "classmethod": "cls"}.get(member.kind, "")
+    decorators = {"staticmethod": ("staticmethod",), "classmethod": ("classmethod",)}
+    for node in nodes:
+        if _matches(member.signature, node):
+            continue
+        text = _format_def(
+            indent,
+            member.name,
+            _parameters(member.signature, node, first),
+            ast.unparse(node.returns) if node.returns else "Any",
+            isinstance(node, ast.AsyncFunctionDef),
+            _decorators(node) or decorators.get(member.kind, ()),
+        )
+        stubs.replace(node, text, f"{where}{member.name}: parameters differ from the module")
+
+
+def _new_member(indent: str, member: Member) -> str:
+    if member.kind == "property":
+        return f"{indent}@property\n{indent}def {member.name}(self) -> Any: ..."
+    if member.kind == "dunder":
+        return f"{indent}def {member.name}{DUNDERS[member.name]}: ..."
+    first = {"method": "self", "classmethod": "cls"}.get(member.kind, "")
+    if member.signature is None:
+        parameters = [first, "*args: Any", "**kwargs: Any"] if first else ["*args: Any"]
+    else:
+        parameters = _parameters(member.signature, None, first)
+    decorators = {"staticmethod": ("staticmethod",), "classmethod": ("classmethod",)}
+    return _format_def(
+        indent, member.name, parameters, "Any", decorators=decorators.get(member.kind, ())
+    )
+
+
+def _sync_class(stubs: Stubs, runtime: RuntimeClass) -> None:
+    names = STUB_CLASSES.get(runtime.name, [runtime.name])
+    nodes = [stubs.classes[name] for name in names if name in stubs.classes]
+    if not nodes:
+        if runtime.exception:
+            body = f'{INDENT}"""{runtime.name}"""'
+        else:
+            members = [_new_member(INDENT, member) for member in runtime.members.values()]
+            body = "\n".join(members) or f"{INDENT}..."
+        bases = f"({', '.join(runtime.bases)})" if runtime.bases else ""
+        stubs.insert(
+            len(stubs.lines),
+            f"\nclass {runtime.name}{bases}:\n{body}",
+            f"{runtime.name}: missing from the stubs",
+        )
+        return
+    if runtime.exception:
+        return
+
+    found: dict[str, list[tuple[ast.ClassDef, ast.stmt]]] = {}
+    for node in nodes:
+        for name, definitions in stubs.members(node).items():
+            found.setdefault(name, []).extend((node, definition) for definition in definitions)
+
+    where = f"{runtime.name}."
+    if runtime.constructor is not None:
+        init = [node for _, node in found.get("__init__", [])]
+        if init:
+            _sync_function(
+                stubs,
+                where,
+                INDENT,
+                Member("__init__", "method", runtime.constructor),
+                init,  # type: ignore[arg-type]
+            )
+        else:
+            text = _format_def(
+                INDENT, "__init__", _parameters(runtime.constructor, None, "self"), "None"
+            )
+            stubs.insert(
+                _after_docstring(nodes[0]), text, f"{where}__init__: missing from the stubs"
+            )
+
+    for name, member in runtime.members.items():
+        definitions = found.get(name)
+        if definitions is None:
+            assert nodes[0].end_lineno is not None
+            stubs.insert(
+                nodes[0].end_lineno,
+                _new_member(INDENT, member),
+                f"{where}{name}: missing from the stubs",
+            )
+        elif member.kind in ("method", "staticmethod", "classmethod"):
+            _sync_function(
+                stubs,
+                where,
+                INDENT,
+                member,
+                [
+                    node
+                    for _, node in definitions
+                    if isinstance(node, ast.FunctionDef | ast.AsyncFunctionDef)
+                ],
+            )
+
+
+def _after_docstring(node: ast.ClassDef) -> int:
+    first = node.body[0]
+    if isinstance(first, ast.Expr) and isinstance(first.value, ast.Constant):
+        assert first.end_lineno is not None
+        return first.end_lineno
+    return node.lineno
+
+
+def sync(module: ModuleType, text: str) -> Stubs:
+    functions, classes = introspect(module)
+    stubs = Stubs(text)
+    stub_classes = {name for names in STUB_CLASSES.values() for name in names}
+
+    last_function = max(
+        (node.end_lineno or 0 for nodes in stubs.functions.values() for node in nodes),
+        default=len(stubs.lines),
+    )
+    for name, member in functions.items():
+        nodes = stubs.functions.get(name)
+        if nodes is None:
+            stubs.insert(last_function, _new_member("", member), f"{name}: missing from the stubs")
+        else:
+            _sync_function(stubs, "", "", member, nodes)
+    for name, nodes in stubs.functions.items():
+        if name not in functions and not name.startswith("_"):
+            for node in nodes:
+                stubs.remove(node, f"{name}: not in the module")
+
+    for runtime in classes.values():
+        _sync_class(stubs, runtime)
+    for name, node in stubs.classes.items():
+        if name not in classes and name not in stub_classes and not name.startswith("_"):
+            stubs.remove(node, f"{name}: not in the module")
+
+    # Members of each stub class, including those the runtime classes inherit
+    allowed: dict[str, set[str]] = {}
+    for runtime in classes.values():
+        names: set[str] = set()
+        ancestor: RuntimeClass | None = runtime
+        while ancestor is not None:
+            names |= ancestor.members.keys()
+            ancestor = classes.get(ancestor.bases[0]) if ancestor.bases else None
+        for stub_name in STUB_CLASSES.get(runtime.name, [runtime.name]):
+            allowed.setdefault(stub_name, set()).update(names)
+    for name, node in stubs.classes.items():
+        if name not in allowed or classes.get(name, None) is not None and classes[name].exception:
+            continue
+        for member, definitions in stubs.members(node).items():
+            if member in allowed[name] or member == "__init__" or member in DUNDERS:
+                continue
+            for definition in definitions:
+                stubs.remove(definition, f"{name}.{member}: not in the module")
+    return stubs
+
+
+def extension_stub(module: ModuleType) -> str:
+    names = sorted(name for name in vars(module) if not name.startswith("_"))
+    imports = "\n".join(f"    {name} as {name}," for name in names)
+    return f"{GENERATED_HEADER}\nfrom . import (\n{imports}\n)\n"
+
+
+def main() -> int:
+    parser = argparse.ArgumentParser(description=__doc__.split("\n\n")[0])
+    parser.add_argument(
+        "--check", action="store_true", help="report outdated stubs instead of updating them"
+    )
+    options = parser.parse_args()
+
+    from rsylla import _rsylla
+
+    stubs = sync(_rsylla, STUB.read_text())
+    generated = extension_stub(_rsylla)
+    outdated = not EXTENSION_STUB.exists() or EXTENSION_STUB.read_text() != generated
+    for problem in stubs.problems:
+        print(problem)
+    if options.check:
+        if outdated:
+            print(f"{EXTENSION_STUB.relative_to(ROOT)} is out of date")
+        return 1 if stubs.problems or outdated else 0
+
+    STUB.write_text(stubs.render())
+    EXTENSION_STUB.write_text(generated)
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -0,0 +1,74 @@
+use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyCFunction, PyDict, PyTuple};
+use std::future::Future;
+
+static GET_RUNNING_LOOP: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+// Awaitable for the request on the running event loop. asyncio event loops, uvloop included,
+// get an asyncio future; trio, directly or as anyio's backend, gets a coroutine from
+// rsylla._aio waiting for a concurrent.futures.Future
+pub fn future_into_py<'py, F, T>(py: Python<'py>, request: F) -> PyResult<Bound<'py, PyAny>>
+where
+    F: Future<Output = PyResult<T>> + Send + 'static,
+    T: for<'a> IntoPyObject<'a> + Send + 'static,
+{
+    let running_loop = GET_RUNNING_LOOP
+        .import(py, "asyncio", "_get_running_loop")?
+        .call0()?;
+    if !running_loop.is_none() {
+        return pyo3_async_runtimes::tokio::future_into_py(py, request);
+    }
+    let aio = py.import("rsylla._aio")?;
+    // Without a supported event loop nothing would wait for the request, so it isn't started
+    aio.call_method0("check_event_loop")?;
+    aio.call_method1("wait", (concurrent_future(py, request)?,))
+}
+
+// Starts the request on the driver's runtime and returns a concurrent.futures.Future for its
+// result. Cancelling the future before it completes aborts the request.
+pub fn concurrent_future<'py, F, T>(py: Python<'py>, request: F) -> PyResult<Bound<'py, PyAny>>
+where
+    F: Future<Output = PyResult<T>> + Send + 'static,
+    T: for<'a> IntoPyObject<'a> + Send + 'static,
+{
+    let future = py
+        .import("concurrent.futures")?
+        .getattr("Future")?
+        .call0()?;
+
+    let pending = future.clone().unbind();
+    let task = pyo3_async_runtimes::tokio::get_runtime().spawn(async move {
+        let result = request.await;
+        Python::attach(|py| {
+            let future = pending.bind(py);
+            // A future cancelled in the meantime can't take a result anymore
+            let cancelled = future.call_method0("cancelled").and_then(|c| c.is_truthy());
+            if !matches!(cancelled, Ok(false)) {
+                return;
+            }
+            let outcome = match result {
+                Ok(result) => future.call_method1("set_result", (result,)),
+                Err(err) => future.call_method1("set_exception", (err.into_value(py),)),
+            };
+            if let Err(err) = outcome {
+                err.write_unraisable(py, Some(future));
+            }
+        })
+    });
+
+    let task = task.abort_handle();
+    let abort_on_cancel = PyCFunction::new_closure(
+        py,
+        None,
+        None,
+        move |args: &Bound<'_, PyTuple>, _kwargs: Option<&Bound<'_, PyDict>>| -> PyResult<()> {
+            if args.get_item(0)?.call_method0("cancelled")?.is_truthy()? {
+                task.abort();
+            }
+            Ok(())
+        },
+    )?;
+    future.call_method1("add_done_callback", (abort_on_cancel,))?;
+    Ok(future)
+}
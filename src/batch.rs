@@ -1,24 +1,59 @@
 use pyo3::prelude::*;
-use scylla::statement::batch::Batch as ScyllaBatch;
-use scylla::statement::Consistency;
+use scylla::statement::batch::{Batch as ScyllaBatch, BatchStatement, BatchType};
+use std::time::Duration;
 
-use crate::query::{PreparedStatement, Query};
+use crate::profile::{ExecutionProfile, ProfileOptions};
+use crate::query::{
+    consistency_to_str, parse_consistency, parse_serial_consistency, serial_consistency_to_str,
+    PreparedStatement, Query,
+};
 
 #[pyclass]
 #[derive(Clone)]
 pub struct Batch {
     pub(crate) inner: ScyllaBatch,
+    // Settings of the profile set by with_profile() that the driver doesn't apply
+    pub(crate) options: ProfileOptions,
+}
+
+impl Batch {
+    fn check_statement(&self, query: &str) -> PyResult<()> {
+        if self.inner.get_type() == BatchType::Counter && !is_counter_mutation(query) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Counter batches can only contain counter updates (SET c = c + ?) and deletes, got: {}",
+                query
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[pymethods]
 impl Batch {
+    // Options apply to the whole batch, like those of a Statement
     #[new]
-    #[pyo3(signature = (batch_type="logged"))]
-    pub fn new(batch_type: &str) -> PyResult<Self> {
+    #[pyo3(signature = (
+        batch_type="logged",
+        consistency=None,
+        serial_consistency=None,
+        timestamp=None,
+        timeout_ms=None,
+        idempotent=false,
+        tracing=false
+    ))]
+    pub fn new(
+        batch_type: &str,
+        consistency: Option<&str>,
+        serial_consistency: Option<&str>,
+        timestamp: Option<i64>,
+        timeout_ms: Option<u64>,
+        idempotent: bool,
+        tracing: bool,
+    ) -> PyResult<Self> {
         let btype = match batch_type.to_lowercase().as_str() {
-            "logged" => scylla::statement::batch::BatchType::Logged,
-            "unlogged" => scylla::statement::batch::BatchType::Unlogged,
-            "counter" => scylla::statement::batch::BatchType::Counter,
+            "logged" => BatchType::Logged,
+            "unlogged" => BatchType::Unlogged,
+            "counter" => BatchType::Counter,
             _ => {
                 return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
                     "Invalid batch type. Must be 'logged', 'unlogged', or 'counter'",
@@ -26,21 +61,88 @@ impl Batch {
             }
         };
 
+        let mut inner = ScyllaBatch::new(btype);
+        if let Some(consistency) = consistency {
+            inner.set_consistency(parse_consistency(consistency)?);
+        }
+        if let Some(serial_consistency) = serial_consistency {
+            inner.set_serial_consistency(Some(parse_serial_consistency(serial_consistency)?));
+        }
+        inner.set_timestamp(timestamp);
+        inner.set_request_timeout(timeout_ms.map(Duration::from_millis));
+        inner.set_is_idempotent(idempotent);
+        inner.set_tracing(tracing);
+
         Ok(Batch {
-            inner: ScyllaBatch::new(btype),
+            inner,
+            options: ProfileOptions::default(),
         })
     }
 
-    pub fn append_statement(&mut self, query: &str) {
+    #[staticmethod]
+    pub fn counter() -> Self {
+        Batch {
+            inner: ScyllaBatch::new(BatchType::Counter),
+            options: ProfileOptions::default(),
+        }
+    }
+
+    pub fn append_statement(&mut self, query: &str) -> PyResult<()> {
+        self.check_statement(query)?;
         self.inner.append_statement(query);
+        Ok(())
     }
 
-    pub fn append_query(&mut self, query: &Query) {
+    pub fn append_query(&mut self, query: &Query) -> PyResult<()> {
+        self.check_statement(&query.inner.contents)?;
         self.inner.append_statement(query.inner.clone());
+        Ok(())
     }
 
-    pub fn append_prepared(&mut self, prepared: &PreparedStatement) {
+    pub fn append_prepared(&mut self, prepared: &PreparedStatement) -> PyResult<()> {
+        self.check_statement(prepared.prepared.get_statement())?;
         self.inner.append_statement((*prepared.prepared).clone());
+        Ok(())
+    }
+
+    #[getter]
+    pub fn batch_type(&self) -> &'static str {
+        match self.inner.get_type() {
+            BatchType::Logged => "logged",
+            BatchType::Unlogged => "unlogged",
+            BatchType::Counter => "counter",
+        }
+    }
+
+    #[getter]
+    pub fn consistency(&self) -> Option<String> {
+        self.inner
+            .get_consistency()
+            .map(|c| consistency_to_str(c).to_string())
+    }
+
+    #[getter]
+    pub fn serial_consistency(&self) -> Option<String> {
+        self.inner
+            .get_serial_consistency()
+            .map(|c| serial_consistency_to_str(c).to_string())
+    }
+
+    #[getter]
+    pub fn timestamp(&self) -> Option<i64> {
+        self.inner.get_timestamp()
+    }
+
+    #[getter]
+    pub fn timeout_ms(&self) -> Option<u64> {
+        self.inner
+            .get_request_timeout()
+            .map(|timeout| timeout.as_millis() as u64)
+    }
+
+    #[getter]
+    pub fn tracing(&self) -> bool {
+        self.inner.get_tracing()
     }
 
     pub fn with_consistency(&mut self, consistency: &str) -> PyResult<Self> {
@@ -60,9 +162,9 @@ impl Batch {
         Ok(self.clone())
     }
 
-    pub fn with_timeout(&mut self, _timeout_ms: u64) -> PyResult<Self> {
-        // Note: set_request_timeout has been removed from the scylla crate
-        // Timeout configuration should be done at the session level
+    pub fn with_timeout(&mut self, timeout_ms: u64) -> PyResult<Self> {
+        self.inner
+            .set_request_timeout(Some(Duration::from_millis(timeout_ms)));
         Ok(self.clone())
     }
 
@@ -71,6 +173,13 @@ impl Batch {
         Ok(self.clone())
     }
 
+    pub fn with_profile(&mut self, profile: &ExecutionProfile) -> PyResult<Self> {
+        self.inner
+            .set_execution_profile_handle(Some(profile.handle()?));
+        self.options = profile.options();
+        Ok(self.clone())
+    }
+
     pub fn is_idempotent(&self) -> bool {
         self.inner.get_is_idempotent()
     }
@@ -83,36 +192,54 @@ impl Batch {
         self.inner.statements.len()
     }
 
+    // CQL of the statements, in the order they were appended
+    #[getter]
+    pub fn statements(&self) -> Vec<String> {
+        self.inner
+            .statements
+            .iter()
+            .map(|statement| match statement {
+                BatchStatement::Query(query) => query.contents.clone(),
+                BatchStatement::PreparedStatement(prepared) => prepared.get_statement().to_string(),
+                _ => String::new(),
+            })
+            .collect()
+    }
+
     pub fn __repr__(&self) -> String {
         format!("Batch(statements={})", self.inner.statements.len())
     }
 }
 
-fn parse_consistency(consistency: &str) -> PyResult<Consistency> {
-    match consistency.to_uppercase().as_str() {
-        "ANY" => Ok(Consistency::Any),
-        "ONE" => Ok(Consistency::One),
-        "TWO" => Ok(Consistency::Two),
-        "THREE" => Ok(Consistency::Three),
-        "QUORUM" => Ok(Consistency::Quorum),
-        "ALL" => Ok(Consistency::All),
-        "LOCAL_QUORUM" | "LOCALQUORUM" => Ok(Consistency::LocalQuorum),
-        "EACH_QUORUM" | "EACHQUORUM" => Ok(Consistency::EachQuorum),
-        "LOCAL_ONE" | "LOCALONE" => Ok(Consistency::LocalOne),
-        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-            "Invalid consistency level: {}",
-            consistency
-        ))),
+// Counter tables only accept increments and decrements of every assigned column, or deletes
+fn is_counter_mutation(query: &str) -> bool {
+    let query = query
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    if query.starts_with("delete ") {
+        return true;
     }
-}
-
-fn parse_serial_consistency(consistency: &str) -> PyResult<scylla::statement::SerialConsistency> {
-    match consistency.to_uppercase().as_str() {
-        "SERIAL" => Ok(scylla::statement::SerialConsistency::Serial),
-        "LOCAL_SERIAL" | "LOCALSERIAL" => Ok(scylla::statement::SerialConsistency::LocalSerial),
-        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-            "Invalid serial consistency level: {}",
-            consistency
-        ))),
+    if !query.starts_with("update ") {
+        return false;
     }
+    let Some((_, assignments)) = query.split_once(" set ") else {
+        return false;
+    };
+    let assignments = match assignments.split_once(" where ") {
+        Some((assignments, _)) => assignments,
+        None => assignments,
+    };
+    assignments.split(',').all(|assignment| {
+        let Some((column, value)) = assignment.split_once('=') else {
+            return false;
+        };
+        let column = column.trim();
+        value
+            .trim()
+            .strip_prefix(column)
+            .map(str::trim_start)
+            .is_some_and(|rest| rest.starts_with('+') || rest.starts_with('-'))
+    })
 }
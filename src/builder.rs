@@ -0,0 +1,695 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyByteArray, PyBytes, PyDict, PyList, PyString};
+
+use crate::query::Query;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Select,
+    Insert,
+    Update,
+    Delete,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Select => "SELECT",
+            Kind::Insert => "INSERT",
+            Kind::Update => "UPDATE",
+            Kind::Delete => "DELETE",
+        }
+    }
+}
+
+const OPERATORS: [&str; 9] = [
+    "=",
+    "<",
+    "<=",
+    ">",
+    ">=",
+    "!=",
+    "IN",
+    "CONTAINS",
+    "CONTAINS KEY",
+];
+
+// Identifiers are always quoted, so names are taken verbatim and can't break out of the statement
+pub fn quote_identifier(name: &str) -> PyResult<String> {
+    if name.is_empty() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "Identifier must not be empty",
+        ));
+    }
+    Ok(format!("\"{}\"", name.replace('"', "\"\"")))
+}
+
+pub fn quote_table(table: &str) -> PyResult<String> {
+    match table.split_once('.') {
+        Some((keyspace, table)) => Ok(format!(
+            "{}.{}",
+            quote_identifier(keyspace)?,
+            quote_identifier(table)?
+        )),
+        None => quote_identifier(table),
+    }
+}
+
+fn quote_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+// Options are written as string literals, which the server accepts for numbers too
+pub fn create_keyspace_statement(
+    name: &str,
+    replication: &Bound<'_, PyDict>,
+    durable_writes: bool,
+    if_not_exists: bool,
+) -> PyResult<String> {
+    if !replication.contains("class")? {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "Replication must contain a 'class' key",
+        ));
+    }
+    let options = replication
+        .iter()
+        .map(|(key, value)| {
+            let value = match value.extract::<i64>() {
+                Ok(number) if !value.is_instance_of::<pyo3::types::PyBool>() => number.to_string(),
+                _ => value.extract::<String>().map_err(|_| {
+                    PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+                        "Replication option {} must be a string or an int",
+                        key
+                    ))
+                })?,
+            };
+            Ok(format!(
+                "{}: {}",
+                quote_string(&key.extract::<String>()?),
+                quote_string(&value)
+            ))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(format!(
+        "CREATE KEYSPACE {}{} WITH replication = {{{}}} AND durable_writes = {}",
+        if if_not_exists { "IF NOT EXISTS " } else { "" },
+        quote_identifier(name)?,
+        options.join(", "),
+        durable_writes
+    ))
+}
+
+// Upper-cased words of a statement with their offsets, skipping literals, quoted
+// identifiers, comments and anything in parentheses
+pub(crate) fn top_level_words(cql: &str) -> Vec<(usize, String)> {
+    let mut words = Vec::new();
+    let mut depth = 0;
+    let mut chars = cql.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                // Doubled quotes inside are read as two adjacent literals
+                for (_, next) in chars.by_ref() {
+                    if next == c {
+                        break;
+                    }
+                }
+            }
+            '-' | '/' if cql[start..].starts_with("--") || cql[start..].starts_with("//") => {
+                while chars.next_if(|&(_, next)| next != '\n').is_some() {}
+            }
+            '/' if cql[start..].starts_with("/*") => {
+                chars.next();
+                let end = cql[start + 2..]
+                    .find("*/")
+                    .map_or(cql.len(), |end| start + end + 4);
+                while chars.next_if(|&(i, _)| i < end).is_some() {}
+            }
+            '$' if cql[start..].starts_with("$$") => {
+                chars.next();
+                let end = cql[start + 2..]
+                    .find("$$")
+                    .map_or(cql.len(), |end| start + end + 4);
+                while chars.next_if(|&(i, _)| i < end).is_some() {}
+            }
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, next)) = chars.next_if(|&(_, n)| n.is_alphanumeric() || n == '_')
+                {
+                    end = i + next.len_utf8();
+                }
+                if depth == 0 {
+                    words.push((start, cql[start..end].to_uppercase()));
+                }
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+// Statements whose USING clause takes the given option
+fn using_statements(keyword: &str) -> &'static [&'static str] {
+    match keyword {
+        "TTL" => &["INSERT", "UPDATE"],
+        "TIMESTAMP" => &["INSERT", "UPDATE", "DELETE"],
+        _ => &["SELECT", "INSERT", "UPDATE", "DELETE"],
+    }
+}
+
+// Adds `option` (e.g. "TTL 60") to the USING clause of a statement, creating the clause if
+// needed. SELECT takes it at the very end, DELETE before WHERE and UPDATE before SET.
+pub fn with_using_option(cql: &str, option: &str) -> PyResult<String> {
+    let cql = cql.trim_end().trim_end_matches(';').trim_end();
+    let words = top_level_words(cql);
+    let keyword = option.split(' ').next().unwrap_or_default();
+    let statement = words.first().map_or("", |(_, word)| word.as_str());
+    let statements = using_statements(keyword);
+    if !statements.contains(&statement) {
+        let (last, rest) = statements.split_last().unwrap();
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "{} is only supported for {} and {} statements",
+            keyword.to_lowercase(),
+            rest.join(", "),
+            last
+        )));
+    }
+    let before = |clause: &str| {
+        words
+            .iter()
+            .find(|(_, word)| word == clause)
+            .map(|&(start, _)| (start, " "))
+            .ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "{} statement has no {} clause",
+                    statement, clause
+                ))
+            })
+    };
+    let (clause_end, tail) = match statement {
+        "UPDATE" => before("SET")?,
+        "DELETE" => before("WHERE")?,
+        _ => (cql.len(), ""),
+    };
+    let clause = words
+        .iter()
+        .filter(|&&(start, _)| start < clause_end)
+        .skip_while(|(_, word)| word != "USING")
+        .map(|(_, word)| word.as_str())
+        .collect::<Vec<_>>();
+    if clause.contains(&keyword) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Statement already has a USING {} clause",
+            keyword
+        )));
+    }
+    let (head, rest) = cql.split_at(clause_end);
+    let separator = if clause.is_empty() { "USING" } else { "AND" };
+    Ok(format!(
+        "{} {} {}{}{}",
+        head.trim_end(),
+        separator,
+        option,
+        tail,
+        rest
+    ))
+}
+
+// Adds BYPASS CACHE to a SELECT, ahead of its USING clause if it has one
+pub fn with_bypass_cache(cql: &str) -> PyResult<String> {
+    let cql = cql.trim_end().trim_end_matches(';').trim_end();
+    let words = top_level_words(cql);
+    if words.first().map(|(_, word)| word.as_str()) != Some("SELECT") {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "bypass_cache is only supported for SELECT statements",
+        ));
+    }
+    if words
+        .windows(2)
+        .any(|pair| pair[0].1 == "BYPASS" && pair[1].1 == "CACHE")
+    {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "Statement already has a BYPASS CACHE clause",
+        ));
+    }
+    let (head, rest) = match words.iter().find(|(_, word)| word == "USING") {
+        Some(&(using, _)) => cql.split_at(using),
+        None => (cql, ""),
+    };
+    let tail = if rest.is_empty() { "" } else { " " };
+    Ok(format!("{} BYPASS CACHE{}{}", head.trim_end(), tail, rest))
+}
+
+// Builds parameterized statements: every value goes through a bind marker
+#[pyclass]
+pub struct QueryBuilder {
+    kind: Kind,
+    table: String,
+    columns: Vec<String>,
+    // Column and the expression assigned to it
+    assignments: Vec<(String, String)>,
+    conditions: Vec<String>,
+    if_conditions: Vec<String>,
+    if_exists: bool,
+    if_not_exists: bool,
+    order_by: Vec<String>,
+    limit: Option<u32>,
+    per_partition_limit: Option<u32>,
+    allow_filtering: bool,
+    ttl: Option<i32>,
+    timestamp: Option<i64>,
+    values: Vec<(String, Py<PyAny>)>,
+}
+
+impl Clone for QueryBuilder {
+    fn clone(&self) -> Self {
+        Python::attach(|py| QueryBuilder {
+            kind: self.kind,
+            table: self.table.clone(),
+            columns: self.columns.clone(),
+            assignments: self.assignments.clone(),
+            conditions: self.conditions.clone(),
+            if_conditions: self.if_conditions.clone(),
+            if_exists: self.if_exists,
+            if_not_exists: self.if_not_exists,
+            order_by: self.order_by.clone(),
+            limit: self.limit,
+            per_partition_limit: self.per_partition_limit,
+            allow_filtering: self.allow_filtering,
+            ttl: self.ttl,
+            timestamp: self.timestamp,
+            values: self
+                .values
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone_ref(py)))
+                .collect(),
+        })
+    }
+}
+
+impl QueryBuilder {
+    fn new(kind: Kind, table: &str, columns: Option<Vec<String>>) -> PyResult<Self> {
+        Ok(QueryBuilder {
+            kind,
+            table: quote_table(table)?,
+            columns: columns
+                .unwrap_or_default()
+                .iter()
+                .map(|column| quote_identifier(column))
+                .collect::<PyResult<_>>()?,
+            assignments: Vec::new(),
+            conditions: Vec::new(),
+            if_conditions: Vec::new(),
+            if_exists: false,
+            if_not_exists: false,
+            order_by: Vec::new(),
+            limit: None,
+            per_partition_limit: None,
+            allow_filtering: false,
+            ttl: None,
+            timestamp: None,
+            values: Vec::new(),
+        })
+    }
+
+    pub fn select(table: &str, columns: Option<Vec<String>>) -> PyResult<Self> {
+        Self::new(Kind::Select, table, columns)
+    }
+
+    pub fn insert(table: &str) -> PyResult<Self> {
+        Self::new(Kind::Insert, table, None)
+    }
+
+    pub fn update(table: &str) -> PyResult<Self> {
+        Self::new(Kind::Update, table, None)
+    }
+
+    pub fn delete(table: &str, columns: Option<Vec<String>>) -> PyResult<Self> {
+        Self::new(Kind::Delete, table, columns)
+    }
+
+    fn expect(&self, method: &str, kinds: &[Kind]) -> PyResult<()> {
+        if kinds.contains(&self.kind) {
+            return Ok(());
+        }
+        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "{}() is not supported for {} statements",
+            method,
+            self.kind.name()
+        )))
+    }
+
+    fn bind(&mut self, value: Py<PyAny>) -> String {
+        let name = format!("p{}", self.values.len());
+        let marker = format!(":{}", name);
+        self.values.push((name, value));
+        marker
+    }
+
+    fn condition(&mut self, column: &str, op: &str, value: Py<PyAny>) -> PyResult<String> {
+        let op = op.trim().to_uppercase();
+        if !OPERATORS.contains(&op.as_str()) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid operator: {}. Must be one of {}",
+                op,
+                OPERATORS.join(", ")
+            )));
+        }
+        let column = quote_identifier(column)?;
+        Ok(format!("{} {} {}", column, op, self.bind(value)))
+    }
+
+    // Selects `function(column)` under the name Row.writetime() and Row.ttl() look up
+    fn select_metadata(&mut self, method: &str, function: &str, column: &str) -> PyResult<Self> {
+        self.expect(method, &[Kind::Select])?;
+        if self.columns.is_empty() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "{}() needs the selected columns to be listed, it can't be combined with SELECT *",
+                method
+            )));
+        }
+        self.columns.push(format!(
+            "{}({}) AS {}",
+            function.to_uppercase(),
+            quote_identifier(column)?,
+            quote_identifier(&format!("{}({})", function, column))?
+        ));
+        Ok(self.clone())
+    }
+
+    fn using(&self) -> String {
+        let mut options = Vec::new();
+        if let Some(ttl) = self.ttl {
+            options.push(format!("TTL {}", ttl));
+        }
+        if let Some(timestamp) = self.timestamp {
+            options.push(format!("TIMESTAMP {}", timestamp));
+        }
+        if options.is_empty() {
+            String::new()
+        } else {
+            format!(" USING {}", options.join(" AND "))
+        }
+    }
+
+    fn where_clause(&self) -> PyResult<String> {
+        if self.conditions.is_empty() {
+            if self.kind == Kind::Select {
+                return Ok(String::new());
+            }
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "{} statements need at least one where condition",
+                self.kind.name()
+            )));
+        }
+        Ok(format!(" WHERE {}", self.conditions.join(" AND ")))
+    }
+
+    fn if_clause(&self) -> String {
+        if self.if_exists {
+            " IF EXISTS".to_string()
+        } else if self.if_not_exists {
+            " IF NOT EXISTS".to_string()
+        } else if !self.if_conditions.is_empty() {
+            format!(" IF {}", self.if_conditions.join(" AND "))
+        } else {
+            String::new()
+        }
+    }
+
+    pub fn to_cql(&self) -> PyResult<String> {
+        let cql = match self.kind {
+            Kind::Select => {
+                let columns = if self.columns.is_empty() {
+                    "*".to_string()
+                } else {
+                    self.columns.join(", ")
+                };
+                let mut cql = format!(
+                    "SELECT {} FROM {}{}",
+                    columns,
+                    self.table,
+                    self.where_clause()?
+                );
+                if !self.order_by.is_empty() {
+                    cql.push_str(&format!(" ORDER BY {}", self.order_by.join(", ")));
+                }
+                if let Some(limit) = self.per_partition_limit {
+                    cql.push_str(&format!(" PER PARTITION LIMIT {}", limit));
+                }
+                if let Some(limit) = self.limit {
+                    cql.push_str(&format!(" LIMIT {}", limit));
+                }
+                if self.allow_filtering {
+                    cql.push_str(" ALLOW FILTERING");
+                }
+                cql
+            }
+            Kind::Insert => {
+                if self.assignments.is_empty() {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "INSERT statements need at least one value",
+                    ));
+                }
+                let (columns, values): (Vec<_>, Vec<_>) = self.assignments.iter().cloned().unzip();
+                format!(
+                    "INSERT INTO {} ({}) VALUES ({}){}{}",
+                    self.table,
+                    columns.join(", "),
+                    values.join(", "),
+                    self.if_clause(),
+                    self.using()
+                )
+            }
+            Kind::Update => {
+                if self.assignments.is_empty() {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "UPDATE statements need at least one assignment",
+                    ));
+                }
+                let assignments = self
+                    .assignments
+                    .iter()
+                    .map(|(column, expression)| format!("{} = {}", column, expression))
+                    .collect::<Vec<_>>();
+                format!(
+                    "UPDATE {}{} SET {}{}{}",
+                    self.table,
+                    self.using(),
+                    assignments.join(", "),
+                    self.where_clause()?,
+                    self.if_clause()
+                )
+            }
+            Kind::Delete => {
+                let columns = if self.columns.is_empty() {
+                    String::new()
+                } else {
+                    format!(" {}", self.columns.join(", "))
+                };
+                format!(
+                    "DELETE{} FROM {}{}{}{}",
+                    columns,
+                    self.table,
+                    self.using(),
+                    self.where_clause()?,
+                    self.if_clause()
+                )
+            }
+        };
+        Ok(cql)
+    }
+
+    pub fn values_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let values = PyDict::new(py);
+        for (name, value) in &self.values {
+            values.set_item(name, value.bind(py))?;
+        }
+        Ok(values)
+    }
+}
+
+#[pymethods]
+impl QueryBuilder {
+    pub fn where_eq(&mut self, column: &str, value: Py<PyAny>) -> PyResult<Self> {
+        self.where_op(column, "=", value)
+    }
+
+    pub fn where_in(&mut self, column: &str, values: &Bound<'_, PyAny>) -> PyResult<Self> {
+        // Strings and bytes are iterable, but never meant as a list of values
+        if values.is_instance_of::<PyString>()
+            || values.is_instance_of::<PyBytes>()
+            || values.is_instance_of::<PyByteArray>()
+        {
+            return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+                "where_in values for {} must be a list of values, not str or bytes",
+                column
+            )));
+        }
+        let values = PyList::new(
+            values.py(),
+            values.try_iter()?.collect::<PyResult<Vec<_>>>()?,
+        )?;
+        self.where_op(column, "IN", values.into_any().unbind())
+    }
+
+    pub fn where_op(&mut self, column: &str, op: &str, value: Py<PyAny>) -> PyResult<Self> {
+        self.expect("where", &[Kind::Select, Kind::Update, Kind::Delete])?;
+        let condition = self.condition(column, op, value)?;
+        self.conditions.push(condition);
+        Ok(self.clone())
+    }
+
+    pub fn value(&mut self, column: &str, value: Py<PyAny>) -> PyResult<Self> {
+        self.expect("value", &[Kind::Insert])?;
+        let column = quote_identifier(column)?;
+        let marker = self.bind(value);
+        self.assignments.push((column, marker));
+        Ok(self.clone())
+    }
+
+    pub fn set(&mut self, column: &str, value: Py<PyAny>) -> PyResult<Self> {
+        self.expect("set", &[Kind::Update])?;
+        let column = quote_identifier(column)?;
+        let marker = self.bind(value);
+        self.assignments.push((column, marker));
+        Ok(self.clone())
+    }
+
+    #[pyo3(signature = (column, amount=None))]
+    pub fn increment(
+        &mut self,
+        py: Python,
+        column: &str,
+        amount: Option<Py<PyAny>>,
+    ) -> PyResult<Self> {
+        self.expect("increment", &[Kind::Update])?;
+        let column = quote_identifier(column)?;
+        let amount = match amount {
+            Some(amount) => amount,
+            None => 1i64.into_pyobject(py)?.into_any().unbind(),
+        };
+        let expression = format!("{} + {}", column, self.bind(amount));
+        self.assignments.push((column, expression));
+        Ok(self.clone())
+    }
+
+    #[pyo3(signature = (column, amount=None))]
+    pub fn decrement(
+        &mut self,
+        py: Python,
+        column: &str,
+        amount: Option<Py<PyAny>>,
+    ) -> PyResult<Self> {
+        self.expect("decrement", &[Kind::Update])?;
+        let column = quote_identifier(column)?;
+        let amount = match amount {
+            Some(amount) => amount,
+            None => 1i64.into_pyobject(py)?.into_any().unbind(),
+        };
+        let expression = format!("{} - {}", column, self.bind(amount));
+        self.assignments.push((column, expression));
+        Ok(self.clone())
+    }
+
+    pub fn select_writetime(&mut self, column: &str) -> PyResult<Self> {
+        self.select_metadata("select_writetime", "writetime", column)
+    }
+
+    pub fn select_ttl(&mut self, column: &str) -> PyResult<Self> {
+        self.select_metadata("select_ttl", "ttl", column)
+    }
+
+    #[pyo3(signature = (column, descending=false))]
+    pub fn order_by(&mut self, column: &str, descending: bool) -> PyResult<Self> {
+        self.expect("order_by", &[Kind::Select])?;
+        let column = quote_identifier(column)?;
+        self.order_by.push(format!(
+            "{} {}",
+            column,
+            if descending { "DESC" } else { "ASC" }
+        ));
+        Ok(self.clone())
+    }
+
+    pub fn limit(&mut self, limit: u32) -> PyResult<Self> {
+        self.expect("limit", &[Kind::Select])?;
+        self.limit = Some(limit);
+        Ok(self.clone())
+    }
+
+    pub fn per_partition_limit(&mut self, limit: u32) -> PyResult<Self> {
+        self.expect("per_partition_limit", &[Kind::Select])?;
+        self.per_partition_limit = Some(limit);
+        Ok(self.clone())
+    }
+
+    pub fn allow_filtering(&mut self) -> PyResult<Self> {
+        self.expect("allow_filtering", &[Kind::Select])?;
+        self.allow_filtering = true;
+        Ok(self.clone())
+    }
+
+    pub fn if_exists(&mut self) -> PyResult<Self> {
+        self.expect("if_exists", &[Kind::Update, Kind::Delete])?;
+        self.if_exists = true;
+        Ok(self.clone())
+    }
+
+    pub fn if_not_exists(&mut self) -> PyResult<Self> {
+        self.expect("if_not_exists", &[Kind::Insert])?;
+        self.if_not_exists = true;
+        Ok(self.clone())
+    }
+
+    #[pyo3(signature = (column, value, op="="))]
+    pub fn if_condition(&mut self, column: &str, value: Py<PyAny>, op: &str) -> PyResult<Self> {
+        self.expect("if_condition", &[Kind::Update, Kind::Delete])?;
+        let condition = self.condition(column, op, value)?;
+        self.if_conditions.push(condition);
+        Ok(self.clone())
+    }
+
+    pub fn using_ttl(&mut self, ttl: i32) -> PyResult<Self> {
+        self.expect("using_ttl", &[Kind::Insert, Kind::Update])?;
+        if ttl < 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "ttl must not be negative",
+            ));
+        }
+        self.ttl = Some(ttl);
+        Ok(self.clone())
+    }
+
+    pub fn using_timestamp(&mut self, timestamp: i64) -> PyResult<Self> {
+        self.expect(
+            "using_timestamp",
+            &[Kind::Insert, Kind::Update, Kind::Delete],
+        )?;
+        self.timestamp = Some(timestamp);
+        Ok(self.clone())
+    }
+
+    #[getter]
+    pub fn cql(&self) -> PyResult<String> {
+        self.to_cql()
+    }
+
+    #[getter]
+    pub fn values<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        self.values_dict(py)
+    }
+
+    pub fn build<'py>(&self, py: Python<'py>) -> PyResult<(Query, Bound<'py, PyDict>)> {
+        Ok((Query::new(&self.to_cql()?), self.values_dict(py)?))
+    }
+
+    pub fn __repr__(&self) -> String {
+        match self.to_cql() {
+            Ok(cql) => format!("QueryBuilder({})", cql),
+            Err(_) => format!("QueryBuilder({} {})", self.kind.name(), self.table),
+        }
+    }
+}
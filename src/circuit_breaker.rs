@@ -0,0 +1,294 @@
+use pyo3::prelude::*;
+use scylla::cluster::{ClusterState, NodeRef};
+use scylla::errors::{DbError, RequestAttemptError};
+use scylla::policies::load_balancing::{FallbackPlan, LoadBalancingPolicy, RoutingInfo};
+use scylla::routing::Shard;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::cluster::HostInfo;
+
+#[derive(Clone, Copy, Default)]
+enum Circuit {
+    #[default]
+    Closed,
+    Open {
+        until: Instant,
+    },
+    // The open period is over and one request probes the node, since the given time
+    HalfOpen {
+        probe: Instant,
+    },
+}
+
+#[derive(Default)]
+struct HostCircuit {
+    circuit: Circuit,
+    // Failed attempts since the last successful one
+    failures: u32,
+    opened: u64,
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct CircuitBreakerStats {
+    #[pyo3(get)]
+    host: HostInfo,
+    // "closed", "open" or "half_open"
+    #[pyo3(get)]
+    state: &'static str,
+    #[pyo3(get)]
+    consecutive_failures: u32,
+    #[pyo3(get)]
+    opened: u64,
+    // Until the node is probed again, while the circuit is open
+    #[pyo3(get)]
+    retry_in_ms: Option<u64>,
+}
+
+#[pymethods]
+impl CircuitBreakerStats {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "CircuitBreakerStats(address={}:{}, state={}, consecutive_failures={}, opened={})",
+            self.host.address, self.host.port, self.state, self.consecutive_failures, self.opened
+        )
+    }
+}
+
+// Circuits of every node a session sent requests to, keyed by host id
+pub struct CircuitBreakers {
+    failure_threshold: u32,
+    open_period: Duration,
+    hosts: Mutex<HashMap<Uuid, HostCircuit>>,
+}
+
+impl CircuitBreakers {
+    pub fn new(failure_threshold: u32, open_period: Duration) -> Self {
+        CircuitBreakers {
+            failure_threshold,
+            open_period,
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Whether requests may go to the node. The first caller after the open period becomes the
+    // probe, and so does the next one if the probe doesn't report back within another period.
+    fn allows(&self, node: NodeRef<'_>) -> bool {
+        let mut hosts = self.hosts.lock().unwrap();
+        let Some(host) = hosts.get_mut(&node.host_id) else {
+            return true;
+        };
+        let now = Instant::now();
+        match host.circuit {
+            Circuit::Closed => true,
+            Circuit::Open { until } if now < until => false,
+            Circuit::HalfOpen { probe } if now < probe + self.open_period => false,
+            _ => {
+                host.circuit = Circuit::HalfOpen { probe: now };
+                true
+            }
+        }
+    }
+
+    fn record_success(&self, node: NodeRef<'_>) {
+        let mut hosts = self.hosts.lock().unwrap();
+        if let Some(host) = hosts.get_mut(&node.host_id) {
+            host.circuit = Circuit::Closed;
+            host.failures = 0;
+        }
+    }
+
+    fn record_failure(&self, node: NodeRef<'_>) {
+        let mut hosts = self.hosts.lock().unwrap();
+        let host = hosts.entry(node.host_id).or_default();
+        host.failures += 1;
+        let trips = match host.circuit {
+            Circuit::Closed => host.failures >= self.failure_threshold,
+            Circuit::HalfOpen { .. } => true,
+            // Tried as a last resort while open
+            Circuit::Open { .. } => false,
+        };
+        if trips {
+            host.circuit = Circuit::Open {
+                until: Instant::now() + self.open_period,
+            };
+            host.opened += 1;
+        }
+    }
+
+    pub fn stats(&self, cluster_state: &ClusterState) -> Vec<CircuitBreakerStats> {
+        let hosts = self.hosts.lock().unwrap();
+        let now = Instant::now();
+        cluster_state
+            .get_nodes_info()
+            .iter()
+            .filter(|node| node.is_enabled())
+            .map(|node| {
+                let host = hosts.get(&node.host_id);
+                let (state, retry_in) = match host.map_or(Circuit::Closed, |host| host.circuit) {
+                    Circuit::Closed => ("closed", None),
+                    Circuit::Open { until } => ("open", Some(until.saturating_duration_since(now))),
+                    Circuit::HalfOpen { .. } => ("half_open", None),
+                };
+                CircuitBreakerStats {
+                    host: HostInfo::from_node(node),
+                    state,
+                    consecutive_failures: host.map_or(0, |host| host.failures),
+                    opened: host.map_or(0, |host| host.opened),
+                    retry_in_ms: retry_in.map(|retry_in| retry_in.as_millis() as u64),
+                }
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for CircuitBreakers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitBreakers").finish_non_exhaustive()
+    }
+}
+
+// Errors telling that the node itself is in trouble, rather than the request or its replicas
+fn is_node_failure(error: &RequestAttemptError) -> bool {
+    matches!(
+        error,
+        RequestAttemptError::BrokenConnectionError(_)
+            | RequestAttemptError::UnableToAllocStreamId
+            | RequestAttemptError::DbError(
+                DbError::Overloaded
+                    | DbError::ServerError
+                    | DbError::IsBootstrapping
+                    | DbError::ReadTimeout { .. }
+                    | DbError::WriteTimeout { .. },
+                _
+            )
+    )
+}
+
+// Nodes of a fallback plan in order, with those whose circuit is open moved to the end
+struct Fallback<'a> {
+    plan: FallbackPlan<'a>,
+    breakers: &'a CircuitBreakers,
+    open: Vec<(NodeRef<'a>, Option<Shard>)>,
+}
+
+impl<'a> Iterator for Fallback<'a> {
+    type Item = (NodeRef<'a>, Option<Shard>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for target in self.plan.by_ref() {
+            if self.breakers.allows(target.0) {
+                return Some(target);
+            }
+            self.open.push(target);
+        }
+        (!self.open.is_empty()).then(|| self.open.remove(0))
+    }
+}
+
+// Wraps the session's policy to keep requests away from nodes failing over and over
+#[derive(Debug)]
+pub struct CircuitBreakerPolicy {
+    inner: Arc<dyn LoadBalancingPolicy>,
+    breakers: Arc<CircuitBreakers>,
+}
+
+impl CircuitBreakerPolicy {
+    pub fn new(inner: Arc<dyn LoadBalancingPolicy>, breakers: Arc<CircuitBreakers>) -> Self {
+        CircuitBreakerPolicy { inner, breakers }
+    }
+}
+
+impl LoadBalancingPolicy for CircuitBreakerPolicy {
+    fn pick<'a>(
+        &'a self,
+        request: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> Option<(NodeRef<'a>, Option<Shard>)> {
+        // Without a pick the driver goes through the fallback plan, which skips open circuits
+        self.inner
+            .pick(request, cluster)
+            .filter(|(node, _)| self.breakers.allows(node))
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        request: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> FallbackPlan<'a> {
+        Box::new(Fallback {
+            plan: self.inner.fallback(request, cluster),
+            breakers: &self.breakers,
+            open: Vec::new(),
+        })
+    }
+
+    fn on_request_success(&self, request: &RoutingInfo, latency: Duration, node: NodeRef<'_>) {
+        self.breakers.record_success(node);
+        self.inner.on_request_success(request, latency, node);
+    }
+
+    fn on_request_failure(
+        &self,
+        request: &RoutingInfo,
+        latency: Duration,
+        node: NodeRef<'_>,
+        error: &RequestAttemptError,
+    ) {
+        // Any other error still came back from the node, which proves it responsive
+        if is_node_failure(error) {
+            self.breakers.record_failure(node);
+        } else {
+            self.breakers.record_success(node);
+        }
+        self.inner.on_request_failure(request, latency, node, error);
+    }
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+}
+
+// Appends per-node circuit gauges and counters in the Prometheus text format
+pub fn render_prometheus(stats: &[CircuitBreakerStats], out: &mut String) {
+    let labels = |stats: &CircuitBreakerStats| {
+        format!(
+            "host=\"{}:{}\",datacenter=\"{}\"",
+            stats.host.address,
+            stats.host.port,
+            stats.host.datacenter.as_deref().unwrap_or("")
+        )
+    };
+
+    let _ = writeln!(
+        out,
+        "# HELP rsylla_circuit_breaker_open Whether requests avoid a host after repeated failures"
+    );
+    let _ = writeln!(out, "# TYPE rsylla_circuit_breaker_open gauge");
+    for stats in stats {
+        let _ = writeln!(
+            out,
+            "rsylla_circuit_breaker_open{{{}}} {}",
+            labels(stats),
+            u8::from(stats.state != "closed")
+        );
+    }
+
+    let _ = writeln!(
+        out,
+        "# HELP rsylla_circuit_breaker_opened_total Times requests started avoiding a host"
+    );
+    let _ = writeln!(out, "# TYPE rsylla_circuit_breaker_opened_total counter");
+    for stats in stats {
+        let _ = writeln!(
+            out,
+            "rsylla_circuit_breaker_opened_total{{{}}} {}",
+            labels(stats),
+            stats.opened
+        );
+    }
+}
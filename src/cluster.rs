@@ -0,0 +1,370 @@
+use futures::future;
+use pyo3::prelude::*;
+use scylla::client::session::Session as ScyllaSession;
+use scylla::cluster::metadata::Peer;
+use scylla::cluster::{ClusterState, Node};
+use scylla::policies::host_filter::HostFilter;
+use scylla::policies::load_balancing::{NodeIdentifier, SingleTargetLoadBalancingPolicy};
+use scylla::statement::unprepared::Statement as ScyllaQuery;
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct HostInfo {
+    #[pyo3(get)]
+    pub(crate) address: String,
+    #[pyo3(get)]
+    pub(crate) port: u16,
+    #[pyo3(get)]
+    pub(crate) datacenter: Option<String>,
+    #[pyo3(get)]
+    rack: Option<String>,
+    #[pyo3(get)]
+    host_id: String,
+}
+
+impl HostInfo {
+    pub fn from_peer(peer: &Peer) -> Self {
+        HostInfo {
+            address: peer.address.ip().to_string(),
+            port: peer.address.port(),
+            datacenter: peer.datacenter.clone(),
+            rack: peer.rack.clone(),
+            host_id: peer.host_id.to_string(),
+        }
+    }
+
+    pub fn from_node(node: &Node) -> Self {
+        HostInfo {
+            address: node.address.ip().to_string(),
+            port: node.address.port(),
+            datacenter: node.datacenter.clone(),
+            rack: node.rack.clone(),
+            host_id: node.host_id.to_string(),
+        }
+    }
+}
+
+#[pymethods]
+impl HostInfo {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "HostInfo(address={}:{}, datacenter={:?}, rack={:?})",
+            self.address, self.port, self.datacenter, self.rack
+        )
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct NodeHealth {
+    #[pyo3(get)]
+    host: HostInfo,
+    #[pyo3(get)]
+    connected: bool,
+}
+
+#[pymethods]
+impl NodeHealth {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "NodeHealth(address={}:{}, connected={})",
+            self.host.address, self.host.port, self.connected
+        )
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct HealthReport {
+    #[pyo3(get)]
+    healthy: bool,
+    #[pyo3(get)]
+    nodes: Vec<NodeHealth>,
+    // Datacenter name to (connected nodes, total nodes); nodes without a known DC are under ""
+    #[pyo3(get)]
+    datacenters: BTreeMap<String, (usize, usize)>,
+}
+
+impl HealthReport {
+    // Healthy when every datacenter, or with per_node every node, has an open connection
+    pub fn check(cluster_state: &ClusterState, per_node: bool) -> Self {
+        let nodes: Vec<NodeHealth> = cluster_state
+            .get_nodes_info()
+            .iter()
+            .filter(|node| node.is_enabled())
+            .map(|node| NodeHealth {
+                host: HostInfo::from_node(node),
+                connected: node.is_connected(),
+            })
+            .collect();
+
+        let mut datacenters = BTreeMap::new();
+        for node in &nodes {
+            let dc = node.host.datacenter.clone().unwrap_or_default();
+            let (connected, total) = datacenters.entry(dc).or_insert((0, 0));
+            *total += 1;
+            if node.connected {
+                *connected += 1;
+            }
+        }
+
+        let healthy = !nodes.is_empty()
+            && if per_node {
+                nodes.iter().all(|node| node.connected)
+            } else {
+                datacenters.values().all(|(connected, _)| *connected > 0)
+            };
+
+        HealthReport {
+            healthy,
+            nodes,
+            datacenters,
+        }
+    }
+
+    pub fn connected_nodes(&self) -> usize {
+        self.nodes.iter().filter(|node| node.connected).count()
+    }
+
+    pub fn total_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+#[pymethods]
+impl HealthReport {
+    pub fn __bool__(&self) -> bool {
+        self.healthy
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "HealthReport(healthy={}, connected_nodes={}/{})",
+            self.healthy,
+            self.connected_nodes(),
+            self.total_nodes()
+        )
+    }
+}
+
+// The driver always speaks version 4 of the native protocol
+const PROTOCOL_VERSION: u8 = 4;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct NodeInfo {
+    #[pyo3(get)]
+    host: HostInfo,
+    #[pyo3(get)]
+    up: bool,
+    // Unknown until the driver opened a connection to the node
+    #[pyo3(get)]
+    shards: Option<u32>,
+    // Cassandra version the node reports in system.local, ScyllaDB reports the one it's
+    // compatible with
+    #[pyo3(get)]
+    release_version: Option<String>,
+    // None on Cassandra
+    #[pyo3(get)]
+    scylla_version: Option<String>,
+}
+
+#[pymethods]
+impl NodeInfo {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "NodeInfo(address={}:{}, datacenter={:?}, up={}, version={:?})",
+            self.host.address,
+            self.host.port,
+            self.host.datacenter,
+            self.up,
+            self.scylla_version
+                .as_ref()
+                .or(self.release_version.as_ref())
+        )
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct ClusterInfo {
+    #[pyo3(get)]
+    name: Option<String>,
+    #[pyo3(get)]
+    protocol_version: u8,
+    #[pyo3(get)]
+    nodes: Vec<NodeInfo>,
+}
+
+#[pymethods]
+impl ClusterInfo {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "ClusterInfo(name={:?}, protocol_version={}, nodes={})",
+            self.name,
+            self.protocol_version,
+            self.nodes.len()
+        )
+    }
+}
+
+// Runs a query on the given node only, None if it fails or returns no row
+async fn query_node<R>(session: &ScyllaSession, node: &Arc<Node>, query: &str) -> Option<R>
+where
+    R: for<'frame, 'metadata> scylla::deserialize::row::DeserializeRow<'frame, 'metadata>,
+{
+    let mut statement = ScyllaQuery::new(query);
+    statement.set_load_balancing_policy(Some(SingleTargetLoadBalancingPolicy::new(
+        NodeIdentifier::Node(node.clone()),
+        None,
+    )));
+    session
+        .query_unpaged(statement, &[])
+        .await
+        .ok()?
+        .into_rows_result()
+        .ok()?
+        .maybe_first_row::<R>()
+        .ok()?
+}
+
+// Versions are read from every node that is up, the rest comes from the driver's metadata
+pub async fn cluster_info(session: Arc<ScyllaSession>) -> ClusterInfo {
+    let cluster_state = session.get_cluster_state();
+    let nodes = cluster_state
+        .get_nodes_info()
+        .iter()
+        .filter(|node| node.is_enabled())
+        .map(|node| {
+            let session = session.clone();
+            async move {
+                let up = node.is_connected();
+                let local = match up {
+                    true => {
+                        query_node::<(Option<String>, Option<String>)>(
+                            &session,
+                            node,
+                            "SELECT cluster_name, release_version FROM system.local",
+                        )
+                        .await
+                    }
+                    false => None,
+                };
+                let scylla_version = match up {
+                    true => {
+                        query_node::<(Option<String>,)>(
+                            &session,
+                            node,
+                            "SELECT version FROM system.versions",
+                        )
+                        .await
+                    }
+                    false => None,
+                };
+                let (cluster_name, release_version) = local.unwrap_or_default();
+                let info = NodeInfo {
+                    host: HostInfo::from_node(node),
+                    up,
+                    shards: node.sharder().map(|sharder| sharder.nr_shards.get() as u32),
+                    release_version,
+                    scylla_version: scylla_version.and_then(|(version,)| version),
+                };
+                (cluster_name, info)
+            }
+        });
+    let nodes = future::join_all(nodes).await;
+
+    ClusterInfo {
+        name: nodes.iter().find_map(|(name, _)| name.clone()),
+        protocol_version: PROTOCOL_VERSION,
+        nodes: nodes.into_iter().map(|(_, info)| info).collect(),
+    }
+}
+
+#[derive(Clone)]
+pub enum HostAddr {
+    Ip(IpAddr),
+    Socket(SocketAddr),
+}
+
+impl HostAddr {
+    pub fn parse(addr: &str) -> PyResult<Self> {
+        if let Ok(ip) = addr.parse::<IpAddr>() {
+            Ok(HostAddr::Ip(ip))
+        } else if let Ok(socket) = addr.parse::<SocketAddr>() {
+            Ok(HostAddr::Socket(socket))
+        } else {
+            Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid host address: {}",
+                addr
+            )))
+        }
+    }
+
+    fn matches(&self, peer: &Peer) -> bool {
+        match self {
+            HostAddr::Ip(ip) => peer.address.ip() == *ip,
+            HostAddr::Socket(socket) => {
+                peer.address.ip() == socket.ip() && peer.address.port() == socket.port()
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct PyHostFilter {
+    pub allow_hosts: Option<Vec<HostAddr>>,
+    pub deny_hosts: Vec<HostAddr>,
+    pub allow_dcs: Option<Vec<String>>,
+    pub deny_dcs: Vec<String>,
+    pub predicate: Option<Py<PyAny>>,
+}
+
+impl PyHostFilter {
+    fn in_dcs(dcs: &[String], peer: &Peer) -> bool {
+        peer.datacenter
+            .as_ref()
+            .is_some_and(|dc| dcs.iter().any(|d| d == dc))
+    }
+
+    fn call_predicate(predicate: &Py<PyAny>, peer: &Peer) -> bool {
+        Python::attach(|py| {
+            predicate
+                .call1(py, (HostInfo::from_peer(peer),))
+                .and_then(|accepted| accepted.bind(py).is_truthy())
+                .unwrap_or_else(|err| {
+                    // A failing predicate rejects the host instead of aborting metadata refresh
+                    err.write_unraisable(py, Some(predicate.bind(py)));
+                    false
+                })
+        })
+    }
+}
+
+impl HostFilter for PyHostFilter {
+    fn accept(&self, peer: &Peer) -> bool {
+        if let Some(allowed) = &self.allow_hosts {
+            if !allowed.iter().any(|addr| addr.matches(peer)) {
+                return false;
+            }
+        }
+        if self.deny_hosts.iter().any(|addr| addr.matches(peer)) {
+            return false;
+        }
+        if let Some(allowed) = &self.allow_dcs {
+            if !Self::in_dcs(allowed, peer) {
+                return false;
+            }
+        }
+        if Self::in_dcs(&self.deny_dcs, peer) {
+            return false;
+        }
+        match &self.predicate {
+            Some(predicate) => Self::call_predicate(predicate, peer),
+            None => true,
+        }
+    }
+}
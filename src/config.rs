@@ -0,0 +1,317 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::profile::ExecutionProfile;
+use crate::session::SessionBuilder;
+use crate::url::{parse_host, ConnectionUrl};
+
+// Profile applied as the session's default execution profile
+const DEFAULT_PROFILE: &str = "default";
+
+// Separates the profile name and field in environment variables, as in
+// RSYLLA_PROFILES__ANALYTICS__TIMEOUT_MS
+const ENV_SEPARATOR: &str = "__";
+
+fn invalid_config(source: &str, reason: String) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+        "Invalid session configuration in {}: {}",
+        source, reason
+    ))
+}
+
+fn config_string(source: &str, key: &str, value: &Bound<'_, PyAny>) -> PyResult<String> {
+    value
+        .extract::<String>()
+        .map_err(|_| invalid_config(source, format!("{} must be a string, got {}", key, value)))
+}
+
+// Scalars as the strings connection URLs give them as
+fn option_string(source: &str, key: &str, value: &Bound<'_, PyAny>) -> PyResult<String> {
+    if let Ok(value) = value.cast::<PyBool>() {
+        return Ok(value.is_true().to_string());
+    }
+    if value.cast::<PyString>().is_ok()
+        || value.cast::<PyInt>().is_ok()
+        || value.cast::<PyFloat>().is_ok()
+    {
+        return Ok(value.str()?.to_string());
+    }
+    Err(invalid_config(
+        source,
+        format!("{} must be a string, number or boolean, got {}", key, value),
+    ))
+}
+
+// Environment variables are strings, profile fields are typed
+fn env_value<'py>(py: Python<'py>, value: &str) -> PyResult<Bound<'py, PyAny>> {
+    Ok(match value.to_ascii_lowercase().as_str() {
+        "true" => PyBool::new(py, true).to_owned().into_any(),
+        "false" => PyBool::new(py, false).to_owned().into_any(),
+        _ => match (value.parse::<i64>(), value.parse::<f64>()) {
+            (Ok(int), _) => int.into_pyobject(py)?.into_any(),
+            (_, Ok(float)) => float.into_pyobject(py)?.into_any(),
+            _ => PyString::new(py, value).into_any(),
+        },
+    })
+}
+
+// Session settings read from a file or the environment, turned into a SessionBuilder
+#[pyclass]
+#[derive(Clone)]
+pub struct SessionConfig {
+    settings: ConnectionUrl,
+    profiles: BTreeMap<String, ExecutionProfile>,
+}
+
+impl SessionConfig {
+    fn from_mapping(source: &str, mapping: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let py = mapping.py();
+        let mapping = mapping
+            .cast::<PyDict>()
+            .map_err(|_| invalid_config(source, "expected a mapping of settings".to_string()))?;
+        let mut settings = match mapping.get_item("url")? {
+            Some(url) => ConnectionUrl::parse(&config_string(source, "url", &url)?)?,
+            None => ConnectionUrl::default(),
+        };
+        let mut profiles = BTreeMap::new();
+        let mut username = None;
+        let mut password = None;
+
+        for (key, value) in mapping.iter() {
+            let key = config_string(source, "keys", &key)?;
+            match key.as_str() {
+                "url" => {}
+                "contact_points" => {
+                    let hosts = if let Ok(hosts) = value.extract::<String>() {
+                        hosts
+                            .split(',')
+                            .map(|host| host.trim().to_string())
+                            .collect()
+                    } else if value.cast::<PyList>().is_ok() || value.cast::<PyTuple>().is_ok() {
+                        value
+                            .try_iter()?
+                            .map(|host| config_string(source, &key, &host?))
+                            .collect::<PyResult<Vec<_>>>()?
+                    } else {
+                        return Err(invalid_config(
+                            source,
+                            format!("contact_points must be a list of hosts, got {}", value),
+                        ));
+                    };
+                    settings.nodes = hosts
+                        .iter()
+                        .map(|host| {
+                            parse_host(host).map_err(|reason| invalid_config(source, reason))
+                        })
+                        .collect::<PyResult<_>>()?;
+                }
+                "keyspace" => settings.keyspace = Some(config_string(source, &key, &value)?),
+                "username" => username = Some(config_string(source, &key, &value)?),
+                "password" => password = Some(config_string(source, &key, &value)?),
+                "auth" => {
+                    let auth = value.cast::<PyDict>().map_err(|_| {
+                        invalid_config(source, "auth must be a mapping".to_string())
+                    })?;
+                    for (field, value) in auth.iter() {
+                        match config_string(source, "keys", &field)?.as_str() {
+                            "username" => {
+                                username = Some(config_string(source, "username", &value)?)
+                            }
+                            "password" => {
+                                password = Some(config_string(source, "password", &value)?)
+                            }
+                            field => {
+                                return Err(invalid_config(
+                                    source,
+                                    format!("unknown auth setting {}", field),
+                                ))
+                            }
+                        }
+                    }
+                }
+                "profiles" => {
+                    let definitions = value.cast::<PyDict>().map_err(|_| {
+                        invalid_config(source, "profiles must be a mapping".to_string())
+                    })?;
+                    for (name, fields) in definitions.iter() {
+                        let name = config_string(source, "profile names", &name)?;
+                        let fields = fields.cast::<PyDict>().map_err(|_| {
+                            invalid_config(source, format!("profile {} must be a mapping", name))
+                        })?;
+                        let profile = py
+                            .get_type::<ExecutionProfile>()
+                            .call((), Some(fields))
+                            .map_err(|err| {
+                                invalid_config(source, format!("profile {}: {}", name, err))
+                            })?
+                            .extract::<ExecutionProfile>()?;
+                        profiles.insert(name, profile);
+                    }
+                }
+                _ => {
+                    let value = option_string(source, &key, &value)?;
+                    settings.options.push((key, value));
+                }
+            }
+        }
+
+        match (username, password) {
+            (Some(username), password) => {
+                settings.credentials = Some((username, password.unwrap_or_default()));
+            }
+            (None, Some(_)) => {
+                return Err(invalid_config(
+                    source,
+                    "password is set without a username".to_string(),
+                ))
+            }
+            (None, None) => {}
+        }
+        if settings.nodes.is_empty() {
+            return Err(invalid_config(
+                source,
+                "no contact_points or url given".to_string(),
+            ));
+        }
+
+        let config = SessionConfig { settings, profiles };
+        // Unknown options and invalid values are reported when loading, not when connecting
+        config.builder()?;
+        Ok(config)
+    }
+}
+
+#[pymethods]
+impl SessionConfig {
+    // TOML, YAML or JSON, told apart by the file extension
+    #[staticmethod]
+    pub fn from_file(py: Python, path: PathBuf) -> PyResult<Self> {
+        let source = path.display().to_string();
+        let text = std::fs::read_to_string(&path).map_err(|err| {
+            PyErr::new::<pyo3::exceptions::PyOSError, _>(format!(
+                "Can't read configuration file {}: {}",
+                source, err
+            ))
+        })?;
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase());
+        let settings = match extension.as_deref() {
+            Some("toml") => py.import("tomllib")?.call_method1("loads", (text,))?,
+            Some("yaml") | Some("yml") => py.import("yaml")?.call_method1("safe_load", (text,))?,
+            Some("json") => py.import("json")?.call_method1("loads", (text,))?,
+            _ => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unsupported configuration file {}: expected a .toml, .yaml, .yml or .json file",
+                source
+            )))
+            }
+        };
+        Self::from_mapping(&source, &settings)
+    }
+
+    // Variables named after the settings, e.g. RSYLLA_CONTACT_POINTS, with profile fields as
+    // RSYLLA_PROFILES__<NAME>__<FIELD>
+    #[staticmethod]
+    #[pyo3(signature = (prefix="RSYLLA_"))]
+    pub fn from_env(py: Python, prefix: &str) -> PyResult<Self> {
+        let settings = PyDict::new(py);
+        let profiles = PyDict::new(py);
+        let mut variables: Vec<_> = std::env::vars()
+            .filter_map(|(name, value)| Some((name.strip_prefix(prefix)?.to_lowercase(), value)))
+            .collect();
+        variables.sort();
+        for (name, value) in variables {
+            let profile_field = name
+                .strip_prefix("profiles")
+                .and_then(|rest| rest.strip_prefix(ENV_SEPARATOR))
+                .and_then(|rest| rest.split_once(ENV_SEPARATOR));
+            match profile_field {
+                Some((profile, field)) => {
+                    let fields = match profiles.get_item(profile)? {
+                        Some(fields) => fields.cast_into::<PyDict>()?,
+                        None => {
+                            let fields = PyDict::new(py);
+                            profiles.set_item(profile, &fields)?;
+                            fields
+                        }
+                    };
+                    fields.set_item(field, env_value(py, &value)?)?;
+                }
+                None => settings.set_item(name, value)?,
+            }
+        }
+        if !profiles.is_empty() {
+            settings.set_item("profiles", profiles)?;
+        }
+        Self::from_mapping(&format!("{}* environment variables", prefix), &settings)
+    }
+
+    // Builder with the settings, to be configured further in code
+    pub fn builder(&self) -> PyResult<SessionBuilder> {
+        let default_profile = self
+            .profiles
+            .get(DEFAULT_PROFILE)
+            .cloned()
+            .unwrap_or_default();
+        SessionBuilder::from_settings(&self.settings, default_profile)
+    }
+
+    pub fn build<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.builder()?.build(py)
+    }
+
+    pub fn build_blocking(&self, py: Python) -> PyResult<Py<PyAny>> {
+        self.builder()?.build_blocking(py)
+    }
+
+    pub fn profile(&self, name: &str) -> PyResult<ExecutionProfile> {
+        self.profiles.get(name).cloned().ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyKeyError, _>(format!("No profile named {}", name))
+        })
+    }
+
+    #[getter]
+    pub fn profiles(&self) -> BTreeMap<String, ExecutionProfile> {
+        self.profiles.clone()
+    }
+
+    #[getter]
+    pub fn contact_points(&self) -> Vec<String> {
+        self.settings.nodes.clone()
+    }
+
+    #[getter]
+    pub fn keyspace(&self) -> Option<String> {
+        self.settings.keyspace.clone()
+    }
+
+    #[getter]
+    pub fn username(&self) -> Option<String> {
+        self.settings
+            .credentials
+            .as_ref()
+            .map(|(username, _)| username.clone())
+    }
+
+    // Without the password
+    pub fn __repr__(&self) -> String {
+        let mut fields = vec![format!("contact_points={:?}", self.settings.nodes)];
+        if let Some(keyspace) = &self.settings.keyspace {
+            fields.push(format!("keyspace={:?}", keyspace));
+        }
+        if let Some(username) = self.username() {
+            fields.push(format!("username={:?}", username));
+        }
+        if !self.profiles.is_empty() {
+            fields.push(format!(
+                "profiles={:?}",
+                self.profiles.keys().collect::<Vec<_>>()
+            ));
+        }
+        format!("SessionConfig({})", fields.join(", "))
+    }
+}
@@ -0,0 +1,185 @@
+use pyo3::prelude::*;
+use scylla::cluster::metadata::{ColumnType, NativeType};
+use scylla::value::CqlValue;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+use crate::metadata::native_type_to_cql;
+
+const NATIVE_TYPES: [&str; 20] = [
+    "ascii",
+    "bigint",
+    "blob",
+    "boolean",
+    "counter",
+    "date",
+    "decimal",
+    "double",
+    "duration",
+    "float",
+    "inet",
+    "int",
+    "smallint",
+    "text",
+    "time",
+    "timestamp",
+    "timeuuid",
+    "tinyint",
+    "uuid",
+    "varint",
+];
+
+struct Converter {
+    encode: Option<Py<PyAny>>,
+    decode: Option<Py<PyAny>>,
+}
+
+// Checked before taking the lock, so conversions cost nothing until a converter is registered
+static ENCODERS: AtomicBool = AtomicBool::new(false);
+static DECODERS: AtomicBool = AtomicBool::new(false);
+static CONVERTERS: RwLock<Option<HashMap<String, Converter>>> = RwLock::new(None);
+
+fn update_flags(converters: &HashMap<String, Converter>) {
+    let encoders = converters.values().any(|c| c.encode.is_some());
+    let decoders = converters.values().any(|c| c.decode.is_some());
+    ENCODERS.store(encoders, Ordering::Relaxed);
+    DECODERS.store(decoders, Ordering::Relaxed);
+}
+
+// Native types go by their CQL name, user types by "keyspace.type"
+fn registry_key(cql_type: &str) -> PyResult<String> {
+    let cql_type = cql_type.trim();
+    let lowered = cql_type.to_lowercase();
+    if lowered == "varchar" {
+        return Ok("text".to_string());
+    }
+    if NATIVE_TYPES.contains(&lowered.as_str()) {
+        return Ok(lowered);
+    }
+    match cql_type.split_once('.') {
+        Some((keyspace, name)) if !keyspace.is_empty() && !name.is_empty() => {
+            Ok(cql_type.to_string())
+        }
+        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Invalid converter type: {}. Must be a native CQL type or a user type as 'keyspace.type'",
+            cql_type
+        ))),
+    }
+}
+
+fn column_type_key(typ: &ColumnType) -> Option<String> {
+    match typ {
+        ColumnType::Native(native) => Some(native_type_to_cql(native).to_string()),
+        ColumnType::UserDefinedType { definition, .. } => {
+            Some(format!("{}.{}", definition.keyspace, definition.name))
+        }
+        _ => None,
+    }
+}
+
+fn value_key(value: &CqlValue) -> Option<String> {
+    let native = match value {
+        CqlValue::Ascii(_) => NativeType::Ascii,
+        CqlValue::BigInt(_) => NativeType::BigInt,
+        CqlValue::Blob(_) => NativeType::Blob,
+        CqlValue::Boolean(_) => NativeType::Boolean,
+        CqlValue::Counter(_) => NativeType::Counter,
+        CqlValue::Date(_) => NativeType::Date,
+        CqlValue::Decimal(_) => NativeType::Decimal,
+        CqlValue::Double(_) => NativeType::Double,
+        CqlValue::Duration(_) => NativeType::Duration,
+        CqlValue::Float(_) => NativeType::Float,
+        CqlValue::Inet(_) => NativeType::Inet,
+        CqlValue::Int(_) => NativeType::Int,
+        CqlValue::SmallInt(_) => NativeType::SmallInt,
+        CqlValue::Text(_) => NativeType::Text,
+        CqlValue::Time(_) => NativeType::Time,
+        CqlValue::Timestamp(_) => NativeType::Timestamp,
+        CqlValue::Timeuuid(_) => NativeType::Timeuuid,
+        CqlValue::TinyInt(_) => NativeType::TinyInt,
+        CqlValue::Uuid(_) => NativeType::Uuid,
+        CqlValue::Varint(_) => NativeType::Varint,
+        CqlValue::UserDefinedType { keyspace, name, .. } => {
+            return Some(format!("{}.{}", keyspace, name))
+        }
+        _ => return None,
+    };
+    Some(native_type_to_cql(&native).to_string())
+}
+
+pub fn has_encoders() -> bool {
+    ENCODERS.load(Ordering::Relaxed)
+}
+
+pub fn has_decoders() -> bool {
+    DECODERS.load(Ordering::Relaxed)
+}
+
+fn lookup(py: Python, key: &str, encode: bool) -> Option<Py<PyAny>> {
+    let converters = CONVERTERS.read().unwrap();
+    let converter = converters.as_ref()?.get(key)?;
+    let callable = if encode {
+        &converter.encode
+    } else {
+        &converter.decode
+    };
+    callable.as_ref().map(|callable| callable.clone_ref(py))
+}
+
+// Passes a value bound to a column of type `typ` through its registered encoder, if any
+pub fn encode<'py>(value: &Bound<'py, PyAny>, typ: &ColumnType) -> PyResult<Bound<'py, PyAny>> {
+    let py = value.py();
+    match column_type_key(typ).and_then(|key| lookup(py, &key, true)) {
+        Some(encode) => encode.bind(py).call1((value,)),
+        None => Ok(value.clone()),
+    }
+}
+
+// Passes the default Python conversion of `value` through its registered decoder, if any
+pub fn decode(py: Python, value: &CqlValue, converted: Py<PyAny>) -> PyResult<Py<PyAny>> {
+    match value_key(value).and_then(|key| lookup(py, &key, false)) {
+        Some(decode) => Ok(decode.bind(py).call1((converted,))?.unbind()),
+        None => Ok(converted),
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (cql_type, encode=None, decode=None))]
+pub fn register_converter(
+    cql_type: &str,
+    encode: Option<Py<PyAny>>,
+    decode: Option<Py<PyAny>>,
+) -> PyResult<()> {
+    if encode.is_none() && decode.is_none() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "A converter needs an encode or a decode callable",
+        ));
+    }
+    let key = registry_key(cql_type)?;
+    // Dropping the replaced callables may run Python code, so it happens once the lock is released
+    let _previous = {
+        let mut converters = CONVERTERS.write().unwrap();
+        let converters = converters.get_or_insert_with(HashMap::new);
+        let previous = converters.insert(key, Converter { encode, decode });
+        update_flags(converters);
+        previous
+    };
+    Ok(())
+}
+
+// Returns whether a converter was registered for the type
+#[pyfunction]
+pub fn unregister_converter(cql_type: &str) -> PyResult<bool> {
+    let key = registry_key(cql_type)?;
+    let removed = {
+        let mut converters = CONVERTERS.write().unwrap();
+        let Some(converters) = converters.as_mut() else {
+            return Ok(false);
+        };
+        let removed = converters.remove(&key);
+        update_flags(converters);
+        removed
+    };
+    Ok(removed.is_some())
+}
@@ -0,0 +1,494 @@
+use futures::stream::{self, StreamExt};
+use num_bigint::BigInt;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+use scylla::client::session::Session as ScyllaSession;
+use scylla::cluster::metadata::{ColumnType, NativeType};
+use scylla::value::{
+    Counter, CqlDate, CqlDecimal, CqlTime, CqlTimestamp, CqlTimeuuid, CqlValue, CqlVarint,
+};
+use std::collections::VecDeque;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use uuid::Uuid;
+
+use crate::error::{prepare_error_to_py, query_error_to_py};
+use crate::metadata::{lookup_table, ordered_columns};
+use crate::rate_limit::Throttle;
+use crate::result::BulkResult;
+
+// Rows read from a parquet file at a time
+const PARQUET_BATCH_SIZE: usize = 1024;
+
+pub enum CopyFormat {
+    Csv {
+        header: bool,
+        delimiter: char,
+        null: String,
+    },
+    Parquet,
+}
+
+impl CopyFormat {
+    pub fn parse(format: &str, header: bool, delimiter: &str, null: &str) -> PyResult<Self> {
+        match format.to_lowercase().as_str() {
+            "csv" => {
+                let mut chars = delimiter.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(delimiter), None) if delimiter != '"' => Ok(CopyFormat::Csv {
+                        header,
+                        delimiter,
+                        null: null.to_string(),
+                    }),
+                    _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Invalid delimiter: {:?}. Must be a single character other than '\"'",
+                        delimiter
+                    ))),
+                }
+            }
+            "parquet" => Ok(CopyFormat::Parquet),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid format: {}. Must be 'csv' or 'parquet'",
+                format
+            ))),
+        }
+    }
+}
+
+fn invalid_value(column: &str, text: &str, typ: &ColumnType) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+        "Invalid value for column {} of type {:?}: {:?}",
+        column, typ, text
+    ))
+}
+
+fn parse_timestamp(text: &str) -> Option<i64> {
+    if let Ok(millis) = text.parse::<i64>() {
+        return Some(millis);
+    }
+    if let Ok(timestamp) = chrono::DateTime::parse_from_rfc3339(text) {
+        return Some(timestamp.timestamp_millis());
+    }
+    for format in ["%Y-%m-%d %H:%M:%S%.f%:z", "%Y-%m-%d %H:%M:%S%.f%z"] {
+        if let Ok(timestamp) = chrono::DateTime::parse_from_str(text, format) {
+            return Some(timestamp.timestamp_millis());
+        }
+    }
+    // Timestamps without a zone are UTC
+    for format in ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"] {
+        if let Ok(timestamp) = chrono::NaiveDateTime::parse_from_str(text, format) {
+            return Some(timestamp.and_utc().timestamp_millis());
+        }
+    }
+    chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .ok()
+        .map(|date| {
+            date.and_time(chrono::NaiveTime::MIN)
+                .and_utc()
+                .timestamp_millis()
+        })
+}
+
+pub(crate) fn parse_decimal(text: &str) -> Option<CqlDecimal> {
+    let (mantissa, exponent) = match text.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i32>().ok()?),
+        None => (text, 0),
+    };
+    let (digits, scale) = match mantissa.split_once('.') {
+        Some((integer, fraction)) => (format!("{}{}", integer, fraction), fraction.len() as i32),
+        None => (mantissa.to_string(), 0),
+    };
+    let unscaled = BigInt::from_str(&digits).ok()?;
+    Some(CqlDecimal::from_signed_be_bytes_and_exponent(
+        unscaled.to_signed_bytes_be(),
+        scale - exponent,
+    ))
+}
+
+fn parse_blob(text: &str) -> Option<Vec<u8>> {
+    let hex = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))?;
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// Parses a field as written by cqlsh COPY TO into a value of the column's type
+fn parse_value(column: &str, text: &str, typ: &ColumnType) -> PyResult<CqlValue> {
+    let invalid = || invalid_value(column, text, typ);
+    let ColumnType::Native(native) = typ else {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Cannot load column {} of type {:?}: only native types are supported",
+            column, typ
+        )));
+    };
+    let text = match native {
+        NativeType::Ascii | NativeType::Text => text,
+        _ => text.trim(),
+    };
+    let value = match native {
+        NativeType::Ascii => CqlValue::Ascii(text.to_string()),
+        NativeType::Text => CqlValue::Text(text.to_string()),
+        NativeType::Boolean => match text.to_lowercase().as_str() {
+            "true" => CqlValue::Boolean(true),
+            "false" => CqlValue::Boolean(false),
+            _ => return Err(invalid()),
+        },
+        NativeType::TinyInt => CqlValue::TinyInt(text.parse().map_err(|_| invalid())?),
+        NativeType::SmallInt => CqlValue::SmallInt(text.parse().map_err(|_| invalid())?),
+        NativeType::Int => CqlValue::Int(text.parse().map_err(|_| invalid())?),
+        NativeType::BigInt => CqlValue::BigInt(text.parse().map_err(|_| invalid())?),
+        NativeType::Counter => CqlValue::Counter(Counter(text.parse().map_err(|_| invalid())?)),
+        NativeType::Varint => CqlValue::Varint(CqlVarint::from_signed_bytes_be(
+            BigInt::from_str(text)
+                .map_err(|_| invalid())?
+                .to_signed_bytes_be(),
+        )),
+        NativeType::Float => CqlValue::Float(text.parse().map_err(|_| invalid())?),
+        NativeType::Double => CqlValue::Double(text.parse().map_err(|_| invalid())?),
+        NativeType::Decimal => CqlValue::Decimal(parse_decimal(text).ok_or_else(invalid)?),
+        NativeType::Uuid => CqlValue::Uuid(Uuid::parse_str(text).map_err(|_| invalid())?),
+        NativeType::Timeuuid => CqlValue::Timeuuid(CqlTimeuuid::from(
+            Uuid::parse_str(text).map_err(|_| invalid())?,
+        )),
+        NativeType::Inet => CqlValue::Inet(text.parse::<IpAddr>().map_err(|_| invalid())?),
+        NativeType::Blob => CqlValue::Blob(parse_blob(text).ok_or_else(invalid)?),
+        NativeType::Timestamp => {
+            CqlValue::Timestamp(CqlTimestamp(parse_timestamp(text).ok_or_else(invalid)?))
+        }
+        NativeType::Date => {
+            let date =
+                chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d").map_err(|_| invalid())?;
+            let days = date
+                .signed_duration_since(chrono::DateTime::UNIX_EPOCH.date_naive())
+                .num_days();
+            // Dates are stored as days since the epoch, centered at 2^31
+            CqlValue::Date(CqlDate((days + (1 << 31)) as u32))
+        }
+        NativeType::Time => {
+            let time =
+                chrono::NaiveTime::parse_from_str(text, "%H:%M:%S%.f").map_err(|_| invalid())?;
+            let nanos = time
+                .signed_duration_since(chrono::NaiveTime::MIN)
+                .num_nanoseconds()
+                .ok_or_else(invalid)?;
+            CqlValue::Time(CqlTime(nanos))
+        }
+        _ => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Cannot load column {} of type {:?}",
+                column, typ
+            )))
+        }
+    };
+    Ok(value)
+}
+
+// Splits a CSV record into fields, unescaping quoted ones. Quoted fields are never null.
+fn split_record(record: &str, delimiter: char) -> Vec<(String, bool)> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut chars = record.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c != '"' {
+                field.push(c);
+            } else if chars.peek() == Some(&'"') {
+                field.push('"');
+                chars.next();
+            } else {
+                in_quotes = false;
+            }
+        } else if c == '"' {
+            in_quotes = true;
+            quoted = true;
+        } else if c == delimiter {
+            fields.push((std::mem::take(&mut field), quoted));
+            quoted = false;
+        } else {
+            field.push(c);
+        }
+    }
+    fields.push((field, quoted));
+    fields
+}
+
+// A record that could be read, and either converted or not
+type Record = PyResult<Vec<Option<CqlValue>>>;
+
+enum Source {
+    Csv {
+        lines: tokio::io::Lines<BufReader<File>>,
+        delimiter: char,
+        null: String,
+    },
+    Parquet {
+        batches: Py<PyAny>,
+        records: VecDeque<Record>,
+    },
+}
+
+impl Source {
+    async fn open(
+        path: &PathBuf,
+        format: &CopyFormat,
+        columns: Option<Vec<String>>,
+    ) -> PyResult<(Self, Option<Vec<String>>)> {
+        match format {
+            CopyFormat::Csv {
+                header,
+                delimiter,
+                null,
+            } => {
+                let file = File::open(path).await?;
+                let mut source = Source::Csv {
+                    lines: BufReader::new(file).lines(),
+                    delimiter: *delimiter,
+                    null: null.clone(),
+                };
+                let mut columns = columns;
+                if *header {
+                    let names = source.next_fields().await?.unwrap_or_default();
+                    columns = columns.or_else(|| {
+                        Some(
+                            names
+                                .into_iter()
+                                .map(|(name, _)| name.trim().to_string())
+                                .collect(),
+                        )
+                    });
+                }
+                Ok((source, columns))
+            }
+            CopyFormat::Parquet => Python::attach(|py| {
+                let file = py
+                    .import("pyarrow.parquet")?
+                    .call_method1("ParquetFile", (path,))?;
+                let columns = match columns {
+                    Some(columns) => columns,
+                    None => file.getattr("schema_arrow")?.getattr("names")?.extract()?,
+                };
+                let kwargs = PyDict::new(py);
+                kwargs.set_item("batch_size", PARQUET_BATCH_SIZE)?;
+                kwargs.set_item("columns", &columns)?;
+                let batches = file.call_method("iter_batches", (), Some(&kwargs))?;
+                Ok((
+                    Source::Parquet {
+                        batches: batches.unbind(),
+                        records: VecDeque::new(),
+                    },
+                    Some(columns),
+                ))
+            }),
+        }
+    }
+
+    // Reads the fields of the next CSV record, which may span several lines
+    async fn next_fields(&mut self) -> PyResult<Option<Vec<(String, bool)>>> {
+        let Source::Csv {
+            lines, delimiter, ..
+        } = self
+        else {
+            return Ok(None);
+        };
+        let mut record = String::new();
+        loop {
+            let Some(line) = lines.next_line().await? else {
+                return Ok((!record.is_empty()).then(|| split_record(&record, *delimiter)));
+            };
+            let line = line.strip_suffix('\r').unwrap_or(&line);
+            if record.is_empty() && line.is_empty() {
+                continue;
+            }
+            if !record.is_empty() {
+                record.push('\n');
+            }
+            record.push_str(line);
+            // An odd number of quotes means a quoted field continues on the next line
+            if record.matches('"').count().is_multiple_of(2) {
+                return Ok(Some(split_record(&record, *delimiter)));
+            }
+        }
+    }
+
+    async fn next(
+        &mut self,
+        columns: &[(String, ColumnType<'static>)],
+    ) -> PyResult<Option<Record>> {
+        match self {
+            Source::Csv { null, .. } => {
+                let null = null.clone();
+                let Some(fields) = self.next_fields().await? else {
+                    return Ok(None);
+                };
+                if fields.len() != columns.len() {
+                    return Ok(Some(Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        format!("Expected {} fields, got {}", columns.len(), fields.len()),
+                    ))));
+                }
+                Ok(Some(
+                    fields
+                        .iter()
+                        .zip(columns)
+                        .map(|((text, quoted), (name, typ))| {
+                            if !quoted && *text == null {
+                                Ok(None)
+                            } else {
+                                parse_value(name, text, typ).map(Some)
+                            }
+                        })
+                        .collect(),
+                ))
+            }
+            Source::Parquet { batches, records } => {
+                if records.is_empty() {
+                    Python::attach(|py| -> PyResult<()> {
+                        let Some(batch) = batches.bind(py).try_iter()?.next() else {
+                            return Ok(());
+                        };
+                        for row in batch?.call_method0("to_pylist")?.try_iter()? {
+                            let row = row?;
+                            let row = row.cast::<PyDict>()?;
+                            records.push_back(
+                                columns
+                                    .iter()
+                                    .map(|(name, typ)| match row.get_item(name)? {
+                                        Some(value) if !value.is_none() => {
+                                            py_to_value(name, &value, typ).map(Some)
+                                        }
+                                        _ => Ok(None),
+                                    })
+                                    .collect(),
+                            );
+                        }
+                        Ok(())
+                    })?;
+                }
+                Ok(records.pop_front())
+            }
+        }
+    }
+}
+
+// Parquet values go through their string form, like CSV fields, except for binary data
+fn py_to_value(column: &str, value: &Bound<'_, PyAny>, typ: &ColumnType) -> PyResult<CqlValue> {
+    if let Ok(bytes) = value.cast::<PyBytes>() {
+        if typ == &ColumnType::Native(NativeType::Blob) {
+            return Ok(CqlValue::Blob(bytes.as_bytes().to_vec()));
+        }
+    }
+    parse_value(column, &value.str()?.to_cow()?, typ)
+}
+
+struct Reader {
+    source: Source,
+    columns: Vec<(String, ColumnType<'static>)>,
+    failed: bool,
+}
+
+impl Reader {
+    async fn next(&mut self) -> Option<PyResult<Record>> {
+        if self.failed {
+            return None;
+        }
+        let record = self.source.next(&self.columns).await;
+        self.failed = record.is_err();
+        record.transpose()
+    }
+}
+
+pub async fn copy_from(
+    session: Arc<ScyllaSession>,
+    path: PathBuf,
+    table: String,
+    format: CopyFormat,
+    columns: Option<Vec<String>>,
+    concurrency: usize,
+    throttle: Throttle,
+) -> PyResult<BulkResult> {
+    let (keyspace, table_name, metadata) = lookup_table(&session, &table)?;
+    let (source, columns) = Source::open(&path, &format, columns).await?;
+    let columns =
+        columns.unwrap_or_else(|| ordered_columns(&metadata).into_iter().cloned().collect());
+    let columns = columns
+        .into_iter()
+        .map(|name| match metadata.columns.get(&name) {
+            Some(column) => Ok((name, column.typ.clone())),
+            None => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unknown column {} in table {}.{}",
+                name, keyspace, table_name
+            ))),
+        })
+        .collect::<PyResult<Vec<(String, ColumnType<'static>)>>>()?;
+
+    let quote = |name: &str| format!("\"{}\"", name.replace('"', "\"\""));
+    let insert = format!(
+        "INSERT INTO {}.{} ({}) VALUES ({})",
+        quote(&keyspace),
+        quote(&table_name),
+        columns
+            .iter()
+            .map(|(name, _)| quote(name))
+            .collect::<Vec<_>>()
+            .join(", "),
+        vec!["?"; columns.len()].join(", ")
+    );
+    let prepared = session.prepare(insert).await.map_err(prepare_error_to_py)?;
+
+    // Reading stops at the first I/O error, while invalid records are reported and skipped
+    let reader = Reader {
+        source,
+        columns,
+        failed: false,
+    };
+    let records = stream::unfold(reader, |mut reader| async move {
+        reader.next().await.map(|record| (record, reader))
+    });
+
+    let prepared = Arc::new(prepared);
+    let results = records
+        .enumerate()
+        .map(|(index, record)| {
+            let session = session.clone();
+            let prepared = prepared.clone();
+            let throttle = throttle.clone();
+            async move {
+                let values = match record? {
+                    Ok(values) => values,
+                    Err(err) => return Ok((index, Err(err))),
+                };
+                throttle.wait().await;
+                let result = session
+                    .execute_unpaged(&prepared, values)
+                    .await
+                    .map(|_| ())
+                    .map_err(query_error_to_py);
+                Ok::<_, PyErr>((index, result))
+            }
+        })
+        .buffer_unordered(concurrency);
+    let mut results = std::pin::pin!(results);
+
+    let mut succeeded = 0;
+    let mut errors = Vec::new();
+    while let Some(result) = results.next().await {
+        match result? {
+            (_, Ok(())) => succeeded += 1,
+            (index, Err(err)) => errors.push((index, err)),
+        }
+    }
+    errors.sort_by_key(|(index, _)| *index);
+
+    Python::attach(|py| BulkResult::new(py, succeeded, 0, errors))
+}
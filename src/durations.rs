@@ -0,0 +1,94 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use scylla::value::CqlDuration;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+const NANOS_PER_MICRO: i64 = 1_000;
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+const DICT: u8 = 0;
+const TIMEDELTA: u8 = 1;
+const TIMEDELTA_STRICT: u8 = 2;
+
+// Durations are read as dicts unless the mode asks for timedeltas
+static DURATION_MODE: AtomicU8 = AtomicU8::new(DICT);
+
+fn duration_to_dict(py: Python, duration: &CqlDuration) -> PyResult<Py<PyAny>> {
+    let dict = PyDict::new(py);
+    dict.set_item("months", duration.months)?;
+    dict.set_item("days", duration.days)?;
+    dict.set_item("nanoseconds", duration.nanoseconds)?;
+    Ok(dict.into())
+}
+
+pub fn duration_to_py(py: Python, duration: &CqlDuration) -> PyResult<Py<PyAny>> {
+    let mode = DURATION_MODE.load(Ordering::Relaxed);
+    if mode == DICT {
+        return duration_to_dict(py, duration);
+    }
+    // Months have no fixed length and timedeltas stop at microseconds
+    if duration.months != 0 || duration.nanoseconds % NANOS_PER_MICRO != 0 {
+        if mode == TIMEDELTA {
+            return duration_to_dict(py, duration);
+        }
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Duration of {} months, {} days and {} nanoseconds can't be read as a timedelta",
+            duration.months, duration.days, duration.nanoseconds
+        )));
+    }
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("days", duration.days)?;
+    kwargs.set_item("microseconds", duration.nanoseconds / NANOS_PER_MICRO)?;
+    let timedelta = py.import("datetime")?.getattr("timedelta")?;
+    Ok(timedelta.call((), Some(&kwargs))?.unbind())
+}
+
+// A timedelta is normalized with only its days negative, while the components of a CQL
+// duration all share its sign
+pub fn timedelta_to_duration(val: &Bound<'_, PyAny>) -> PyResult<CqlDuration> {
+    let days: i64 = val.getattr("days")?.extract()?;
+    let seconds: i64 = val.getattr("seconds")?.extract()?;
+    let microseconds: i64 = val.getattr("microseconds")?.extract()?;
+    let total =
+        days as i128 * MICROS_PER_DAY as i128 + (seconds * 1_000_000 + microseconds) as i128;
+    let days = i32::try_from(total / MICROS_PER_DAY as i128).map_err(|_| {
+        PyErr::new::<pyo3::exceptions::PyOverflowError, _>(format!(
+            "{} days is out of range for a duration",
+            days
+        ))
+    })?;
+    Ok(CqlDuration {
+        months: 0,
+        days,
+        nanoseconds: (total % MICROS_PER_DAY as i128) as i64 * NANOS_PER_MICRO,
+    })
+}
+
+// "dict" reads durations as dicts of months, days and nanoseconds. "timedelta" reads them as
+// datetime.timedelta, keeping the dict for those a timedelta can't hold, which
+// "timedelta_strict" rejects with a ValueError instead.
+#[pyfunction]
+pub fn set_duration_mode(mode: &str) -> PyResult<()> {
+    let mode = match mode {
+        "dict" => DICT,
+        "timedelta" => TIMEDELTA,
+        "timedelta_strict" => TIMEDELTA_STRICT,
+        _ => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid duration mode: {}. Must be 'dict', 'timedelta' or 'timedelta_strict'",
+                mode
+            )))
+        }
+    };
+    DURATION_MODE.store(mode, Ordering::Relaxed);
+    Ok(())
+}
+
+#[pyfunction]
+pub fn get_duration_mode() -> &'static str {
+    match DURATION_MODE.load(Ordering::Relaxed) {
+        TIMEDELTA => "timedelta",
+        TIMEDELTA_STRICT => "timedelta_strict",
+        _ => "dict",
+    }
+}
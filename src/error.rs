@@ -1,36 +1,449 @@
 use pyo3::create_exception;
 use pyo3::prelude::*;
+use pyo3::IntoPyObjectExt;
+use scylla::errors::{
+    ConnectionError, ConnectionPoolError, ConnectionSetupRequestErrorKind, DbError, ExecutionError,
+    MetadataError, NewSessionError, NextPageError, NextRowError, OperationType,
+    PagerExecutionError, PrepareError, RequestAttemptError, RequestError, SchemaAgreementError,
+    UseKeyspaceError,
+};
+use std::time::Duration;
+
+use crate::query::consistency_to_str;
 
 create_exception!(rsylla, ScyllaError, pyo3::exceptions::PyException);
 
+create_exception!(rsylla, NoHostAvailable, ScyllaError);
+create_exception!(rsylla, AuthenticationError, ScyllaError);
+create_exception!(rsylla, DeserializationError, ScyllaError);
+create_exception!(rsylla, ResultTooLarge, ScyllaError);
+create_exception!(rsylla, ForkedSessionError, ScyllaError);
+create_exception!(rsylla, StrictModeViolation, ScyllaError);
+
+create_exception!(rsylla, Unavailable, ScyllaError);
+create_exception!(rsylla, Overloaded, ScyllaError);
+create_exception!(rsylla, RateLimitReached, Overloaded);
+create_exception!(rsylla, IsBootstrapping, ScyllaError);
+create_exception!(rsylla, TruncateError, ScyllaError);
+create_exception!(rsylla, ServerError, ScyllaError);
+create_exception!(rsylla, ProtocolError, ScyllaError);
+
+create_exception!(rsylla, Timeout, ScyllaError);
+create_exception!(rsylla, ReadTimeout, Timeout);
+create_exception!(rsylla, WriteTimeout, Timeout);
+create_exception!(rsylla, OperationTimedOut, Timeout);
+
+create_exception!(rsylla, ReadFailure, ScyllaError);
+create_exception!(rsylla, WriteFailure, ScyllaError);
+create_exception!(rsylla, FunctionFailure, ScyllaError);
+
+create_exception!(rsylla, InvalidQuery, ScyllaError);
+create_exception!(rsylla, CqlSyntaxError, InvalidQuery);
+create_exception!(rsylla, Unauthorized, InvalidQuery);
+create_exception!(rsylla, ConfigurationError, InvalidQuery);
+create_exception!(rsylla, AlreadyExists, InvalidQuery);
+
+pub fn register_exceptions(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("ScyllaError", py.get_type::<ScyllaError>())?;
+    m.add("NoHostAvailable", py.get_type::<NoHostAvailable>())?;
+    m.add("AuthenticationError", py.get_type::<AuthenticationError>())?;
+    m.add(
+        "DeserializationError",
+        py.get_type::<DeserializationError>(),
+    )?;
+    m.add("ResultTooLarge", py.get_type::<ResultTooLarge>())?;
+    m.add("ForkedSessionError", py.get_type::<ForkedSessionError>())?;
+    m.add("StrictModeViolation", py.get_type::<StrictModeViolation>())?;
+    m.add("Unavailable", py.get_type::<Unavailable>())?;
+    m.add("Overloaded", py.get_type::<Overloaded>())?;
+    m.add("RateLimitReached", py.get_type::<RateLimitReached>())?;
+    m.add("IsBootstrapping", py.get_type::<IsBootstrapping>())?;
+    m.add("TruncateError", py.get_type::<TruncateError>())?;
+    m.add("ServerError", py.get_type::<ServerError>())?;
+    m.add("ProtocolError", py.get_type::<ProtocolError>())?;
+    m.add("Timeout", py.get_type::<Timeout>())?;
+    m.add("ReadTimeout", py.get_type::<ReadTimeout>())?;
+    m.add("WriteTimeout", py.get_type::<WriteTimeout>())?;
+    m.add("OperationTimedOut", py.get_type::<OperationTimedOut>())?;
+    m.add("ReadFailure", py.get_type::<ReadFailure>())?;
+    m.add("WriteFailure", py.get_type::<WriteFailure>())?;
+    m.add("FunctionFailure", py.get_type::<FunctionFailure>())?;
+    m.add("InvalidQuery", py.get_type::<InvalidQuery>())?;
+    m.add("CqlSyntaxError", py.get_type::<CqlSyntaxError>())?;
+    m.add("Unauthorized", py.get_type::<Unauthorized>())?;
+    m.add("ConfigurationError", py.get_type::<ConfigurationError>())?;
+    m.add("AlreadyExists", py.get_type::<AlreadyExists>())?;
+    Ok(())
+}
+
+fn operation_type_to_str(op_type: &OperationType) -> String {
+    match op_type {
+        OperationType::Read => "READ".to_string(),
+        OperationType::Write => "WRITE".to_string(),
+        OperationType::Other(code) => format!("OTHER({})", code),
+    }
+}
+
+// Picks the exception class matching a server-side error and attaches its structured fields
+fn db_error_to_py(db_error: &DbError, message: String) -> PyErr {
+    Python::attach(|py| -> PyResult<PyErr> {
+        let (err, attrs): (PyErr, Vec<(&str, Bound<PyAny>)>) = match db_error {
+            DbError::SyntaxError => (PyErr::new::<CqlSyntaxError, _>(message), vec![]),
+            DbError::Invalid => (PyErr::new::<InvalidQuery, _>(message), vec![]),
+            DbError::Unauthorized => (PyErr::new::<Unauthorized, _>(message), vec![]),
+            DbError::ConfigError => (PyErr::new::<ConfigurationError, _>(message), vec![]),
+            DbError::AuthenticationError => (PyErr::new::<AuthenticationError, _>(message), vec![]),
+            DbError::AlreadyExists { keyspace, table } => (
+                PyErr::new::<AlreadyExists, _>(message),
+                vec![
+                    ("keyspace", keyspace.into_bound_py_any(py)?),
+                    (
+                        "table",
+                        (!table.is_empty()).then_some(table).into_bound_py_any(py)?,
+                    ),
+                ],
+            ),
+            DbError::FunctionFailure {
+                keyspace,
+                function,
+                arg_types,
+            } => (
+                PyErr::new::<FunctionFailure, _>(message),
+                vec![
+                    ("keyspace", keyspace.into_bound_py_any(py)?),
+                    ("function", function.into_bound_py_any(py)?),
+                    ("arg_types", arg_types.into_bound_py_any(py)?),
+                ],
+            ),
+            DbError::Unavailable {
+                consistency,
+                required,
+                alive,
+            } => (
+                PyErr::new::<Unavailable, _>(message),
+                vec![
+                    (
+                        "consistency",
+                        consistency_to_str(*consistency).into_bound_py_any(py)?,
+                    ),
+                    ("required", required.into_bound_py_any(py)?),
+                    ("alive", alive.into_bound_py_any(py)?),
+                ],
+            ),
+            DbError::ReadTimeout {
+                consistency,
+                received,
+                required,
+                data_present,
+            } => (
+                PyErr::new::<ReadTimeout, _>(message),
+                vec![
+                    (
+                        "consistency",
+                        consistency_to_str(*consistency).into_bound_py_any(py)?,
+                    ),
+                    ("received", received.into_bound_py_any(py)?),
+                    ("required", required.into_bound_py_any(py)?),
+                    ("data_present", data_present.into_bound_py_any(py)?),
+                ],
+            ),
+            DbError::WriteTimeout {
+                consistency,
+                received,
+                required,
+                write_type,
+            } => (
+                PyErr::new::<WriteTimeout, _>(message),
+                vec![
+                    (
+                        "consistency",
+                        consistency_to_str(*consistency).into_bound_py_any(py)?,
+                    ),
+                    ("received", received.into_bound_py_any(py)?),
+                    ("required", required.into_bound_py_any(py)?),
+                    ("write_type", write_type.as_str().into_bound_py_any(py)?),
+                ],
+            ),
+            DbError::ReadFailure {
+                consistency,
+                received,
+                required,
+                numfailures,
+                data_present,
+            } => (
+                PyErr::new::<ReadFailure, _>(message),
+                vec![
+                    (
+                        "consistency",
+                        consistency_to_str(*consistency).into_bound_py_any(py)?,
+                    ),
+                    ("received", received.into_bound_py_any(py)?),
+                    ("required", required.into_bound_py_any(py)?),
+                    ("failures", numfailures.into_bound_py_any(py)?),
+                    ("data_present", data_present.into_bound_py_any(py)?),
+                ],
+            ),
+            DbError::WriteFailure {
+                consistency,
+                received,
+                required,
+                numfailures,
+                write_type,
+            } => (
+                PyErr::new::<WriteFailure, _>(message),
+                vec![
+                    (
+                        "consistency",
+                        consistency_to_str(*consistency).into_bound_py_any(py)?,
+                    ),
+                    ("received", received.into_bound_py_any(py)?),
+                    ("required", required.into_bound_py_any(py)?),
+                    ("failures", numfailures.into_bound_py_any(py)?),
+                    ("write_type", write_type.as_str().into_bound_py_any(py)?),
+                ],
+            ),
+            DbError::Overloaded => (PyErr::new::<Overloaded, _>(message), vec![]),
+            DbError::RateLimitReached {
+                op_type,
+                rejected_by_coordinator,
+            } => (
+                PyErr::new::<RateLimitReached, _>(message),
+                vec![
+                    (
+                        "op_type",
+                        operation_type_to_str(op_type).into_bound_py_any(py)?,
+                    ),
+                    (
+                        "rejected_by_coordinator",
+                        rejected_by_coordinator.into_bound_py_any(py)?,
+                    ),
+                ],
+            ),
+            DbError::IsBootstrapping => (PyErr::new::<IsBootstrapping, _>(message), vec![]),
+            DbError::TruncateError => (PyErr::new::<TruncateError, _>(message), vec![]),
+            DbError::ServerError => (PyErr::new::<ServerError, _>(message), vec![]),
+            DbError::ProtocolError => (PyErr::new::<ProtocolError, _>(message), vec![]),
+            _ => (PyErr::new::<ScyllaError, _>(message), vec![]),
+        };
+
+        let value = err.value(py);
+        for (name, attr) in attrs {
+            value.setattr(name, attr)?;
+        }
+        Ok(err)
+    })
+    .unwrap_or_else(|err| err)
+}
+
+fn attempt_error_to_py(err: &RequestAttemptError, message: String) -> PyErr {
+    match err {
+        RequestAttemptError::DbError(db_error, _) => db_error_to_py(db_error, message),
+        _ => PyErr::new::<ScyllaError, _>(message),
+    }
+}
+
+fn connection_pool_error_to_py(err: &ConnectionPoolError, message: String) -> PyErr {
+    if let ConnectionPoolError::Broken {
+        last_connection_error: ConnectionError::ConnectionSetupRequestError(setup_error),
+    } = err
+    {
+        match setup_error.get_error() {
+            ConnectionSetupRequestErrorKind::DbError(db_error, _) => {
+                return db_error_to_py(db_error, message)
+            }
+            ConnectionSetupRequestErrorKind::StartAuthSessionError(_)
+            | ConnectionSetupRequestErrorKind::AuthChallengeEvaluationError(_)
+            | ConnectionSetupRequestErrorKind::AuthFinishError(_)
+            | ConnectionSetupRequestErrorKind::MissingAuthentication => {
+                return PyErr::new::<AuthenticationError, _>(message)
+            }
+            _ => {}
+        }
+    }
+    PyErr::new::<NoHostAvailable, _>(message)
+}
+
+fn prepare_error_kind_to_py(err: &PrepareError, message: String) -> PyErr {
+    match err {
+        PrepareError::ConnectionPoolError(pool_error) => {
+            connection_pool_error_to_py(pool_error, message)
+        }
+        PrepareError::AllAttemptsFailed { first_attempt } => {
+            attempt_error_to_py(first_attempt, message)
+        }
+        _ => PyErr::new::<ScyllaError, _>(message),
+    }
+}
+
+fn use_keyspace_error_kind_to_py(err: &UseKeyspaceError, message: String) -> PyErr {
+    match err {
+        UseKeyspaceError::BadKeyspaceName(_) => PyErr::new::<InvalidQuery, _>(message),
+        UseKeyspaceError::RequestError(attempt_error) => {
+            attempt_error_to_py(attempt_error, message)
+        }
+        _ => PyErr::new::<ScyllaError, _>(message),
+    }
+}
+
 // Helper functions to convert scylla errors to PyErr
 // We can't implement From directly due to orphan rules
-pub fn query_error_to_py(err: scylla::errors::ExecutionError) -> PyErr {
-    PyErr::new::<ScyllaError, _>(format!("Query error: {}", err))
+fn operation_timed_out_to_py(message: String, timeout: Duration) -> PyErr {
+    let err = PyErr::new::<OperationTimedOut, _>(message);
+    Python::attach(|py| {
+        err.value(py)
+            .setattr("timeout_ms", timeout.as_millis() as u64)
+            .map(|_| err)
+            .unwrap_or_else(|attr_err| attr_err)
+    })
+}
+
+pub fn query_error_to_py(err: ExecutionError) -> PyErr {
+    let message = format!("Query error: {}", err);
+    match &err {
+        ExecutionError::BadQuery(_) => PyErr::new::<InvalidQuery, _>(message),
+        ExecutionError::EmptyPlan => PyErr::new::<NoHostAvailable, _>(message),
+        ExecutionError::ConnectionPoolError(pool_error) => {
+            connection_pool_error_to_py(pool_error, message)
+        }
+        ExecutionError::PrepareError(prepare_error) => {
+            prepare_error_kind_to_py(prepare_error, message)
+        }
+        ExecutionError::LastAttemptError(attempt_error) => {
+            attempt_error_to_py(attempt_error, message)
+        }
+        ExecutionError::RequestTimeout(timeout) => operation_timed_out_to_py(message, *timeout),
+        ExecutionError::SchemaAgreementError(SchemaAgreementError::Timeout(timeout)) => {
+            operation_timed_out_to_py(message, *timeout)
+        }
+        ExecutionError::UseKeyspaceError(use_keyspace_error) => {
+            use_keyspace_error_kind_to_py(use_keyspace_error, message)
+        }
+        _ => PyErr::new::<ScyllaError, _>(message),
+    }
+}
+
+fn next_page_error_to_py(err: &NextPageError, message: String) -> PyErr {
+    match err {
+        NextPageError::RequestFailure(RequestError::EmptyPlan) => {
+            PyErr::new::<NoHostAvailable, _>(message)
+        }
+        NextPageError::RequestFailure(RequestError::ConnectionPoolError(pool_error)) => {
+            connection_pool_error_to_py(pool_error, message)
+        }
+        NextPageError::RequestFailure(RequestError::RequestTimeout(timeout)) => {
+            operation_timed_out_to_py(message, *timeout)
+        }
+        NextPageError::RequestFailure(RequestError::LastAttemptError(attempt_error)) => {
+            attempt_error_to_py(attempt_error, message)
+        }
+        _ => PyErr::new::<ScyllaError, _>(message),
+    }
+}
+
+pub fn pager_error_to_py(err: PagerExecutionError) -> PyErr {
+    let message = format!("Query error: {}", err);
+    match &err {
+        PagerExecutionError::PrepareError(prepare_error) => {
+            prepare_error_kind_to_py(prepare_error, message)
+        }
+        PagerExecutionError::NextPageError(page_error) => {
+            next_page_error_to_py(page_error, message)
+        }
+        _ => PyErr::new::<ScyllaError, _>(message),
+    }
+}
+
+pub fn next_row_error_to_py(err: NextRowError) -> PyErr {
+    match &err {
+        NextRowError::NextPageError(page_error) => {
+            next_page_error_to_py(page_error, format!("Query error: {}", err))
+        }
+        _ => deserialization_error_to_py(err),
+    }
+}
+
+pub fn session_error_to_py(err: NewSessionError) -> PyErr {
+    let message = format!("Session error: {}", err);
+    match &err {
+        NewSessionError::FailedToResolveAnyHostname(_) | NewSessionError::EmptyKnownNodesList => {
+            PyErr::new::<NoHostAvailable, _>(message)
+        }
+        NewSessionError::MetadataError(MetadataError::ConnectionPoolError(pool_error)) => {
+            connection_pool_error_to_py(pool_error, message)
+        }
+        NewSessionError::UseKeyspaceError(use_keyspace_error) => {
+            use_keyspace_error_kind_to_py(use_keyspace_error, message)
+        }
+        _ => PyErr::new::<ScyllaError, _>(message),
+    }
+}
+
+pub fn prepare_error_to_py(err: PrepareError) -> PyErr {
+    let message = format!("Prepare error: {}", err);
+    prepare_error_kind_to_py(&err, message)
+}
+
+pub fn use_keyspace_error_to_py(err: UseKeyspaceError) -> PyErr {
+    let message = format!("Use keyspace error: {}", err);
+    use_keyspace_error_kind_to_py(&err, message)
+}
+
+pub fn schema_agreement_error_to_py(err: SchemaAgreementError) -> PyErr {
+    let message = format!("Schema agreement error: {}", err);
+    match err {
+        SchemaAgreementError::Timeout(timeout) => operation_timed_out_to_py(message, timeout),
+        _ => PyErr::new::<ScyllaError, _>(message),
+    }
+}
+
+pub fn schema_agreement_timeout_to_py(timeout: Duration) -> PyErr {
+    operation_timed_out_to_py(
+        format!(
+            "Schema agreement error: Schema agreement exceeded {}ms",
+            timeout.as_millis()
+        ),
+        timeout,
+    )
+}
+
+pub fn not_ready_to_py(timeout: Duration, connected: usize, required: usize) -> PyErr {
+    operation_timed_out_to_py(
+        format!(
+            "Session not ready: {} of {} required nodes connected after {}ms",
+            connected,
+            required,
+            timeout.as_millis()
+        ),
+        timeout,
+    )
 }
 
-pub fn session_error_to_py(err: scylla::errors::NewSessionError) -> PyErr {
-    PyErr::new::<ScyllaError, _>(format!("Session error: {}", err))
+pub fn deadline_exceeded_to_py(timeout: Duration) -> PyErr {
+    operation_timed_out_to_py(
+        format!(
+            "Request timeout: Request did not complete within {}ms",
+            timeout.as_millis()
+        ),
+        timeout,
+    )
 }
 
-pub fn prepare_error_to_py(err: scylla::errors::PrepareError) -> PyErr {
-    PyErr::new::<ScyllaError, _>(format!("Prepare error: {}", err))
+pub fn metadata_error_to_py(err: scylla::errors::MetadataError) -> PyErr {
+    PyErr::new::<ScyllaError, _>(format!("Metadata error: {}", err))
 }
 
-pub fn use_keyspace_error_to_py(err: scylla::errors::UseKeyspaceError) -> PyErr {
-    PyErr::new::<ScyllaError, _>(format!("Use keyspace error: {}", err))
+pub fn token_error_to_py(err: scylla::errors::ClusterStateTokenError) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Cannot compute token: {}", err))
 }
 
-pub fn schema_agreement_error_to_py(err: scylla::errors::SchemaAgreementError) -> PyErr {
-    PyErr::new::<ScyllaError, _>(format!("Schema agreement error: {}", err))
+pub fn tracing_error_to_py(err: scylla::errors::TracingError) -> PyErr {
+    PyErr::new::<ScyllaError, _>(format!("Tracing error: {}", err))
 }
 
-#[allow(dead_code)]
 pub fn serialization_error_to_py(err: scylla::serialize::SerializationError) -> PyErr {
     PyErr::new::<ScyllaError, _>(format!("Serialization error: {}", err))
 }
 
-#[allow(dead_code)]
-pub fn deserialization_error_to_py(err: scylla::deserialize::DeserializationError) -> PyErr {
-    PyErr::new::<ScyllaError, _>(format!("Deserialization error: {}", err))
+pub fn deserialization_error_to_py(err: impl std::fmt::Display) -> PyErr {
+    PyErr::new::<DeserializationError, _>(format!("Row deserialization error: {}", err))
 }
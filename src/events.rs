@@ -0,0 +1,581 @@
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+use pyo3::IntoPyObjectExt;
+use scylla::client::session::Session as ScyllaSession;
+use scylla::cluster::metadata::Keyspace;
+use scylla::cluster::ClusterState;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::iter::Peekable;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use crate::aio::future_into_py;
+use crate::cluster::HostInfo;
+use crate::session::wait;
+
+pub const TOPOLOGY_CHANGE: &str = "TOPOLOGY_CHANGE";
+pub const STATUS_CHANGE: &str = "STATUS_CHANGE";
+pub const SCHEMA_CHANGE: &str = "SCHEMA_CHANGE";
+
+#[pyclass]
+#[derive(Clone)]
+pub struct TopologyChange {
+    // NEW_NODE, REMOVED_NODE or MOVED_NODE
+    #[pyo3(get)]
+    change: String,
+    #[pyo3(get)]
+    host: HostInfo,
+}
+
+#[pymethods]
+impl TopologyChange {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "TopologyChange(change={}, host={})",
+            self.change,
+            self.host.__repr__()
+        )
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct StatusChange {
+    // UP or DOWN
+    #[pyo3(get)]
+    change: String,
+    #[pyo3(get)]
+    host: HostInfo,
+}
+
+#[pymethods]
+impl StatusChange {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "StatusChange(change={}, host={})",
+            self.change,
+            self.host.__repr__()
+        )
+    }
+}
+
+#[pyclass(module = "rsylla._rsylla")]
+#[derive(Clone)]
+pub struct SchemaChange {
+    // CREATED, UPDATED or DROPPED
+    #[pyo3(get)]
+    change: String,
+    // KEYSPACE, TABLE, TYPE, FUNCTION or AGGREGATE
+    #[pyo3(get)]
+    target: String,
+    #[pyo3(get)]
+    keyspace: String,
+    // None for keyspace changes
+    #[pyo3(get)]
+    name: Option<String>,
+}
+
+#[pymethods]
+impl SchemaChange {
+    pub fn __repr__(&self) -> String {
+        match &self.name {
+            Some(name) => format!(
+                "SchemaChange(change={}, target={}, name={}.{})",
+                self.change, self.target, self.keyspace, name
+            ),
+            None => format!(
+                "SchemaChange(change={}, target={}, name={})",
+                self.change, self.target, self.keyspace
+            ),
+        }
+    }
+
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyTuple>)> {
+        let change = slf.borrow();
+        let args = (
+            change.change.clone(),
+            change.target.clone(),
+            change.keyspace.clone(),
+            change.name.clone(),
+        )
+            .into_pyobject(slf.py())?;
+        Ok((slf.get_type().getattr("_from_snapshot")?, args))
+    }
+
+    #[staticmethod]
+    pub fn _from_snapshot(
+        change: String,
+        target: String,
+        keyspace: String,
+        name: Option<String>,
+    ) -> Self {
+        SchemaChange {
+            change,
+            target,
+            keyspace,
+            name,
+        }
+    }
+}
+
+impl SchemaChange {
+    // The change a DDL statement makes, as the server reports it. Unqualified names belong to
+    // `keyspace`, indexes and materialized views are reported as changes of tables.
+    pub fn from_statement(statement: &str, keyspace: Option<&str>) -> Option<SchemaChange> {
+        let mut tokens = cql_tokens(statement).into_iter().peekable();
+        let change = match next_keyword(&mut tokens)?.as_str() {
+            "CREATE" => "CREATED",
+            "ALTER" => "UPDATED",
+            "DROP" => "DROPPED",
+            _ => return None,
+        };
+        let mut target = next_keyword(&mut tokens)?;
+        if target == "OR" {
+            next_keyword(&mut tokens);
+            target = next_keyword(&mut tokens)?;
+        }
+        if target == "CUSTOM" {
+            target = next_keyword(&mut tokens)?;
+        }
+        if target == "MATERIALIZED" {
+            next_keyword(&mut tokens);
+            target = "VIEW".to_string();
+        }
+        while matches!(
+            tokens
+                .peek()
+                .map(|token| token.to_ascii_uppercase())
+                .as_deref(),
+            Some("IF" | "NOT" | "EXISTS")
+        ) {
+            tokens.next();
+        }
+
+        let schema_change = |change: &str, target: &str, name: (Option<String>, String)| {
+            let (qualifier, name) = name;
+            SchemaChange {
+                change: change.to_string(),
+                target: target.to_string(),
+                keyspace: qualifier
+                    .or_else(|| keyspace.map(str::to_string))
+                    .unwrap_or_default(),
+                name: Some(name),
+            }
+        };
+        match target.as_str() {
+            "KEYSPACE" | "SCHEMA" => Some(SchemaChange {
+                change: change.to_string(),
+                target: "KEYSPACE".to_string(),
+                keyspace: qualified_name(&mut tokens)?.1,
+                name: None,
+            }),
+            "TABLE" | "COLUMNFAMILY" | "VIEW" => {
+                Some(schema_change(change, "TABLE", qualified_name(&mut tokens)?))
+            }
+            "TYPE" | "FUNCTION" | "AGGREGATE" => {
+                Some(schema_change(change, &target, qualified_name(&mut tokens)?))
+            }
+            // The index name is optional when creating one and only its keyspace is known when
+            // dropping one
+            "INDEX" if change == "CREATED" => {
+                while next_keyword(&mut tokens)? != "ON" {}
+                Some(schema_change(
+                    "UPDATED",
+                    "TABLE",
+                    qualified_name(&mut tokens)?,
+                ))
+            }
+            "INDEX" => {
+                let mut dropped = schema_change("UPDATED", "TABLE", qualified_name(&mut tokens)?);
+                dropped.name = None;
+                Some(dropped)
+            }
+            _ => None,
+        }
+    }
+}
+
+type Tokens = Peekable<std::vec::IntoIter<String>>;
+
+fn next_keyword(tokens: &mut Tokens) -> Option<String> {
+    tokens.next().map(|token| token.to_ascii_uppercase())
+}
+
+// `name` or `keyspace.name`
+fn qualified_name(tokens: &mut Tokens) -> Option<(Option<String>, String)> {
+    let first = tokens.next()?;
+    if tokens.peek().map(String::as_str) == Some(".") {
+        tokens.next();
+        Some((Some(first), tokens.next()?))
+    } else {
+        Some((None, first))
+    }
+}
+
+// Splits a statement into words, identifiers and punctuation. Unquoted
+// identifiers are case-insensitive and folded to lower case, quoted ones are kept as written.
+fn cql_tokens(statement: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = statement.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        if c == '"' {
+            let mut identifier = String::new();
+            while let Some(c) = chars.next() {
+                if c == '"' {
+                    if chars.peek() != Some(&'"') {
+                        break;
+                    }
+                    chars.next();
+                }
+                identifier.push(c);
+            }
+            tokens.push(identifier);
+        } else if c.is_alphanumeric() || c == '_' {
+            let mut word = c.to_lowercase().to_string();
+            while let Some(&c) = chars.peek().filter(|c| c.is_alphanumeric() || **c == '_') {
+                word.extend(c.to_lowercase());
+                chars.next();
+            }
+            tokens.push(word);
+        } else {
+            tokens.push(c.to_string());
+        }
+    }
+    tokens
+}
+
+#[derive(Clone)]
+pub enum ClusterEvent {
+    Topology(TopologyChange),
+    Status(StatusChange),
+    Schema(SchemaChange),
+}
+
+impl ClusterEvent {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ClusterEvent::Topology(_) => TOPOLOGY_CHANGE,
+            ClusterEvent::Status(_) => STATUS_CHANGE,
+            ClusterEvent::Schema(_) => SCHEMA_CHANGE,
+        }
+    }
+
+    pub fn into_py_any(self, py: Python) -> PyResult<Py<PyAny>> {
+        match self {
+            ClusterEvent::Topology(event) => event.into_py_any(py),
+            ClusterEvent::Status(event) => event.into_py_any(py),
+            ClusterEvent::Schema(event) => event.into_py_any(py),
+        }
+    }
+}
+
+pub fn parse_event_kinds(events: Option<Vec<String>>) -> PyResult<HashSet<&'static str>> {
+    let Some(events) = events else {
+        return Ok(HashSet::from([
+            TOPOLOGY_CHANGE,
+            STATUS_CHANGE,
+            SCHEMA_CHANGE,
+        ]));
+    };
+    events
+        .iter()
+        .map(|event| match event.to_uppercase().as_str() {
+            TOPOLOGY_CHANGE => Ok(TOPOLOGY_CHANGE),
+            STATUS_CHANGE => Ok(STATUS_CHANGE),
+            SCHEMA_CHANGE => Ok(SCHEMA_CHANGE),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid event type: {}. Must be TOPOLOGY_CHANGE, STATUS_CHANGE or SCHEMA_CHANGE",
+                event
+            ))),
+        })
+        .collect()
+}
+
+// Nodes as last seen by the watcher: address and whether any connection was open
+type NodeStates = HashMap<Uuid, (SocketAddr, bool, HostInfo)>;
+
+fn node_states(cluster_state: &ClusterState) -> NodeStates {
+    cluster_state
+        .get_nodes_info()
+        .iter()
+        .filter(|node| node.is_enabled())
+        .map(|node| {
+            (
+                node.host_id,
+                (
+                    SocketAddr::new(node.address.ip(), node.address.port()),
+                    node.is_connected(),
+                    HostInfo::from_node(node),
+                ),
+            )
+        })
+        .collect()
+}
+
+fn diff_nodes(old: &NodeStates, new: &NodeStates, events: &mut Vec<ClusterEvent>) {
+    let topology = |change: &str, host: &HostInfo| {
+        ClusterEvent::Topology(TopologyChange {
+            change: change.to_string(),
+            host: host.clone(),
+        })
+    };
+    for (host_id, (_, _, host)) in old {
+        if !new.contains_key(host_id) {
+            events.push(topology("REMOVED_NODE", host));
+        }
+    }
+    for (host_id, (addr, connected, host)) in new {
+        match old.get(host_id) {
+            None => events.push(topology("NEW_NODE", host)),
+            Some((old_addr, was_connected, _)) => {
+                if old_addr != addr {
+                    events.push(topology("MOVED_NODE", host));
+                }
+                if was_connected != connected {
+                    events.push(ClusterEvent::Status(StatusChange {
+                        change: if *connected { "UP" } else { "DOWN" }.to_string(),
+                        host: host.clone(),
+                    }));
+                }
+            }
+        }
+    }
+}
+
+fn diff_named<K: Eq + Hash + ToString, V: PartialEq>(
+    old: &HashMap<K, V>,
+    new: &HashMap<K, V>,
+    mut emit: impl FnMut(&str, String),
+) {
+    for name in old.keys().filter(|name| !new.contains_key(*name)) {
+        emit("DROPPED", name.to_string());
+    }
+    for (name, value) in new {
+        match old.get(name) {
+            None => emit("CREATED", name.to_string()),
+            Some(old_value) if old_value != value => emit("UPDATED", name.to_string()),
+            Some(_) => {}
+        }
+    }
+}
+
+fn schema_change(change: &str, target: &str, keyspace: &str, name: Option<String>) -> ClusterEvent {
+    ClusterEvent::Schema(SchemaChange {
+        change: change.to_string(),
+        target: target.to_string(),
+        keyspace: keyspace.to_string(),
+        name,
+    })
+}
+
+fn diff_keyspace(keyspace: &str, old: &Keyspace, new: &Keyspace, events: &mut Vec<ClusterEvent>) {
+    if old.strategy != new.strategy || old.durable_writes != new.durable_writes {
+        events.push(schema_change("UPDATED", "KEYSPACE", keyspace, None));
+    }
+    // Materialized views are reported as tables, like the server does
+    diff_named(&old.tables, &new.tables, |change, name| {
+        events.push(schema_change(change, "TABLE", keyspace, Some(name)))
+    });
+    diff_named(&old.views, &new.views, |change, name| {
+        events.push(schema_change(change, "TABLE", keyspace, Some(name)))
+    });
+    diff_named(
+        &old.user_defined_types,
+        &new.user_defined_types,
+        |change, name| events.push(schema_change(change, "TYPE", keyspace, Some(name))),
+    );
+}
+
+fn diff_schema(old: &ClusterState, new: &ClusterState, events: &mut Vec<ClusterEvent>) {
+    let old_keyspaces: HashMap<&str, &Keyspace> = old.keyspaces_iter().collect();
+    let new_keyspaces: HashMap<&str, &Keyspace> = new.keyspaces_iter().collect();
+    for keyspace in old_keyspaces.keys() {
+        if !new_keyspaces.contains_key(keyspace) {
+            events.push(schema_change("DROPPED", "KEYSPACE", keyspace, None));
+        }
+    }
+    for (keyspace, new_keyspace) in &new_keyspaces {
+        match old_keyspaces.get(keyspace) {
+            None => events.push(schema_change("CREATED", "KEYSPACE", keyspace, None)),
+            Some(old_keyspace) if old_keyspace != new_keyspace => {
+                diff_keyspace(keyspace, old_keyspace, new_keyspace, events)
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+// Follows the driver's view of the cluster and reports what changed between polls.
+// The driver refreshes it when the control connection receives topology, status or
+// schema events, so changes show up within one poll interval.
+pub fn watch(
+    session: Weak<ScyllaSession>,
+    interval: Duration,
+    mut sink: impl FnMut(Vec<ClusterEvent>) -> bool + Send + 'static,
+) -> AbortHandle {
+    let task = pyo3_async_runtimes::tokio::get_runtime().spawn(async move {
+        let Some(mut cluster_state) = session.upgrade().map(|s| s.get_cluster_state()) else {
+            return;
+        };
+        let mut nodes = node_states(&cluster_state);
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            // Stop once the session is closed
+            let Some(new_cluster_state) = session.upgrade().map(|s| s.get_cluster_state()) else {
+                break;
+            };
+            let new_nodes = node_states(&new_cluster_state);
+
+            let mut events = Vec::new();
+            diff_nodes(&nodes, &new_nodes, &mut events);
+            if !Arc::ptr_eq(&cluster_state, &new_cluster_state) {
+                diff_schema(&cluster_state, &new_cluster_state, &mut events);
+            }
+            cluster_state = new_cluster_state;
+            nodes = new_nodes;
+
+            if !events.is_empty() && !sink(events) {
+                break;
+            }
+        }
+    });
+    task.abort_handle()
+}
+
+#[pyclass]
+pub struct EventSubscription {
+    task: Mutex<Option<AbortHandle>>,
+}
+
+impl EventSubscription {
+    pub fn new(task: AbortHandle) -> Self {
+        EventSubscription {
+            task: Mutex::new(Some(task)),
+        }
+    }
+}
+
+#[pymethods]
+impl EventSubscription {
+    pub fn cancel(&self) {
+        if let Some(task) = self.task.lock().unwrap().take() {
+            task.abort();
+        }
+    }
+
+    #[getter]
+    pub fn active(&self) -> bool {
+        self.task
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("EventSubscription(active={})", self.active())
+    }
+}
+
+impl Drop for EventSubscription {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+// Events of a watcher, handed over to Python through async iteration
+#[pyclass]
+pub struct EventStream {
+    events: Arc<tokio::sync::Mutex<mpsc::UnboundedReceiver<ClusterEvent>>>,
+    task: Mutex<Option<AbortHandle>>,
+}
+
+impl EventStream {
+    pub fn start(
+        session: Weak<ScyllaSession>,
+        interval: Duration,
+        kinds: HashSet<&'static str>,
+    ) -> Self {
+        let (sender, events) = mpsc::unbounded_channel();
+        let task = watch(session, interval, move |events| {
+            events
+                .into_iter()
+                .filter(|event| kinds.contains(event.kind()))
+                .all(|event| sender.send(event).is_ok())
+        });
+        EventStream {
+            events: Arc::new(tokio::sync::Mutex::new(events)),
+            task: Mutex::new(Some(task)),
+        }
+    }
+}
+
+// The next event, or None once the stream was cancelled or the session closed
+async fn next_event(
+    events: Arc<tokio::sync::Mutex<mpsc::UnboundedReceiver<ClusterEvent>>>,
+) -> PyResult<Option<Py<PyAny>>> {
+    let event = events.lock().await.recv().await;
+    Python::attach(|py| event.map(|event| event.into_py_any(py)).transpose())
+}
+
+#[pymethods]
+impl EventStream {
+    pub fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let event = next_event(self.events.clone());
+        future_into_py(py, async move {
+            event
+                .await?
+                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyStopAsyncIteration, _>(()))
+        })
+    }
+
+    pub fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __next__(&self, py: Python) -> PyResult<Option<Py<PyAny>>> {
+        wait(py, next_event(self.events.clone()))
+    }
+
+    // Stops watching, iteration ends after the events received so far
+    pub fn cancel(&self) {
+        if let Some(task) = self.task.lock().unwrap().take() {
+            task.abort();
+        }
+    }
+
+    #[getter]
+    pub fn active(&self) -> bool {
+        self.task
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("EventStream(active={})", self.active())
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
@@ -0,0 +1,41 @@
+use pyo3::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// NaN and infinities are stored as-is unless the policy says to raise
+static RAISE_NON_FINITE: AtomicBool = AtomicBool::new(false);
+
+pub fn check_finite(value: f64, cql_type: &str) -> PyResult<()> {
+    if value.is_finite() || !RAISE_NON_FINITE.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+        "Non-finite value {} for a {} column is not allowed by the float policy",
+        value, cql_type
+    )))
+}
+
+// "store" binds NaN and infinities as-is, "raise" rejects them with a ValueError
+#[pyfunction]
+pub fn set_float_policy(policy: &str) -> PyResult<()> {
+    let raise = match policy {
+        "store" => false,
+        "raise" => true,
+        _ => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid float policy: {}. Must be 'store' or 'raise'",
+                policy
+            )))
+        }
+    };
+    RAISE_NON_FINITE.store(raise, Ordering::Relaxed);
+    Ok(())
+}
+
+#[pyfunction]
+pub fn get_float_policy() -> &'static str {
+    if RAISE_NON_FINITE.load(Ordering::Relaxed) {
+        "raise"
+    } else {
+        "store"
+    }
+}
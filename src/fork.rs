@@ -0,0 +1,26 @@
+use pyo3::prelude::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::error::ForkedSessionError;
+
+// Process the driver's runtime was started in. Its worker threads don't survive a fork, so
+// requests made in a forked child would wait forever.
+static RUNTIME_PID: AtomicU32 = AtomicU32::new(0);
+
+// Called before a session starts using the runtime
+pub fn runtime_started() {
+    let _ = RUNTIME_PID.compare_exchange(0, std::process::id(), Ordering::SeqCst, Ordering::SeqCst);
+}
+
+pub fn check() -> PyResult<()> {
+    let pid = RUNTIME_PID.load(Ordering::SeqCst);
+    if pid == 0 || pid == std::process::id() {
+        return Ok(());
+    }
+    Err(PyErr::new::<ForkedSessionError, _>(format!(
+        "The driver was started in process {} and can't be used after a fork, connect sessions \
+         in each child instead (e.g. without gunicorn's --preload, or with the 'spawn' or \
+         'forkserver' multiprocessing start method)",
+        pid
+    )))
+}
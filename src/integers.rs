@@ -0,0 +1,112 @@
+use pyo3::prelude::*;
+use scylla::value::CqlValue;
+
+pub fn out_of_range(value: &Bound<'_, PyAny>, cql_type: &str, min: i64, max: i64) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyOverflowError, _>(format!(
+        "Value {} is out of range for {} ({} to {})",
+        value, cql_type, min, max
+    ))
+}
+
+// Integer range of a CQL integer type
+pub fn integer_range(cql_type: &str) -> Option<(i64, i64)> {
+    match cql_type {
+        "tinyint" => Some((i8::MIN.into(), i8::MAX.into())),
+        "smallint" => Some((i16::MIN.into(), i16::MAX.into())),
+        "int" => Some((i32::MIN.into(), i32::MAX.into())),
+        "bigint" | "counter" => Some((i64::MIN, i64::MAX)),
+        _ => None,
+    }
+}
+
+fn extract_in_range<'py, T>(value: &Bound<'py, PyAny>, cql_type: &str) -> PyResult<T>
+where
+    T: for<'a> FromPyObject<'a, 'py, Error = PyErr>,
+{
+    value
+        .extract::<T>()
+        .map_err(|err| match integer_range(cql_type) {
+            Some((min, max))
+                if err.is_instance_of::<pyo3::exceptions::PyOverflowError>(value.py()) =>
+            {
+                out_of_range(value, cql_type, min, max)
+            }
+            _ => err,
+        })
+}
+
+// Explicitly typed integers, bound as their CQL type whatever the statement
+macro_rules! integer_class {
+    ($name:ident, $int:ty, $cql_type:literal, $variant:ident) => {
+        #[pyclass(frozen)]
+        #[derive(Clone)]
+        pub struct $name {
+            #[pyo3(get)]
+            value: $int,
+        }
+
+        impl $name {
+            pub fn to_cql(&self) -> CqlValue {
+                CqlValue::$variant(self.value)
+            }
+        }
+
+        #[pymethods]
+        impl $name {
+            #[new]
+            pub fn new(value: &Bound<'_, PyAny>) -> PyResult<Self> {
+                Ok($name {
+                    value: extract_in_range(value, $cql_type)?,
+                })
+            }
+
+            pub fn __int__(&self) -> $int {
+                self.value
+            }
+
+            pub fn __index__(&self) -> $int {
+                self.value
+            }
+
+            pub fn __repr__(&self) -> String {
+                format!("{}({})", stringify!($name), self.value)
+            }
+
+            pub fn __eq__(&self, other: &Bound<'_, PyAny>) -> bool {
+                match other.cast::<$name>() {
+                    Ok(other) => other.get().value == self.value,
+                    Err(_) => other
+                        .extract::<i64>()
+                        .is_ok_and(|other| other == self.value as i64),
+                }
+            }
+
+            // Hashes like the plain int, as it compares equal to it
+            pub fn __hash__(&self, py: Python) -> PyResult<isize> {
+                self.value.into_pyobject(py)?.hash()
+            }
+        }
+    };
+}
+
+integer_class!(TinyInt, i8, "tinyint", TinyInt);
+integer_class!(SmallInt, i16, "smallint", SmallInt);
+integer_class!(Int, i32, "int", Int);
+integer_class!(BigInt, i64, "bigint", BigInt);
+
+// The CQL value of an explicitly typed integer
+pub fn typed_integer(value: &Bound<'_, PyAny>) -> Option<CqlValue> {
+    if let Ok(value) = value.cast::<TinyInt>() {
+        return Some(value.get().to_cql());
+    }
+    if let Ok(value) = value.cast::<SmallInt>() {
+        return Some(value.get().to_cql());
+    }
+    if let Ok(value) = value.cast::<Int>() {
+        return Some(value.get().to_cql());
+    }
+    if let Ok(value) = value.cast::<BigInt>() {
+        return Some(value.get().to_cql());
+    }
+    None
+}
@@ -0,0 +1,380 @@
+use chrono::Timelike;
+use num_bigint::BigInt;
+use pyo3::conversion::IntoPyObjectExt;
+use pyo3::prelude::*;
+use pyo3::types::{
+    PyBool, PyByteArray, PyBytes, PyDict, PyFloat, PyInt, PyList, PyMemoryView, PyString,
+};
+use scylla::client::session::Session as ScyllaSession;
+use scylla::cluster::metadata::{CollectionType, Column, ColumnType, NativeType};
+use scylla::value::{Counter, CqlDate, CqlTime, CqlTimestamp, CqlTimeuuid, CqlValue, CqlVarint};
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::builder::quote_identifier;
+use crate::copy::parse_decimal;
+use crate::floats::check_finite;
+use crate::types::{
+    cql_value_to_py, datetime_to_millis, is_instance_of_class, plain_value, py_items, EnumBinding,
+};
+
+fn json_error(val: &Bound<'_, PyAny>) -> PyErr {
+    let type_name = val
+        .get_type()
+        .name()
+        .map(|name| name.to_string())
+        .unwrap_or_default();
+    PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+        "Cannot convert a value of type {} to JSON",
+        type_name
+    ))
+}
+
+// Timestamps are written the way the server writes them in SELECT JSON results
+fn timestamp_to_json(millis: i64) -> Option<String> {
+    let datetime = chrono::DateTime::from_timestamp_millis(millis)?;
+    Some(datetime.format("%Y-%m-%d %H:%M:%S%.3fZ").to_string())
+}
+
+// Converts a Python value to the JSON the server accepts in INSERT JSON. Values JSON has no
+// type for (blobs, UUIDs, dates, decimals, addresses) are written as the strings CQL parses
+// them from.
+pub fn py_to_json(val: &Bound<'_, PyAny>, enums: EnumBinding) -> PyResult<Value> {
+    if val.is_none() {
+        return Ok(Value::Null);
+    }
+    if let Some(value) = plain_value(val, enums)? {
+        return py_to_json(&value, enums);
+    }
+    if let Ok(b) = val.cast::<PyBool>() {
+        return Ok(Value::Bool(b.is_true()));
+    }
+    if val.cast::<PyInt>().is_ok() {
+        // Integers past 64 bits go as strings, which varint columns accept
+        return Ok(match val.extract::<i64>() {
+            Ok(i) => Value::from(i),
+            Err(_) => Value::String(val.str()?.to_string()),
+        });
+    }
+    if let Ok(f) = val.cast::<PyFloat>() {
+        let f = f.value();
+        check_finite(f, "double")?;
+        return Ok(match Number::from_f64(f) {
+            Some(number) => Value::Number(number),
+            None if f.is_nan() => Value::String("NaN".to_string()),
+            None if f > 0.0 => Value::String("Infinity".to_string()),
+            None => Value::String("-Infinity".to_string()),
+        });
+    }
+    if let Ok(s) = val.cast::<PyString>() {
+        return Ok(Value::String(s.to_str()?.to_string()));
+    }
+    if val.cast::<PyBytes>().is_ok()
+        || val.cast::<PyByteArray>().is_ok()
+        || val.cast::<PyMemoryView>().is_ok()
+    {
+        let bytes = val.call_method0("hex")?.extract::<String>()?;
+        return Ok(Value::String(format!("0x{}", bytes)));
+    }
+    if is_instance_of_class(val, "datetime", "datetime")? {
+        let millis = datetime_to_millis(val)?;
+        return timestamp_to_json(millis).map(Value::String).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Timestamp out of range: {}",
+                val
+            ))
+        });
+    }
+    if is_instance_of_class(val, "datetime", "date")?
+        || is_instance_of_class(val, "datetime", "time")?
+    {
+        return Ok(Value::String(val.call_method0("isoformat")?.extract()?));
+    }
+    if is_instance_of_class(val, "uuid", "UUID")?
+        || is_instance_of_class(val, "decimal", "Decimal")?
+        || is_instance_of_class(val, "ipaddress", "IPv4Address")?
+        || is_instance_of_class(val, "ipaddress", "IPv6Address")?
+    {
+        return Ok(Value::String(val.str()?.to_string()));
+    }
+    // Map keys are strings in JSON, other key types are written as the JSON of the key
+    if let Ok(dict) = val.cast::<PyDict>() {
+        let mut object = Map::with_capacity(dict.len());
+        for (key, value) in dict.iter() {
+            let key = match py_to_json(&key, enums)? {
+                Value::String(key) => key,
+                key => key.to_string(),
+            };
+            object.insert(key, py_to_json(&value, enums)?);
+        }
+        return Ok(Value::Object(object));
+    }
+    if let Some(items) = py_items(val)? {
+        return items
+            .iter()
+            .map(|item| py_to_json(item, enums))
+            .collect::<PyResult<_>>()
+            .map(Value::Array);
+    }
+    Err(json_error(val))
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    let text = text.strip_prefix("0x")?;
+    if text.len() % 2 != 0 {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn parse_timestamp(text: &str) -> Option<i64> {
+    if let Ok(datetime) = chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.fZ") {
+        return Some(datetime.and_utc().timestamp_millis());
+    }
+    chrono::DateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f%z")
+        .or_else(|_| chrono::DateTime::parse_from_rfc3339(text))
+        .ok()
+        .map(|datetime| datetime.timestamp_millis())
+}
+
+fn json_to_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => match text.as_str() {
+            "NaN" => Some(f64::NAN),
+            "Infinity" => Some(f64::INFINITY),
+            "-Infinity" => Some(f64::NEG_INFINITY),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn json_to_native(value: &Value, native: &NativeType) -> Option<CqlValue> {
+    let integer = || value.as_i64();
+    let text = || value.as_str();
+    Some(match native {
+        NativeType::Ascii => CqlValue::Ascii(text()?.to_string()),
+        NativeType::Text => CqlValue::Text(text()?.to_string()),
+        NativeType::Boolean => CqlValue::Boolean(value.as_bool()?),
+        NativeType::TinyInt => CqlValue::TinyInt(integer()?.try_into().ok()?),
+        NativeType::SmallInt => CqlValue::SmallInt(integer()?.try_into().ok()?),
+        NativeType::Int => CqlValue::Int(integer()?.try_into().ok()?),
+        NativeType::BigInt => CqlValue::BigInt(integer()?),
+        NativeType::Counter => CqlValue::Counter(Counter(integer()?)),
+        NativeType::Float => CqlValue::Float(json_to_float(value)? as f32),
+        NativeType::Double => CqlValue::Double(json_to_float(value)?),
+        NativeType::Varint => {
+            let digits = BigInt::from_str(&value.as_number()?.to_string()).ok()?;
+            CqlValue::Varint(CqlVarint::from_signed_bytes_be(digits.to_signed_bytes_be()))
+        }
+        NativeType::Decimal => CqlValue::Decimal(parse_decimal(&value.as_number()?.to_string())?),
+        NativeType::Blob => CqlValue::Blob(decode_hex(text()?)?),
+        NativeType::Uuid => CqlValue::Uuid(text()?.parse().ok()?),
+        NativeType::Timeuuid => {
+            CqlValue::Timeuuid(CqlTimeuuid::from(text()?.parse::<uuid::Uuid>().ok()?))
+        }
+        NativeType::Inet => CqlValue::Inet(text()?.parse().ok()?),
+        NativeType::Timestamp => CqlValue::Timestamp(CqlTimestamp(match value {
+            Value::Number(_) => integer()?,
+            _ => parse_timestamp(text()?)?,
+        })),
+        NativeType::Date => {
+            let date = chrono::NaiveDate::parse_from_str(text()?, "%Y-%m-%d").ok()?;
+            let days = (date - chrono::NaiveDate::from_ymd_opt(1970, 1, 1)?).num_days();
+            // Dates are stored as days since the epoch, centered at 2^31
+            CqlValue::Date(CqlDate((days + (1 << 31)) as u32))
+        }
+        NativeType::Time => {
+            let time = chrono::NaiveTime::parse_from_str(text()?, "%H:%M:%S%.f").ok()?;
+            let nanos =
+                time.num_seconds_from_midnight() as i64 * 1_000_000_000 + time.nanosecond() as i64;
+            CqlValue::Time(CqlTime(nanos))
+        }
+        _ => return None,
+    })
+}
+
+fn json_to_elements(value: &Value, typ: &ColumnType) -> Option<Vec<CqlValue>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|item| json_to_cql(item, typ))
+        .collect()
+}
+
+// Map keys are strings in JSON; non-text keys are the JSON of the key, or its plain text for
+// types written as strings, such as UUIDs
+fn json_key(key: &str, typ: &ColumnType) -> Value {
+    match typ {
+        ColumnType::Native(NativeType::Ascii | NativeType::Text) => Value::String(key.to_string()),
+        _ => serde_json::from_str(key).unwrap_or_else(|_| Value::String(key.to_string())),
+    }
+}
+
+// Converts a value of a SELECT JSON document to the value of its column type, so it's read back
+// as the Python value the column would have without JSON. None means the JSON doesn't fit the
+// type.
+fn json_to_cql(value: &Value, typ: &ColumnType) -> Option<CqlValue> {
+    Some(match typ {
+        ColumnType::Native(native) => json_to_native(value, native)?,
+        ColumnType::Collection { typ, .. } => match typ {
+            CollectionType::List(element_type) => {
+                CqlValue::List(json_to_elements(value, element_type)?)
+            }
+            CollectionType::Set(element_type) => {
+                CqlValue::Set(json_to_elements(value, element_type)?)
+            }
+            CollectionType::Map(key_type, value_type) => CqlValue::Map(
+                value
+                    .as_object()?
+                    .iter()
+                    .map(|(key, value)| {
+                        Some((
+                            json_to_cql(&json_key(key, key_type), key_type)?,
+                            json_to_cql(value, value_type)?,
+                        ))
+                    })
+                    .collect::<Option<_>>()?,
+            ),
+            _ => return None,
+        },
+        ColumnType::Vector { typ, .. } => CqlValue::Vector(json_to_elements(value, typ)?),
+        ColumnType::Tuple(element_types) => {
+            let items = value.as_array()?;
+            if items.len() != element_types.len() {
+                return None;
+            }
+            CqlValue::Tuple(
+                items
+                    .iter()
+                    .zip(element_types)
+                    .map(|(item, typ)| match item {
+                        Value::Null => Some(None),
+                        item => json_to_cql(item, typ).map(Some),
+                    })
+                    .collect::<Option<_>>()?,
+            )
+        }
+        ColumnType::UserDefinedType { definition, .. } => {
+            let object = value.as_object()?;
+            let fields = definition
+                .field_types
+                .iter()
+                .map(|(name, typ)| {
+                    let field = object
+                        .get(name.as_ref())
+                        .or_else(|| object.get(&quote_identifier(name).ok()?));
+                    let value = match field {
+                        None | Some(Value::Null) => None,
+                        Some(field) => Some(json_to_cql(field, typ)?),
+                    };
+                    Some((name.to_string(), value))
+                })
+                .collect::<Option<_>>()?;
+            CqlValue::UserDefinedType {
+                keyspace: definition.keyspace.to_string(),
+                name: definition.name.to_string(),
+                fields,
+            }
+        }
+        _ => return None,
+    })
+}
+
+// Column names that aren't lowercase identifiers are quoted in JSON documents
+fn unquote(name: &str) -> String {
+    match name
+        .strip_prefix('"')
+        .and_then(|name| name.strip_suffix('"'))
+    {
+        Some(name) => name.replace("\"\"", "\""),
+        None => name.to_string(),
+    }
+}
+
+// Converts JSON as is, for values whose column type is unknown, e.g. selected function calls
+fn plain_json_to_py(py: Python, value: &Value) -> PyResult<Py<PyAny>> {
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => b.into_bound_py_any(py)?.unbind(),
+        Value::Number(number) => match number.as_i64() {
+            Some(i) => i.into_bound_py_any(py)?.unbind(),
+            // Integers past 64 bits are parsed by Python from their digits
+            None if BigInt::from_str(&number.to_string()).is_ok() => py
+                .get_type::<PyInt>()
+                .call1((number.to_string(),))?
+                .unbind(),
+            None => number.as_f64().into_bound_py_any(py)?.unbind(),
+        },
+        Value::String(s) => s.into_bound_py_any(py)?.unbind(),
+        Value::Array(items) => {
+            let list = PyList::empty(py);
+            for item in items {
+                list.append(plain_json_to_py(py, item)?)?;
+            }
+            list.into_any().unbind()
+        }
+        Value::Object(object) => {
+            let dict = PyDict::new(py);
+            for (key, value) in object {
+                dict.set_item(key, plain_json_to_py(py, value)?)?;
+            }
+            dict.into_any().unbind()
+        }
+    })
+}
+
+// Columns of the table a SELECT JSON result was read from, if the schema knows it
+pub fn table_columns(
+    session: &ScyllaSession,
+    keyspace: &str,
+    table: &str,
+) -> HashMap<String, Column> {
+    let cluster_state = session.get_cluster_state();
+    cluster_state
+        .get_keyspace(keyspace)
+        .and_then(|ks| {
+            ks.tables
+                .get(table)
+                .or_else(|| ks.views.get(table).map(|view| &view.view_metadata))
+        })
+        .map(|table| table.columns.clone())
+        .unwrap_or_default()
+}
+
+// Parses a SELECT JSON document into a dict, with values converted by the types of their
+// columns
+pub fn document_to_py(
+    py: Python,
+    document: &str,
+    columns: &HashMap<String, Column>,
+) -> PyResult<Py<PyAny>> {
+    let object = match serde_json::from_str(document) {
+        Ok(Value::Object(object)) => object,
+        _ => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid JSON document: {}",
+                document
+            )))
+        }
+    };
+    let dict = PyDict::new(py);
+    for (key, value) in &object {
+        let name = unquote(key);
+        let converted = match columns.get(&name) {
+            Some(column) if !value.is_null() => json_to_cql(value, &column.typ),
+            _ => None,
+        };
+        let value = match converted {
+            Some(value) => cql_value_to_py(py, &value)?,
+            None => plain_json_to_py(py, value)?,
+        };
+        dict.set_item(name, value)?;
+    }
+    Ok(dict.into_any().unbind())
+}
@@ -3,32 +3,152 @@
 
 use pyo3::prelude::*;
 
+mod aio;
 mod batch;
+mod builder;
+mod circuit_breaker;
+mod cluster;
+mod config;
+mod converters;
+mod copy;
+mod durations;
 mod error;
+mod events;
+mod floats;
+mod fork;
+mod integers;
+mod json;
+mod logging;
+mod mapper;
+mod metadata;
+mod metrics;
+mod migrations;
+mod pool;
+mod prepare_cache;
+mod profile;
 mod query;
+mod rate_limit;
 mod result;
+mod routing;
+mod scan;
 mod session;
+mod shutdown;
+mod statement;
+mod stream;
+mod strict;
+mod telemetry;
+mod timeuuid;
+mod tracing;
 mod types;
+mod url;
 
 use batch::Batch;
-use error::ScyllaError;
+use builder::QueryBuilder;
+use circuit_breaker::CircuitBreakerStats;
+use cluster::{ClusterInfo, HealthReport, HostInfo, NodeHealth, NodeInfo};
+use config::SessionConfig;
+use events::{EventStream, EventSubscription, SchemaChange, StatusChange, TopologyChange};
+use integers::{BigInt, Int, SmallInt, TinyInt};
+use mapper::Mapper;
+use metadata::{
+    AggregateMetadata, ColumnSpec, CqlType, FunctionMetadata, KeyspaceMetadata, TableMetadata,
+    UserType,
+};
+use metrics::Metrics;
+use migrations::{Migration, MigrationRunner};
+use pool::PoolStats;
+use prepare_cache::PrepareCacheStats;
+use profile::ExecutionProfile;
 use query::{PreparedStatement, Query};
-use result::{QueryResult, Row};
-use session::{Session, SessionBuilder};
+use result::{BulkResult, ExecutionInfo, Page, QueryResult, Row};
+use routing::TokenRange;
+use scan::FullScan;
+use session::{AsyncSession, BlockingSession, SessionBuilder};
+use statement::Statement;
+use stream::RowStream;
+use telemetry::{RequestContext, SlowQuery};
+use tracing::{TracingEvent, TracingInfo};
 
-#[pymodule]
+// Classes keep shared state behind locks and atomics, so the module runs without the GIL on
+// free-threaded builds
+#[pymodule(gil_used = false)]
 fn _rsylla(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Core types
     m.add_class::<SessionBuilder>()?;
-    m.add_class::<Session>()?;
+    m.add_class::<SessionConfig>()?;
+    m.add_class::<BlockingSession>()?;
+    m.add_class::<AsyncSession>()?;
     m.add_class::<Query>()?;
+    m.add_class::<QueryBuilder>()?;
+    m.add_class::<Statement>()?;
     m.add_class::<PreparedStatement>()?;
     m.add_class::<QueryResult>()?;
+    m.add_class::<Page>()?;
+    m.add_class::<RowStream>()?;
     m.add_class::<Row>()?;
+    m.add_class::<ExecutionInfo>()?;
+    m.add_class::<BulkResult>()?;
+    m.add_class::<HostInfo>()?;
+    m.add_class::<TokenRange>()?;
+    m.add_class::<FullScan>()?;
+    m.add_class::<HealthReport>()?;
+    m.add_class::<ClusterInfo>()?;
+    m.add_class::<NodeInfo>()?;
+    m.add_class::<NodeHealth>()?;
+    m.add_class::<EventSubscription>()?;
+    m.add_class::<EventStream>()?;
+    m.add_class::<TopologyChange>()?;
+    m.add_class::<StatusChange>()?;
+    m.add_class::<SchemaChange>()?;
+    m.add_class::<KeyspaceMetadata>()?;
+    m.add_class::<TableMetadata>()?;
+    m.add_class::<UserType>()?;
+    m.add_class::<ColumnSpec>()?;
+    m.add_class::<CqlType>()?;
+    m.add_class::<FunctionMetadata>()?;
+    m.add_class::<AggregateMetadata>()?;
+    m.add_class::<Metrics>()?;
+    m.add_class::<PrepareCacheStats>()?;
+    m.add_class::<PoolStats>()?;
+    m.add_class::<CircuitBreakerStats>()?;
+    m.add_class::<ExecutionProfile>()?;
+    m.add_class::<TinyInt>()?;
+    m.add_class::<SmallInt>()?;
+    m.add_class::<Int>()?;
+    m.add_class::<BigInt>()?;
+    m.add_class::<SlowQuery>()?;
+    m.add_class::<RequestContext>()?;
+    m.add_class::<TracingInfo>()?;
+    m.add_class::<TracingEvent>()?;
     m.add_class::<Batch>()?;
+    m.add_class::<Mapper>()?;
+    m.add_class::<MigrationRunner>()?;
+    m.add_class::<Migration>()?;
 
-    // Exception
-    m.add("ScyllaError", _py.get_type::<ScyllaError>())?;
+    // Driver log bridge
+    m.add_function(wrap_pyfunction!(logging::enable_logging, m)?)?;
+    m.add_function(wrap_pyfunction!(logging::disable_logging, m)?)?;
+    m.add_function(wrap_pyfunction!(session::connect, m)?)?;
+
+    // Token and shard helpers
+    m.add_function(wrap_pyfunction!(routing::token_for, m)?)?;
+    m.add_function(wrap_pyfunction!(routing::shard_for, m)?)?;
+    m.add_function(wrap_pyfunction!(converters::register_converter, m)?)?;
+    m.add_function(wrap_pyfunction!(converters::unregister_converter, m)?)?;
+    m.add_function(wrap_pyfunction!(floats::set_float_policy, m)?)?;
+    m.add_function(wrap_pyfunction!(floats::get_float_policy, m)?)?;
+    m.add_function(wrap_pyfunction!(durations::set_duration_mode, m)?)?;
+    m.add_function(wrap_pyfunction!(durations::get_duration_mode, m)?)?;
+
+    // Timeuuid helpers
+    m.add_function(wrap_pyfunction!(timeuuid::timeuuid_now, m)?)?;
+    m.add_function(wrap_pyfunction!(timeuuid::timeuuid_from_datetime, m)?)?;
+    m.add_function(wrap_pyfunction!(timeuuid::timeuuid_to_datetime, m)?)?;
+    m.add_function(wrap_pyfunction!(timeuuid::min_timeuuid, m)?)?;
+    m.add_function(wrap_pyfunction!(timeuuid::max_timeuuid, m)?)?;
+
+    // Exceptions
+    error::register_exceptions(_py, m)?;
 
     Ok(())
 }
@@ -0,0 +1,163 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, Once, RwLock};
+
+use ::tracing::field::{Field, Visit};
+use ::tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::Registry;
+
+struct LogConfig {
+    min_level: Level,
+    logger: Arc<Py<PyAny>>,
+    level_map: HashMap<Level, i32>,
+}
+
+static CONFIG: RwLock<Option<LogConfig>> = RwLock::new(None);
+static INSTALL: Once = Once::new();
+
+fn parse_level(level: &str) -> PyResult<Level> {
+    match level.to_uppercase().as_str() {
+        "TRACE" => Ok(Level::TRACE),
+        "DEBUG" => Ok(Level::DEBUG),
+        "INFO" => Ok(Level::INFO),
+        "WARN" | "WARNING" => Ok(Level::WARN),
+        "ERROR" => Ok(Level::ERROR),
+        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Invalid log level: {}. Must be TRACE, DEBUG, INFO, WARNING or ERROR",
+            level
+        ))),
+    }
+}
+
+fn default_level_map() -> HashMap<Level, i32> {
+    HashMap::from([
+        (Level::TRACE, 5),
+        (Level::DEBUG, 10),
+        (Level::INFO, 20),
+        (Level::WARN, 30),
+        (Level::ERROR, 40),
+    ])
+}
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+}
+
+// Forwards driver events to a Python logger
+struct PythonLogLayer;
+
+impl<S: Subscriber> Layer<S> for PythonLogLayer {
+    fn enabled(&self, metadata: &Metadata<'_>, _ctx: Context<'_, S>) -> bool {
+        let config = CONFIG.read().unwrap();
+        config
+            .as_ref()
+            .is_some_and(|config| *metadata.level() <= config.min_level)
+    }
+
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        // Never hold the config lock while waiting for the GIL
+        let Some((logger, level)) = CONFIG
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|config| (config.logger.clone(), config.level_map[metadata.level()]))
+        else {
+            return;
+        };
+
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let message = format!("{}{}", visitor.message, visitor.fields);
+
+        Python::attach(|py| {
+            let logger = logger.bind(py);
+            let result = logger
+                .call_method1("isEnabledFor", (level,))
+                .and_then(|enabled| enabled.is_truthy())
+                .and_then(|enabled| {
+                    if !enabled {
+                        return Ok(());
+                    }
+                    let extra = PyDict::new(py);
+                    extra.set_item("rust_target", metadata.target())?;
+                    let kwargs = PyDict::new(py);
+                    kwargs.set_item("extra", extra)?;
+                    logger.call_method("log", (level, "%s", message), Some(&kwargs))?;
+                    Ok(())
+                });
+            if let Err(err) = result {
+                err.write_unraisable(py, Some(logger));
+            }
+        });
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (level="INFO", logger_name="rsylla", level_map=None))]
+pub fn enable_logging(
+    py: Python,
+    level: &str,
+    logger_name: &str,
+    level_map: Option<HashMap<String, i32>>,
+) -> PyResult<()> {
+    let min_level = parse_level(level)?;
+    let mut levels = default_level_map();
+    for (name, python_level) in level_map.unwrap_or_default() {
+        levels.insert(parse_level(&name)?, python_level);
+    }
+    let logger = py
+        .import("logging")?
+        .call_method1("getLogger", (logger_name,))?
+        .unbind();
+    let logger = Arc::new(logger);
+
+    *CONFIG.write().unwrap() = Some(LogConfig {
+        min_level,
+        logger,
+        level_map: levels,
+    });
+
+    let mut install_result = Ok(());
+    INSTALL.call_once(|| {
+        let subscriber = Registry::default().with(PythonLogLayer);
+        install_result = ::tracing::subscriber::set_global_default(subscriber).map_err(|err| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+                "Failed to install log bridge: {}",
+                err
+            ))
+        });
+    });
+    // Levels may have changed since callsites were first registered
+    ::tracing::callsite::rebuild_interest_cache();
+    install_result
+}
+
+#[pyfunction]
+pub fn disable_logging() {
+    *CONFIG.write().unwrap() = None;
+    ::tracing::callsite::rebuild_interest_cache();
+}
@@ -0,0 +1,285 @@
+use futures::future::{BoxFuture, FutureExt};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::builder::{quote_identifier, quote_table};
+use crate::error::prepare_error_to_py;
+use crate::profile::ProfileOptions;
+use crate::query::PreparedStatement;
+use crate::result::QueryResult;
+use crate::session::Session;
+
+// Table mapping used by rsylla.mapper, with its statements prepared once per CQL text
+#[pyclass]
+pub struct Mapper {
+    #[pyo3(get)]
+    table: String,
+    #[pyo3(get)]
+    columns: Vec<String>,
+    #[pyo3(get)]
+    partition_key: Vec<String>,
+    #[pyo3(get)]
+    clustering_key: Vec<String>,
+    quoted_table: String,
+    prepared: Arc<Mutex<HashMap<String, PreparedStatement>>>,
+}
+
+impl Mapper {
+    fn primary_key(&self) -> impl Iterator<Item = &String> {
+        self.partition_key.iter().chain(&self.clustering_key)
+    }
+
+    // Columns present in `values`, in table order, rejecting unknown names
+    fn present_columns(&self, values: &Bound<'_, PyDict>) -> PyResult<Vec<&String>> {
+        for key in values.keys() {
+            let key = key.extract::<String>()?;
+            if !self.columns.contains(&key) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Unknown column for {}: {}",
+                    self.table, key
+                )));
+            }
+        }
+        self.columns
+            .iter()
+            .filter_map(|column| match values.contains(column) {
+                Ok(true) => Some(Ok(column)),
+                Ok(false) => None,
+                Err(err) => Some(Err(err)),
+            })
+            .collect()
+    }
+
+    // Condition on the full primary key, with the key values taken from `values`
+    fn key_condition<'py>(
+        &self,
+        values: &Bound<'py, PyDict>,
+    ) -> PyResult<(String, Bound<'py, PyDict>)> {
+        let mut conditions = Vec::new();
+        let key = PyDict::new(values.py());
+        for column in self.primary_key() {
+            let Some(value) = values.get_item(column)? else {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Missing primary key column for {}: {}",
+                    self.table, column
+                )));
+            };
+            conditions.push(format!("{} = ?", quote_identifier(column)?));
+            key.set_item(column, value)?;
+        }
+        Ok((conditions.join(" AND "), key))
+    }
+
+    fn select_columns(&self) -> PyResult<String> {
+        Ok(self
+            .columns
+            .iter()
+            .map(|column| quote_identifier(column))
+            .collect::<PyResult<Vec<_>>>()?
+            .join(", "))
+    }
+
+    fn request(
+        &self,
+        py: Python,
+        session: &Session,
+        cql: String,
+        values: &Bound<'_, PyDict>,
+    ) -> PyResult<BoxFuture<'static, PyResult<QueryResult>>> {
+        let cached = self.prepared.lock().unwrap().get(&cql).cloned();
+        if let Some(prepared) = cached {
+            return session.prepared_request(py, &prepared, Some(values), None);
+        }
+
+        let scylla_session = session.session()?;
+        let session = session.clone();
+        let cache = self.prepared.clone();
+        let values = values.clone().unbind();
+        Ok(async move {
+            let prepared = scylla_session
+                .prepare(cql.as_str())
+                .await
+                .map_err(prepare_error_to_py)?;
+            let prepared = PreparedStatement {
+                prepared: Arc::new(prepared),
+                options: ProfileOptions::default(),
+            };
+            cache.lock().unwrap().insert(cql, prepared.clone());
+            let request = Python::attach(|py| {
+                session.prepared_request(py, &prepared, Some(values.bind(py)), None)
+            })?;
+            request.await
+        }
+        .boxed())
+    }
+}
+
+#[pymethods]
+impl Mapper {
+    #[new]
+    #[pyo3(signature = (table, columns, partition_key, clustering_key=None))]
+    pub fn new(
+        table: &str,
+        columns: Vec<String>,
+        partition_key: Vec<String>,
+        clustering_key: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        let clustering_key = clustering_key.unwrap_or_default();
+        if partition_key.is_empty() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "A mapped table needs at least one partition key column",
+            ));
+        }
+        for column in partition_key.iter().chain(&clustering_key) {
+            if !columns.contains(column) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Primary key column is not a mapped column: {}",
+                    column
+                )));
+            }
+        }
+        Ok(Mapper {
+            table: table.to_string(),
+            quoted_table: quote_table(table)?,
+            columns,
+            partition_key,
+            clustering_key,
+            prepared: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    #[pyo3(signature = (session, values, ttl=None, timestamp=None))]
+    pub fn save<'py>(
+        &self,
+        py: Python<'py>,
+        session: &Session,
+        values: &Bound<'_, PyDict>,
+        ttl: Option<i32>,
+        timestamp: Option<i64>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.key_condition(values)?;
+        let columns = self
+            .present_columns(values)?
+            .into_iter()
+            .map(|column| quote_identifier(column))
+            .collect::<PyResult<Vec<_>>>()?;
+        let mut cql = format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            self.quoted_table,
+            columns.join(", "),
+            vec!["?"; columns.len()].join(", ")
+        );
+        let values = values.copy()?;
+        let mut options = Vec::new();
+        if let Some(ttl) = ttl {
+            options.push("TTL ?");
+            values.set_item("[ttl]", ttl)?;
+        }
+        if let Some(timestamp) = timestamp {
+            options.push("TIMESTAMP ?");
+            values.set_item("[timestamp]", timestamp)?;
+        }
+        if !options.is_empty() {
+            cql.push_str(" USING ");
+            cql.push_str(&options.join(" AND "));
+        }
+
+        let request = self.request(py, session, cql, &values)?;
+        session.run(py, async move {
+            request.await?;
+            Ok(())
+        })
+    }
+
+    pub fn get<'py>(
+        &self,
+        py: Python<'py>,
+        session: &Session,
+        key: &Bound<'_, PyDict>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let (condition, key) = self.key_condition(key)?;
+        let cql = format!(
+            "SELECT {} FROM {} WHERE {}",
+            self.select_columns()?,
+            self.quoted_table,
+            condition
+        );
+
+        let request = self.request(py, session, cql, &key)?;
+        session.run(py, async move {
+            let result = request.await?;
+            Python::attach(|py| result.first_row_typed(py))
+        })
+    }
+
+    pub fn delete<'py>(
+        &self,
+        py: Python<'py>,
+        session: &Session,
+        key: &Bound<'_, PyDict>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let (condition, key) = self.key_condition(key)?;
+        let cql = format!("DELETE FROM {} WHERE {}", self.quoted_table, condition);
+
+        let request = self.request(py, session, cql, &key)?;
+        session.run(py, async move {
+            request.await?;
+            Ok(())
+        })
+    }
+
+    #[pyo3(signature = (session, conditions=None, limit=None, allow_filtering=false))]
+    pub fn find<'py>(
+        &self,
+        py: Python<'py>,
+        session: &Session,
+        conditions: Option<&Bound<'_, PyDict>>,
+        limit: Option<i32>,
+        allow_filtering: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let values = match conditions {
+            Some(conditions) => conditions.copy()?,
+            None => PyDict::new(py),
+        };
+        let mut cql = format!(
+            "SELECT {} FROM {}",
+            self.select_columns()?,
+            self.quoted_table
+        );
+        let columns = self.present_columns(&values)?;
+        if !columns.is_empty() {
+            let conditions = columns
+                .into_iter()
+                .map(|column| Ok(format!("{} = ?", quote_identifier(column)?)))
+                .collect::<PyResult<Vec<_>>>()?;
+            cql.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+        }
+        if let Some(limit) = limit {
+            cql.push_str(" LIMIT ?");
+            values.set_item("[limit]", limit)?;
+        }
+        if allow_filtering {
+            cql.push_str(" ALLOW FILTERING");
+        }
+
+        let request = self.request(py, session, cql, &values)?;
+        session.run(py, async move {
+            let result = request.await?;
+            Python::attach(|py| result.rows_typed(py).map(Bound::unbind))
+        })
+    }
+
+    #[getter]
+    pub fn prepared_statements(&self) -> usize {
+        self.prepared.lock().unwrap().len()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "Mapper(table={}, partition_key={:?}, clustering_key={:?})",
+            self.table, self.partition_key, self.clustering_key
+        )
+    }
+}
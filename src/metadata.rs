@@ -0,0 +1,637 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use scylla::client::session::Session as ScyllaSession;
+use scylla::cluster::metadata::{
+    CollectionType, ColumnType, Keyspace, NativeType, Strategy, Table, UserDefinedType,
+};
+use scylla::frame::response::result::ColumnSpec as ScyllaColumnSpec;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use crate::error::{deserialization_error_to_py, query_error_to_py};
+
+// Renders a column type the way it is written in CQL
+pub fn column_type_to_cql(typ: &ColumnType) -> String {
+    let frozen = |frozen: bool, typ: String| {
+        if frozen {
+            format!("frozen<{}>", typ)
+        } else {
+            typ
+        }
+    };
+    match typ {
+        ColumnType::Native(native) => native_type_to_cql(native).to_string(),
+        ColumnType::Collection { frozen: f, typ } => {
+            let typ = match typ {
+                CollectionType::List(elem) => format!("list<{}>", column_type_to_cql(elem)),
+                CollectionType::Set(elem) => format!("set<{}>", column_type_to_cql(elem)),
+                CollectionType::Map(key, value) => format!(
+                    "map<{}, {}>",
+                    column_type_to_cql(key),
+                    column_type_to_cql(value)
+                ),
+                other => format!("{:?}", other),
+            };
+            frozen(*f, typ)
+        }
+        ColumnType::Vector { typ, dimensions } => {
+            format!("vector<{}, {}>", column_type_to_cql(typ), dimensions)
+        }
+        ColumnType::UserDefinedType {
+            frozen: f,
+            definition,
+        } => frozen(*f, definition.name.to_string()),
+        ColumnType::Tuple(types) => format!(
+            "tuple<{}>",
+            types
+                .iter()
+                .map(column_type_to_cql)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        other => format!("{:?}", other),
+    }
+}
+
+pub fn native_type_to_cql(typ: &NativeType) -> &'static str {
+    match typ {
+        NativeType::Ascii => "ascii",
+        NativeType::Boolean => "boolean",
+        NativeType::Blob => "blob",
+        NativeType::Counter => "counter",
+        NativeType::Date => "date",
+        NativeType::Decimal => "decimal",
+        NativeType::Double => "double",
+        NativeType::Duration => "duration",
+        NativeType::Float => "float",
+        NativeType::Int => "int",
+        NativeType::BigInt => "bigint",
+        NativeType::Text => "text",
+        NativeType::Timestamp => "timestamp",
+        NativeType::Inet => "inet",
+        NativeType::SmallInt => "smallint",
+        NativeType::TinyInt => "tinyint",
+        NativeType::Time => "time",
+        NativeType::Timeuuid => "timeuuid",
+        NativeType::Uuid => "uuid",
+        NativeType::Varint => "varint",
+        _ => "unknown",
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct UserType {
+    #[pyo3(get)]
+    keyspace: String,
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    field_names: Vec<String>,
+    #[pyo3(get)]
+    field_types: Vec<String>,
+}
+
+impl From<&UserDefinedType<'_>> for UserType {
+    fn from(udt: &UserDefinedType<'_>) -> Self {
+        UserType {
+            keyspace: udt.keyspace.to_string(),
+            name: udt.name.to_string(),
+            field_names: udt
+                .field_types
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            field_types: udt
+                .field_types
+                .iter()
+                .map(|(_, typ)| column_type_to_cql(typ))
+                .collect(),
+        }
+    }
+}
+
+#[pymethods]
+impl UserType {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "UserType(name={}.{}, fields={:?})",
+            self.keyspace, self.name, self.field_names
+        )
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct CqlType {
+    #[pyo3(get)]
+    kind: String,
+    #[pyo3(get)]
+    frozen: bool,
+    // Element types of collections and vectors, key and value of maps, items of tuples
+    // and field types of UDTs
+    #[pyo3(get)]
+    parameters: Vec<CqlType>,
+    #[pyo3(get)]
+    dimensions: Option<usize>,
+    #[pyo3(get)]
+    keyspace: Option<String>,
+    #[pyo3(get)]
+    name: Option<String>,
+    #[pyo3(get)]
+    field_names: Vec<String>,
+    cql: String,
+}
+
+impl From<&ColumnType<'_>> for CqlType {
+    fn from(typ: &ColumnType<'_>) -> Self {
+        let mut cql_type = CqlType {
+            kind: String::new(),
+            frozen: false,
+            parameters: Vec::new(),
+            dimensions: None,
+            keyspace: None,
+            name: None,
+            field_names: Vec::new(),
+            cql: column_type_to_cql(typ),
+        };
+        match typ {
+            ColumnType::Native(native) => cql_type.kind = native_type_to_cql(native).to_string(),
+            ColumnType::Collection { frozen, typ } => {
+                cql_type.frozen = *frozen;
+                let (kind, parameters) = match typ {
+                    CollectionType::List(elem) => ("list", vec![elem.as_ref()]),
+                    CollectionType::Set(elem) => ("set", vec![elem.as_ref()]),
+                    CollectionType::Map(key, value) => ("map", vec![key.as_ref(), value.as_ref()]),
+                    _ => ("unknown", vec![]),
+                };
+                cql_type.kind = kind.to_string();
+                cql_type.parameters = parameters.into_iter().map(CqlType::from).collect();
+            }
+            ColumnType::Vector { typ, dimensions } => {
+                cql_type.kind = "vector".to_string();
+                cql_type.parameters = vec![CqlType::from(typ.as_ref())];
+                cql_type.dimensions = Some(*dimensions as usize);
+            }
+            ColumnType::UserDefinedType { frozen, definition } => {
+                cql_type.kind = "udt".to_string();
+                cql_type.frozen = *frozen;
+                cql_type.keyspace = Some(definition.keyspace.to_string());
+                cql_type.name = Some(definition.name.to_string());
+                cql_type.field_names = definition
+                    .field_types
+                    .iter()
+                    .map(|(name, _)| name.to_string())
+                    .collect();
+                cql_type.parameters = definition
+                    .field_types
+                    .iter()
+                    .map(|(_, typ)| CqlType::from(typ))
+                    .collect();
+            }
+            ColumnType::Tuple(types) => {
+                cql_type.kind = "tuple".to_string();
+                cql_type.parameters = types.iter().map(CqlType::from).collect();
+            }
+            _ => cql_type.kind = "unknown".to_string(),
+        }
+        cql_type
+    }
+}
+
+#[pymethods]
+impl CqlType {
+    pub fn __str__(&self) -> String {
+        self.cql.clone()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("CqlType({})", self.cql)
+    }
+
+    pub fn __eq__(&self, other: &Self) -> bool {
+        self.cql == other.cql
+    }
+
+    pub fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.cql.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct ColumnSpec {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    keyspace: String,
+    #[pyo3(get)]
+    table: String,
+    #[pyo3(get)]
+    cql_type: CqlType,
+}
+
+impl From<&ScyllaColumnSpec<'_>> for ColumnSpec {
+    fn from(spec: &ScyllaColumnSpec<'_>) -> Self {
+        ColumnSpec {
+            name: spec.name().to_string(),
+            keyspace: spec.table_spec().ks_name().to_string(),
+            table: spec.table_spec().table_name().to_string(),
+            cql_type: CqlType::from(spec.typ()),
+        }
+    }
+}
+
+#[pymethods]
+impl ColumnSpec {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "ColumnSpec(name={}, table={}.{}, cql_type={})",
+            self.name, self.keyspace, self.table, self.cql_type.cql
+        )
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct TableMetadata {
+    #[pyo3(get)]
+    name: String,
+    // Primary key columns first, then the rest by name
+    columns: Vec<(String, String)>,
+    #[pyo3(get)]
+    pub(crate) partition_key: Vec<String>,
+    #[pyo3(get)]
+    clustering_key: Vec<String>,
+    pub(crate) partition_key_types: Vec<ColumnType<'static>>,
+    pub(crate) partitioner: Option<String>,
+}
+
+// Primary key columns first, then the rest by name
+pub fn ordered_columns(table: &Table) -> Vec<&String> {
+    let mut names: Vec<&String> = table
+        .partition_key
+        .iter()
+        .chain(&table.clustering_key)
+        .collect();
+    let mut regular: Vec<&String> = table
+        .columns
+        .keys()
+        .filter(|name| !names.contains(name))
+        .collect();
+    regular.sort();
+    names.extend(regular);
+    names
+}
+
+fn unquote(name: &str) -> String {
+    match name
+        .strip_prefix('"')
+        .and_then(|name| name.strip_suffix('"'))
+    {
+        Some(name) => name.replace("\"\"", "\""),
+        None => name.to_lowercase(),
+    }
+}
+
+// Resolves a CQL table name (table or keyspace.table) against the driver's schema metadata.
+// Materialized views are found too.
+pub fn lookup_table(session: &ScyllaSession, name: &str) -> PyResult<(String, String, Table)> {
+    let (keyspace, table) = match name.split_once('.') {
+        Some((keyspace, table)) => (unquote(keyspace), unquote(table)),
+        None => {
+            let keyspace = session.get_keyspace().ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "No keyspace given for table {} and the session has none",
+                    name
+                ))
+            })?;
+            (keyspace.to_string(), unquote(name))
+        }
+    };
+
+    let cluster_state = session.get_cluster_state();
+    let metadata = cluster_state
+        .get_keyspace(&keyspace)
+        .and_then(|ks| {
+            ks.tables
+                .get(&table)
+                .or_else(|| ks.views.get(&table).map(|view| &view.view_metadata))
+        })
+        .cloned()
+        .ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unknown table: {}.{}",
+                keyspace, table
+            ))
+        })?;
+    Ok((keyspace, table, metadata))
+}
+
+impl TableMetadata {
+    fn new(name: &str, table: &Table) -> Self {
+        TableMetadata {
+            name: name.to_string(),
+            columns: ordered_columns(table)
+                .into_iter()
+                .map(|name| (name.clone(), column_type_to_cql(&table.columns[name].typ)))
+                .collect(),
+            partition_key: table.partition_key.clone(),
+            clustering_key: table.clustering_key.clone(),
+            partition_key_types: table
+                .partition_key
+                .iter()
+                .map(|name| table.columns[name].typ.clone())
+                .collect(),
+            partitioner: table.partitioner.clone(),
+        }
+    }
+}
+
+#[pymethods]
+impl TableMetadata {
+    #[getter]
+    pub fn columns<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let columns = PyDict::new(py);
+        for (name, typ) in &self.columns {
+            columns.set_item(name, typ)?;
+        }
+        Ok(columns)
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "TableMetadata(name={}, partition_key={:?}, clustering_key={:?})",
+            self.name, self.partition_key, self.clustering_key
+        )
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct FunctionMetadata {
+    #[pyo3(get)]
+    keyspace: String,
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    argument_names: Vec<String>,
+    #[pyo3(get)]
+    argument_types: Vec<String>,
+    #[pyo3(get)]
+    return_type: Option<String>,
+    #[pyo3(get)]
+    language: Option<String>,
+    #[pyo3(get)]
+    body: Option<String>,
+    #[pyo3(get)]
+    called_on_null_input: Option<bool>,
+}
+
+#[pymethods]
+impl FunctionMetadata {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "FunctionMetadata(name={}.{}({}), return_type={:?})",
+            self.keyspace,
+            self.name,
+            self.argument_types.join(", "),
+            self.return_type
+        )
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct AggregateMetadata {
+    #[pyo3(get)]
+    keyspace: String,
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    argument_types: Vec<String>,
+    #[pyo3(get)]
+    state_func: Option<String>,
+    #[pyo3(get)]
+    state_type: Option<String>,
+    #[pyo3(get)]
+    final_func: Option<String>,
+    #[pyo3(get)]
+    initcond: Option<String>,
+    #[pyo3(get)]
+    return_type: Option<String>,
+}
+
+#[pymethods]
+impl AggregateMetadata {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "AggregateMetadata(name={}.{}({}), return_type={:?})",
+            self.keyspace,
+            self.name,
+            self.argument_types.join(", "),
+            self.return_type
+        )
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct KeyspaceMetadata {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    strategy: String,
+    #[pyo3(get)]
+    replication: HashMap<String, String>,
+    #[pyo3(get)]
+    durable_writes: bool,
+    // Whether the data is distributed in tablets rather than by the token ring (vnodes)
+    #[pyo3(get)]
+    tablets: bool,
+    #[pyo3(get)]
+    tables: HashMap<String, TableMetadata>,
+    #[pyo3(get)]
+    views: HashMap<String, TableMetadata>,
+    #[pyo3(get)]
+    user_types: HashMap<String, UserType>,
+    #[pyo3(get)]
+    functions: Vec<FunctionMetadata>,
+    #[pyo3(get)]
+    aggregates: Vec<AggregateMetadata>,
+}
+
+impl KeyspaceMetadata {
+    fn new(name: &str, keyspace: &Keyspace) -> Self {
+        let (strategy, replication) = match &keyspace.strategy {
+            Strategy::SimpleStrategy { replication_factor } => (
+                "SimpleStrategy".to_string(),
+                HashMap::from([(
+                    "replication_factor".to_string(),
+                    replication_factor.to_string(),
+                )]),
+            ),
+            Strategy::NetworkTopologyStrategy {
+                datacenter_repfactors,
+            } => (
+                "NetworkTopologyStrategy".to_string(),
+                datacenter_repfactors
+                    .iter()
+                    .map(|(dc, rf)| (dc.clone(), rf.to_string()))
+                    .collect(),
+            ),
+            Strategy::LocalStrategy => ("LocalStrategy".to_string(), HashMap::new()),
+            Strategy::Other { name, data } => (name.clone(), data.clone()),
+            other => (format!("{:?}", other), HashMap::new()),
+        };
+
+        KeyspaceMetadata {
+            name: name.to_string(),
+            strategy,
+            replication,
+            durable_writes: keyspace.durable_writes,
+            tablets: keyspace.tablet_based,
+            tables: keyspace
+                .tables
+                .iter()
+                .map(|(name, table)| (name.clone(), TableMetadata::new(name, table)))
+                .collect(),
+            views: keyspace
+                .views
+                .iter()
+                .map(|(name, view)| (name.clone(), TableMetadata::new(name, &view.view_metadata)))
+                .collect(),
+            user_types: keyspace
+                .user_defined_types
+                .iter()
+                .map(|(name, udt)| (name.clone(), UserType::from(udt.as_ref())))
+                .collect(),
+            functions: Vec::new(),
+            aggregates: Vec::new(),
+        }
+    }
+}
+
+#[pymethods]
+impl KeyspaceMetadata {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "KeyspaceMetadata(name={}, strategy={}, tablets={}, tables={}, user_types={}, functions={}, aggregates={})",
+            self.name,
+            self.strategy,
+            self.tablets,
+            self.tables.len(),
+            self.user_types.len(),
+            self.functions.len(),
+            self.aggregates.len()
+        )
+    }
+}
+
+type FunctionRow = (
+    String,
+    Option<Vec<String>>,
+    Option<Vec<String>>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<bool>,
+);
+
+type AggregateRow = (
+    String,
+    Option<Vec<String>>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+);
+
+// The driver doesn't track UDFs and UDAs, so they are read from system_schema directly
+async fn fetch_functions(
+    session: &ScyllaSession,
+    keyspace: &str,
+) -> PyResult<(Vec<FunctionMetadata>, Vec<AggregateMetadata>)> {
+    let rows = session
+        .query_unpaged(
+            "SELECT function_name, argument_names, argument_types, return_type, language, body, \
+             called_on_null_input FROM system_schema.functions WHERE keyspace_name = ?",
+            (keyspace,),
+        )
+        .await
+        .map_err(query_error_to_py)?
+        .into_rows_result()
+        .map_err(deserialization_error_to_py)?;
+    let mut functions = Vec::new();
+    for row in rows
+        .rows::<FunctionRow>()
+        .map_err(deserialization_error_to_py)?
+    {
+        let (name, argument_names, argument_types, return_type, language, body, called_on_null) =
+            row.map_err(deserialization_error_to_py)?;
+        functions.push(FunctionMetadata {
+            keyspace: keyspace.to_string(),
+            name,
+            argument_names: argument_names.unwrap_or_default(),
+            argument_types: argument_types.unwrap_or_default(),
+            return_type,
+            language,
+            body,
+            called_on_null_input: called_on_null,
+        });
+    }
+
+    let rows = session
+        .query_unpaged(
+            "SELECT aggregate_name, argument_types, state_func, state_type, final_func, initcond, \
+             return_type FROM system_schema.aggregates WHERE keyspace_name = ?",
+            (keyspace,),
+        )
+        .await
+        .map_err(query_error_to_py)?
+        .into_rows_result()
+        .map_err(deserialization_error_to_py)?;
+    let mut aggregates = Vec::new();
+    for row in rows
+        .rows::<AggregateRow>()
+        .map_err(deserialization_error_to_py)?
+    {
+        let (name, argument_types, state_func, state_type, final_func, initcond, return_type) =
+            row.map_err(deserialization_error_to_py)?;
+        aggregates.push(AggregateMetadata {
+            keyspace: keyspace.to_string(),
+            name,
+            argument_types: argument_types.unwrap_or_default(),
+            state_func,
+            state_type,
+            final_func,
+            initcond,
+            return_type,
+        });
+    }
+
+    Ok((functions, aggregates))
+}
+
+pub async fn keyspace_metadata(
+    session: &ScyllaSession,
+    keyspace: &str,
+) -> PyResult<Option<KeyspaceMetadata>> {
+    let metadata = {
+        let cluster_state = session.get_cluster_state();
+        match cluster_state.get_keyspace(keyspace) {
+            Some(ks) => KeyspaceMetadata::new(keyspace, ks),
+            None => return Ok(None),
+        }
+    };
+    let (functions, aggregates) = fetch_functions(session, keyspace).await?;
+    Ok(Some(KeyspaceMetadata {
+        functions,
+        aggregates,
+        ..metadata
+    }))
+}
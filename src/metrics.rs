@@ -0,0 +1,376 @@
+use pyo3::prelude::*;
+use scylla::cluster::{ClusterState, NodeRef};
+use scylla::errors::RequestAttemptError;
+use scylla::observability::metrics::Metrics as ScyllaMetrics;
+use scylla::policies::load_balancing::{FallbackPlan, LoadBalancingPolicy, RoutingInfo};
+use scylla::routing::Shard;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct Metrics {
+    #[pyo3(get)]
+    requests: u64,
+    #[pyo3(get)]
+    paged_requests: u64,
+    #[pyo3(get)]
+    errors: u64,
+    #[pyo3(get)]
+    paged_errors: u64,
+    #[pyo3(get)]
+    retries: u64,
+    #[pyo3(get)]
+    request_timeouts: u64,
+    #[pyo3(get)]
+    connection_timeouts: u64,
+    #[pyo3(get)]
+    total_connections: u64,
+    #[pyo3(get)]
+    mean_rate: f64,
+    #[pyo3(get)]
+    one_minute_rate: f64,
+    #[pyo3(get)]
+    five_minute_rate: f64,
+    #[pyo3(get)]
+    fifteen_minute_rate: f64,
+    #[pyo3(get)]
+    latency_min_ms: Option<u64>,
+    #[pyo3(get)]
+    latency_max_ms: Option<u64>,
+    #[pyo3(get)]
+    latency_mean_ms: Option<u64>,
+    #[pyo3(get)]
+    latency_stddev_ms: Option<u64>,
+    #[pyo3(get)]
+    p50: Option<u64>,
+    #[pyo3(get)]
+    p75: Option<u64>,
+    #[pyo3(get)]
+    p95: Option<u64>,
+    #[pyo3(get)]
+    p98: Option<u64>,
+    #[pyo3(get)]
+    p99: Option<u64>,
+    #[pyo3(get)]
+    p999: Option<u64>,
+}
+
+impl Metrics {
+    pub fn snapshot(metrics: &ScyllaMetrics) -> Self {
+        // The latency histogram is empty until the first request completes
+        let latency = metrics.get_snapshot().ok();
+        Metrics {
+            requests: metrics.get_requests_unpaged_num()
+                + metrics.get_requests_manually_paged_num(),
+            paged_requests: metrics.get_requests_automatically_paged_num(),
+            errors: metrics.get_errors_unpaged_num() + metrics.get_errors_manually_paged_num(),
+            paged_errors: metrics.get_errors_automatically_paged_num(),
+            retries: metrics.get_retries_num(),
+            request_timeouts: metrics.get_request_timeouts(),
+            connection_timeouts: metrics.get_connection_timeouts(),
+            total_connections: metrics.get_total_connections(),
+            mean_rate: metrics.get_mean_rate(),
+            one_minute_rate: metrics.get_one_minute_rate(),
+            five_minute_rate: metrics.get_five_minute_rate(),
+            fifteen_minute_rate: metrics.get_fifteen_minute_rate(),
+            latency_min_ms: latency.as_ref().map(|s| s.min),
+            latency_max_ms: latency.as_ref().map(|s| s.max),
+            latency_mean_ms: latency.as_ref().map(|s| s.mean),
+            latency_stddev_ms: latency.as_ref().map(|s| s.stddev),
+            p50: latency.as_ref().map(|s| s.median),
+            p75: latency.as_ref().map(|s| s.percentile_75),
+            p95: latency.as_ref().map(|s| s.percentile_95),
+            p98: latency.as_ref().map(|s| s.percentile_98),
+            p99: latency.as_ref().map(|s| s.percentile_99),
+            p999: latency.as_ref().map(|s| s.percentile_99_9),
+        }
+    }
+}
+
+#[pymethods]
+impl Metrics {
+    pub fn percentile(&self, percentile: f64) -> PyResult<Option<u64>> {
+        match percentile {
+            50.0 => Ok(self.p50),
+            75.0 => Ok(self.p75),
+            95.0 => Ok(self.p95),
+            98.0 => Ok(self.p98),
+            99.0 => Ok(self.p99),
+            99.9 => Ok(self.p999),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unsupported percentile: {}. Use 50, 75, 95, 98, 99 or 99.9",
+                percentile
+            ))),
+        }
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "Metrics(requests={}, errors={}, retries={}, p50={:?}, p95={:?}, p99={:?})",
+            self.requests, self.errors, self.retries, self.p50, self.p95, self.p99
+        )
+    }
+}
+
+const LATENCY_BUCKETS: [f64; 12] = [
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+];
+
+#[derive(Default)]
+struct HostStats {
+    datacenter: Option<String>,
+    requests: u64,
+    errors: u64,
+    latency_sum: f64,
+    latency_buckets: [u64; LATENCY_BUCKETS.len()],
+}
+
+// Per-host counters fed by HostMetricsPolicy
+#[derive(Default)]
+pub struct HostMetrics {
+    hosts: Mutex<BTreeMap<String, HostStats>>,
+}
+
+impl HostMetrics {
+    fn record(&self, node: NodeRef<'_>, latency: Duration, failed: bool) {
+        let mut hosts = self.hosts.lock().unwrap();
+        let stats = hosts
+            .entry(format!("{}:{}", node.address.ip(), node.address.port()))
+            .or_default();
+        stats.datacenter.clone_from(&node.datacenter);
+        stats.requests += 1;
+        if failed {
+            stats.errors += 1;
+        }
+        let latency = latency.as_secs_f64();
+        stats.latency_sum += latency;
+        for (bucket, bound) in stats.latency_buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if latency <= bound {
+                *bucket += 1;
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for HostMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HostMetrics").finish_non_exhaustive()
+    }
+}
+
+// Wraps the session's policy to observe the outcome of every attempt
+#[derive(Debug)]
+pub struct HostMetricsPolicy {
+    inner: Arc<dyn LoadBalancingPolicy>,
+    metrics: Arc<HostMetrics>,
+}
+
+impl HostMetricsPolicy {
+    pub fn new(inner: Arc<dyn LoadBalancingPolicy>, metrics: Arc<HostMetrics>) -> Self {
+        HostMetricsPolicy { inner, metrics }
+    }
+}
+
+impl LoadBalancingPolicy for HostMetricsPolicy {
+    fn pick<'a>(
+        &'a self,
+        request: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> Option<(NodeRef<'a>, Option<Shard>)> {
+        self.inner.pick(request, cluster)
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        request: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> FallbackPlan<'a> {
+        self.inner.fallback(request, cluster)
+    }
+
+    fn on_request_success(&self, request: &RoutingInfo, latency: Duration, node: NodeRef<'_>) {
+        self.metrics.record(node, latency, false);
+        self.inner.on_request_success(request, latency, node);
+    }
+
+    fn on_request_failure(
+        &self,
+        request: &RoutingInfo,
+        latency: Duration,
+        node: NodeRef<'_>,
+        error: &RequestAttemptError,
+    ) {
+        self.metrics.record(node, latency, true);
+        self.inner.on_request_failure(request, latency, node, error);
+    }
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+}
+
+fn write_metric(out: &mut String, name: &str, kind: &str, help: &str, value: impl Display) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+// Renders the Prometheus text exposition format
+pub fn render_prometheus(metrics: &ScyllaMetrics, hosts: Option<&HostMetrics>) -> String {
+    let snapshot = Metrics::snapshot(metrics);
+    let mut out = String::new();
+
+    write_metric(
+        &mut out,
+        "rsylla_requests_total",
+        "counter",
+        "Unpaged and manually paged requests",
+        snapshot.requests,
+    );
+    write_metric(
+        &mut out,
+        "rsylla_paged_requests_total",
+        "counter",
+        "Automatically paged requests",
+        snapshot.paged_requests,
+    );
+    write_metric(
+        &mut out,
+        "rsylla_errors_total",
+        "counter",
+        "Failed unpaged and manually paged requests",
+        snapshot.errors,
+    );
+    write_metric(
+        &mut out,
+        "rsylla_paged_errors_total",
+        "counter",
+        "Failed automatically paged requests",
+        snapshot.paged_errors,
+    );
+    write_metric(
+        &mut out,
+        "rsylla_retries_total",
+        "counter",
+        "Retries decided by the retry policy",
+        snapshot.retries,
+    );
+    write_metric(
+        &mut out,
+        "rsylla_request_timeouts_total",
+        "counter",
+        "Client-side request timeouts",
+        snapshot.request_timeouts,
+    );
+    write_metric(
+        &mut out,
+        "rsylla_connection_timeouts_total",
+        "counter",
+        "Connection timeouts",
+        snapshot.connection_timeouts,
+    );
+    write_metric(
+        &mut out,
+        "rsylla_connections",
+        "gauge",
+        "Open connections",
+        snapshot.total_connections,
+    );
+
+    let _ = writeln!(
+        out,
+        "# HELP rsylla_request_latency_milliseconds Request latency percentiles"
+    );
+    let _ = writeln!(out, "# TYPE rsylla_request_latency_milliseconds gauge");
+    for (quantile, value) in [
+        ("0.5", snapshot.p50),
+        ("0.75", snapshot.p75),
+        ("0.95", snapshot.p95),
+        ("0.98", snapshot.p98),
+        ("0.99", snapshot.p99),
+        ("0.999", snapshot.p999),
+    ] {
+        if let Some(value) = value {
+            let _ = writeln!(
+                out,
+                "rsylla_request_latency_milliseconds{{quantile=\"{}\"}} {}",
+                quantile, value
+            );
+        }
+    }
+
+    if let Some(hosts) = hosts {
+        let hosts = hosts.hosts.lock().unwrap();
+        let labels = |host: &str, stats: &HostStats| {
+            format!(
+                "host=\"{}\",datacenter=\"{}\"",
+                host,
+                stats.datacenter.as_deref().unwrap_or("")
+            )
+        };
+
+        let _ = writeln!(
+            out,
+            "# HELP rsylla_host_requests_total Attempts sent to a host"
+        );
+        let _ = writeln!(out, "# TYPE rsylla_host_requests_total counter");
+        for (host, stats) in hosts.iter() {
+            let _ = writeln!(
+                out,
+                "rsylla_host_requests_total{{{}}} {}",
+                labels(host, stats),
+                stats.requests
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP rsylla_host_errors_total Failed attempts on a host"
+        );
+        let _ = writeln!(out, "# TYPE rsylla_host_errors_total counter");
+        for (host, stats) in hosts.iter() {
+            let _ = writeln!(
+                out,
+                "rsylla_host_errors_total{{{}}} {}",
+                labels(host, stats),
+                stats.errors
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP rsylla_host_latency_seconds Attempt latency on a host"
+        );
+        let _ = writeln!(out, "# TYPE rsylla_host_latency_seconds histogram");
+        for (host, stats) in hosts.iter() {
+            let labels = labels(host, stats);
+            for (count, bound) in stats.latency_buckets.iter().zip(LATENCY_BUCKETS) {
+                let _ = writeln!(
+                    out,
+                    "rsylla_host_latency_seconds_bucket{{{},le=\"{}\"}} {}",
+                    labels, bound, count
+                );
+            }
+            let _ = writeln!(
+                out,
+                "rsylla_host_latency_seconds_bucket{{{},le=\"+Inf\"}} {}",
+                labels, stats.requests
+            );
+            let _ = writeln!(
+                out,
+                "rsylla_host_latency_seconds_sum{{{}}} {}",
+                labels, stats.latency_sum
+            );
+            let _ = writeln!(
+                out,
+                "rsylla_host_latency_seconds_count{{{}}} {}",
+                labels, stats.requests
+            );
+        }
+    }
+
+    out
+}
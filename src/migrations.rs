@@ -0,0 +1,364 @@
+use pyo3::prelude::*;
+use scylla::client::session::Session as ScyllaSession;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::builder::quote_identifier;
+use crate::error::{
+    deserialization_error_to_py, metadata_error_to_py, query_error_to_py,
+    schema_agreement_error_to_py,
+};
+use crate::session::Session;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct Migration {
+    #[pyo3(get)]
+    version: i64,
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    path: String,
+    #[pyo3(get)]
+    statements: Vec<String>,
+}
+
+#[pymethods]
+impl Migration {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "Migration(version={}, name={}, statements={})",
+            self.version,
+            self.name,
+            self.statements.len()
+        )
+    }
+}
+
+// Splits a CQL script on semicolons outside of strings and comments, dropping the comments
+pub fn split_statements(script: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut chars = script.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                current.push(c);
+                for next in chars.by_ref() {
+                    current.push(next);
+                    if next == c {
+                        break;
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'$') => {
+                chars.next();
+                current.push_str("$$");
+                while let Some(next) = chars.next() {
+                    current.push(next);
+                    if next == '$' && chars.peek() == Some(&'$') {
+                        chars.next();
+                        current.push('$');
+                        break;
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                chars.by_ref().find(|&next| next == '\n');
+                current.push('\n');
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                chars.by_ref().find(|&next| next == '\n');
+                current.push('\n');
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+                current.push(' ');
+            }
+            ';' => statements.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    statements.push(current);
+    statements
+        .into_iter()
+        .map(|statement| statement.trim().to_string())
+        .filter(|statement| !statement.is_empty())
+        .collect()
+}
+
+fn is_schema_change(statement: &str) -> bool {
+    let keyword = statement
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    matches!(keyword.as_str(), "CREATE" | "ALTER" | "DROP")
+}
+
+// Migration files are named <version>_<name>.cql and applied by ascending version
+pub fn load_migrations(directory: &Path) -> PyResult<Vec<Migration>> {
+    let entries = std::fs::read_dir(directory).map_err(|err| {
+        PyErr::new::<pyo3::exceptions::PyOSError, _>(format!(
+            "Can't read migrations from {}: {}",
+            directory.display(),
+            err
+        ))
+    })?;
+
+    let mut migrations: Vec<Migration> = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|err| PyErr::new::<pyo3::exceptions::PyOSError, _>(err.to_string()))?
+            .path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("cql") {
+            continue;
+        }
+        let stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+        let digits = stem.chars().take_while(|c| c.is_ascii_digit()).count();
+        let version = stem[..digits].parse::<i64>().map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Migration file name must start with a version number: {}",
+                path.display()
+            ))
+        })?;
+        let name = stem[digits..].trim_start_matches(['_', '-']).to_string();
+
+        if let Some(other) = migrations.iter().find(|m| m.version == version) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Duplicate migration version {}: {} and {}",
+                version,
+                other.path,
+                path.display()
+            )));
+        }
+        let script = std::fs::read_to_string(&path)
+            .map_err(|err| PyErr::new::<pyo3::exceptions::PyOSError, _>(err.to_string()))?;
+        migrations.push(Migration {
+            version,
+            name,
+            path: path.display().to_string(),
+            statements: split_statements(&script),
+        });
+    }
+    migrations.sort_by_key(|migration| migration.version);
+    Ok(migrations)
+}
+
+async fn applied_versions(
+    session: &ScyllaSession,
+    keyspace: &str,
+    table: &str,
+) -> PyResult<BTreeSet<i64>> {
+    // Nothing was applied yet if the versions table doesn't exist
+    session
+        .refresh_metadata()
+        .await
+        .map_err(metadata_error_to_py)?;
+    let cluster_state = session.get_cluster_state();
+    let exists = cluster_state
+        .get_keyspace(keyspace)
+        .is_some_and(|ks| ks.tables.contains_key(table));
+    if !exists {
+        return Ok(BTreeSet::new());
+    }
+
+    let rows = session
+        .query_unpaged(
+            format!(
+                "SELECT version FROM {}.{}",
+                quote_identifier(keyspace)?,
+                quote_identifier(table)?
+            ),
+            (),
+        )
+        .await
+        .map_err(query_error_to_py)?
+        .into_rows_result()
+        .map_err(deserialization_error_to_py)?;
+    rows.rows::<(i64,)>()
+        .map_err(deserialization_error_to_py)?
+        .map(|row| {
+            row.map(|(version,)| version)
+                .map_err(deserialization_error_to_py)
+        })
+        .collect()
+}
+
+async fn apply(
+    session: &ScyllaSession,
+    migration: &Migration,
+    keyspace: &str,
+    table: &str,
+) -> PyResult<()> {
+    for statement in &migration.statements {
+        session
+            .query_unpaged(statement.as_str(), ())
+            .await
+            .map_err(query_error_to_py)?;
+        if is_schema_change(statement) {
+            session
+                .await_schema_agreement()
+                .await
+                .map_err(schema_agreement_error_to_py)?;
+        }
+    }
+
+    // The keyspace may have been created by this very migration, so the table is created late
+    let versions_table = format!(
+        "{}.{}",
+        quote_identifier(keyspace)?,
+        quote_identifier(table)?
+    );
+    session
+        .query_unpaged(
+            format!(
+                "CREATE TABLE IF NOT EXISTS {} \
+                 (version bigint PRIMARY KEY, name text, applied_at timestamp)",
+                versions_table
+            ),
+            (),
+        )
+        .await
+        .map_err(query_error_to_py)?;
+    session
+        .await_schema_agreement()
+        .await
+        .map_err(schema_agreement_error_to_py)?;
+    session
+        .query_unpaged(
+            format!(
+                "INSERT INTO {} (version, name, applied_at) VALUES (?, ?, toTimestamp(now()))",
+                versions_table
+            ),
+            (migration.version, migration.name.as_str()),
+        )
+        .await
+        .map_err(query_error_to_py)?;
+    Ok(())
+}
+
+// Applies CQL migration files in order and records them in a versions table
+#[pyclass]
+pub struct MigrationRunner {
+    session: Session,
+    #[pyo3(get)]
+    directory: PathBuf,
+    keyspace: Option<String>,
+    #[pyo3(get)]
+    table: String,
+}
+
+impl MigrationRunner {
+    fn context(&self) -> PyResult<(Arc<ScyllaSession>, String)> {
+        let session = self.session.session()?;
+        let keyspace = match &self.keyspace {
+            Some(keyspace) => keyspace.clone(),
+            None => session
+                .get_keyspace()
+                .map(|keyspace| keyspace.to_string())
+                .ok_or_else(|| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "MigrationRunner needs a keyspace when the session has none selected",
+                    )
+                })?,
+        };
+        Ok((session, keyspace))
+    }
+}
+
+#[pymethods]
+impl MigrationRunner {
+    #[new]
+    #[pyo3(signature = (session, directory, keyspace=None, table="schema_migrations"))]
+    pub fn new(
+        session: PyRef<'_, Session>,
+        directory: PathBuf,
+        keyspace: Option<String>,
+        table: &str,
+    ) -> Self {
+        MigrationRunner {
+            session: session.clone(),
+            directory,
+            keyspace,
+            table: table.to_string(),
+        }
+    }
+
+    #[getter]
+    pub fn keyspace(&self) -> PyResult<String> {
+        Ok(self.context()?.1)
+    }
+
+    pub fn migrations(&self) -> PyResult<Vec<Migration>> {
+        load_migrations(&self.directory)
+    }
+
+    pub fn applied<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let (session, keyspace) = self.context()?;
+        let table = self.table.clone();
+
+        self.session.run(py, async move {
+            let applied = applied_versions(&session, &keyspace, &table).await?;
+            Ok(applied.into_iter().collect::<Vec<_>>())
+        })
+    }
+
+    pub fn pending<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.run(py, true, None)
+    }
+
+    #[pyo3(signature = (dry_run=false, target=None))]
+    pub fn run<'py>(
+        &self,
+        py: Python<'py>,
+        dry_run: bool,
+        target: Option<i64>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let (session, keyspace) = self.context()?;
+        let table = self.table.clone();
+        let migrations = load_migrations(&self.directory)?;
+
+        self.session.run(py, async move {
+            let applied = applied_versions(&session, &keyspace, &table).await?;
+            let pending: Vec<Migration> = migrations
+                .into_iter()
+                .filter(|migration| !applied.contains(&migration.version))
+                .filter(|migration| target.is_none_or(|target| migration.version <= target))
+                .collect();
+            if dry_run {
+                return Ok(pending);
+            }
+
+            for migration in &pending {
+                if let Err(err) = apply(&session, migration, &keyspace, &table).await {
+                    Python::attach(|py| {
+                        let note = format!("while applying migration {}", migration.path);
+                        err.value(py).call_method1("add_note", (note,)).map(|_| ())
+                    })?;
+                    return Err(err);
+                }
+            }
+            Ok(pending)
+        })
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "MigrationRunner(directory={}, table={})",
+            self.directory.display(),
+            self.table
+        )
+    }
+}
@@ -0,0 +1,62 @@
+use pyo3::prelude::*;
+use scylla::cluster::ClusterState;
+use std::fmt::Write as _;
+
+use crate::cluster::HostInfo;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PoolStats {
+    #[pyo3(get)]
+    host: HostInfo,
+    #[pyo3(get)]
+    connected: bool,
+    #[pyo3(get)]
+    shards: Option<u16>,
+}
+
+#[pymethods]
+impl PoolStats {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "PoolStats(address={}:{}, connected={}, shards={:?})",
+            self.host.address, self.host.port, self.connected, self.shards
+        )
+    }
+}
+
+// State of the session's pool of every enabled node, as the driver reports it
+pub fn stats(cluster_state: &ClusterState) -> Vec<PoolStats> {
+    cluster_state
+        .get_nodes_info()
+        .iter()
+        .filter(|node| node.is_enabled())
+        .map(|node| PoolStats {
+            host: HostInfo::from_node(node),
+            connected: node.is_connected(),
+            shards: node.sharder().map(|sharder| sharder.nr_shards.get()),
+        })
+        .collect()
+}
+
+// Appends a per-node gauge in the Prometheus text format
+pub fn render_prometheus(stats: &[PoolStats], out: &mut String) {
+    let name = "rsylla_pool_connected";
+    let _ = writeln!(
+        out,
+        "# HELP {} 1 while the pool of a host has a working connection",
+        name
+    );
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+    for stats in stats {
+        let _ = writeln!(
+            out,
+            "{}{{host=\"{}:{}\",datacenter=\"{}\"}} {}",
+            name,
+            stats.host.address,
+            stats.host.port,
+            stats.host.datacenter.as_deref().unwrap_or(""),
+            u8::from(stats.connected)
+        );
+    }
+}
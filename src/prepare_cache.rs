@@ -0,0 +1,309 @@
+use pyo3::prelude::*;
+use scylla::errors::{DbError, RequestAttemptError, RequestError};
+use scylla::observability::history::{AttemptId, HistoryListener, RequestId, SpeculativeId};
+use scylla::policies::retry::RetryDecision;
+use scylla::statement::prepared::PreparedStatement as ScyllaPreparedStatement;
+use scylla::statement::unprepared::Statement as ScyllaQuery;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use crate::builder::top_level_words;
+
+// Unqualified table names resolve against the keyspace in use, so it is part of the key
+pub type CacheKey = (Option<String>, String);
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PrepareCacheStats {
+    #[pyo3(get)]
+    hits: u64,
+    #[pyo3(get)]
+    misses: u64,
+    #[pyo3(get)]
+    evictions: u64,
+    #[pyo3(get)]
+    reprepares: u64,
+    #[pyo3(get)]
+    size: usize,
+    #[pyo3(get)]
+    capacity: usize,
+}
+
+#[pymethods]
+impl PrepareCacheStats {
+    #[getter]
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "PrepareCacheStats(hits={}, misses={}, evictions={}, reprepares={}, size={}, capacity={})",
+            self.hits,
+            self.misses,
+            self.evictions,
+            self.reprepares,
+            self.size,
+            self.capacity
+        )
+    }
+}
+
+#[derive(Default)]
+struct CacheState {
+    // Each entry remembers the tick of its last use, the oldest one is evicted first
+    entries: HashMap<CacheKey, (Arc<ScyllaPreparedStatement>, u64)>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+    reprepares: u64,
+}
+
+// LRU of statements prepared on behalf of `execute()` when auto-prepare is enabled
+pub struct PrepareCache {
+    capacity: usize,
+    state: Mutex<CacheState>,
+}
+
+impl PrepareCache {
+    pub fn new(capacity: usize) -> Arc<Self> {
+        Arc::new(PrepareCache {
+            capacity,
+            state: Mutex::new(CacheState::default()),
+        })
+    }
+
+    pub fn get(&self, key: &CacheKey) -> Option<Arc<ScyllaPreparedStatement>> {
+        let mut state = self.state.lock().unwrap();
+        state.tick += 1;
+        let tick = state.tick;
+        match state.entries.get_mut(key) {
+            Some((prepared, last_used)) => {
+                *last_used = tick;
+                let prepared = prepared.clone();
+                state.hits += 1;
+                Some(prepared)
+            }
+            None => {
+                state.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn insert(&self, key: CacheKey, prepared: Arc<ScyllaPreparedStatement>) {
+        let mut state = self.state.lock().unwrap();
+        if !state.entries.contains_key(&key) && state.entries.len() >= self.capacity {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                state.entries.remove(&oldest);
+                state.evictions += 1;
+            }
+        }
+        state.tick += 1;
+        let tick = state.tick;
+        state.entries.insert(key, (prepared, tick));
+    }
+
+    // Drops a statement a node rejected so that the next execution prepares it again, unless it
+    // was already replaced
+    fn rejected(&self, key: &CacheKey, id: &[u8]) -> bool {
+        let mut state = self.state.lock().unwrap();
+        match state.entries.get(key) {
+            Some((prepared, _)) if prepared.get_id().as_ref() == id => {
+                state.entries.remove(key);
+                state.reprepares += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn stats(&self) -> PrepareCacheStats {
+        let state = self.state.lock().unwrap();
+        PrepareCacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            evictions: state.evictions,
+            reprepares: state.reprepares,
+            size: state.entries.len(),
+            capacity: self.capacity,
+        }
+    }
+}
+
+// Only DML is prepared automatically, schema and USE statements are sent as they are
+pub fn is_preparable(statement: &str) -> bool {
+    let words = top_level_words(statement);
+    let keyword = words.first().map_or("", |(_, word)| word.as_str());
+    matches!(keyword, "SELECT" | "INSERT" | "UPDATE" | "DELETE" | "BEGIN")
+}
+
+// Applies the per-call options of an unprepared statement to a cached prepared one
+pub fn with_options(
+    prepared: &ScyllaPreparedStatement,
+    statement: &ScyllaQuery,
+) -> ScyllaPreparedStatement {
+    let mut prepared = prepared.clone();
+    if let Some(consistency) = statement.get_consistency() {
+        prepared.set_consistency(consistency);
+    }
+    prepared.set_serial_consistency(statement.get_serial_consistency());
+    prepared.set_page_size(statement.get_page_size());
+    prepared.set_is_idempotent(statement.get_is_idempotent());
+    prepared.set_tracing(statement.get_tracing());
+    prepared.set_timestamp(statement.get_timestamp());
+    prepared.set_request_timeout(statement.get_request_timeout());
+    prepared.set_retry_policy(statement.get_retry_policy().cloned());
+    prepared.set_execution_profile_handle(statement.get_execution_profile_handle().cloned());
+    prepared
+}
+
+// Appends the cache counters in the Prometheus text format
+pub fn render_prometheus(stats: &PrepareCacheStats, out: &mut String) {
+    let metrics = [
+        (
+            "rsylla_prepare_cache_hits_total",
+            "counter",
+            "Auto-prepare cache lookups that found a prepared statement",
+            stats.hits,
+        ),
+        (
+            "rsylla_prepare_cache_misses_total",
+            "counter",
+            "Auto-prepare cache lookups that had to prepare the statement",
+            stats.misses,
+        ),
+        (
+            "rsylla_prepare_cache_evictions_total",
+            "counter",
+            "Statements evicted from the auto-prepare cache",
+            stats.evictions,
+        ),
+        (
+            "rsylla_prepare_cache_reprepares_total",
+            "counter",
+            "Cached statements reprepared after a node reported them unprepared",
+            stats.reprepares,
+        ),
+        (
+            "rsylla_prepare_cache_size",
+            "gauge",
+            "Statements in the auto-prepare cache",
+            stats.size as u64,
+        ),
+    ];
+    for (name, kind, help, value) in metrics {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} {}", name, kind);
+        let _ = writeln!(out, "{} {}", name, value);
+    }
+}
+
+// Statement of an auto-prepared request, as it is stored in the cache
+pub struct CachedStatement {
+    pub cache: Arc<PrepareCache>,
+    pub key: CacheKey,
+}
+
+impl CachedStatement {
+    // Wraps the request's history listener to notice nodes rejecting the statement
+    pub fn listener(
+        &self,
+        history: Arc<dyn HistoryListener>,
+        prepared: &ScyllaPreparedStatement,
+    ) -> Arc<dyn HistoryListener> {
+        Arc::new(RepreparationListener {
+            history,
+            cache: self.cache.clone(),
+            key: self.key.clone(),
+            id: prepared.get_id().to_vec(),
+        })
+    }
+}
+
+// The driver reprepares a statement a node no longer has, e.g. after the node restarted or
+// evicted it from its own cache, on the same connection. The attempt only fails when that
+// doesn't help, or when the statement came back with another id, and the cached statement is
+// then stale.
+struct RepreparationListener {
+    history: Arc<dyn HistoryListener>,
+    cache: Arc<PrepareCache>,
+    key: CacheKey,
+    id: Vec<u8>,
+}
+
+impl std::fmt::Debug for RepreparationListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RepreparationListener")
+            .field("history", &self.history)
+            .field("statement", &self.key.1)
+            .finish()
+    }
+}
+
+impl HistoryListener for RepreparationListener {
+    fn log_request_start(&self) -> RequestId {
+        self.history.log_request_start()
+    }
+
+    fn log_request_success(&self, request_id: RequestId) {
+        self.history.log_request_success(request_id)
+    }
+
+    fn log_request_error(&self, request_id: RequestId, error: &RequestError) {
+        self.history.log_request_error(request_id, error)
+    }
+
+    fn log_new_speculative_fiber(&self, request_id: RequestId) -> SpeculativeId {
+        self.history.log_new_speculative_fiber(request_id)
+    }
+
+    fn log_attempt_start(
+        &self,
+        request_id: RequestId,
+        speculative_id: Option<SpeculativeId>,
+        node_addr: SocketAddr,
+    ) -> AttemptId {
+        self.history
+            .log_attempt_start(request_id, speculative_id, node_addr)
+    }
+
+    fn log_attempt_success(&self, attempt_id: AttemptId) {
+        self.history.log_attempt_success(attempt_id)
+    }
+
+    fn log_attempt_error(
+        &self,
+        attempt_id: AttemptId,
+        error: &RequestAttemptError,
+        retry_decision: &RetryDecision,
+    ) {
+        self.history
+            .log_attempt_error(attempt_id, error, retry_decision);
+        let unprepared = matches!(
+            error,
+            RequestAttemptError::DbError(DbError::Unprepared { .. }, _)
+                | RequestAttemptError::RepreparedIdChanged { .. }
+        );
+        if unprepared && self.cache.rejected(&self.key, &self.id) {
+            ::tracing::info!(
+                target: "rsylla::prepare_cache",
+                "Statement dropped from the cache after a node reported it unprepared: {}",
+                self.key.1
+            );
+        }
+    }
+}
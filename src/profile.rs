@@ -0,0 +1,412 @@
+use pyo3::prelude::*;
+use scylla::client::execution_profile::{
+    ExecutionProfile as ScyllaExecutionProfile, ExecutionProfileHandle,
+};
+use scylla::policies::retry::{
+    DefaultRetryPolicy, DowngradingConsistencyRetryPolicy, FallthroughRetryPolicy, RetryPolicy,
+};
+use scylla::policies::speculative_execution::{
+    PercentileSpeculativeExecutionPolicy, SimpleSpeculativeExecutionPolicy,
+    SpeculativeExecutionPolicy,
+};
+use scylla::statement::{Consistency, SerialConsistency};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use crate::circuit_breaker::CircuitBreakers;
+use crate::metrics::HostMetrics;
+use crate::query::{
+    consistency_to_str, parse_consistency, parse_serial_consistency, serial_consistency_to_str,
+};
+use crate::rate_limit::RateLimiter;
+use crate::session::LoadBalancingConfig;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Speculative {
+    Disabled,
+    Delay(usize, u64),
+    Percentile(usize, f64),
+}
+
+fn parse_retry_policy(retry_policy: &str) -> PyResult<&'static str> {
+    match retry_policy.to_lowercase().as_str() {
+        "default" => Ok("default"),
+        "fallthrough" => Ok("fallthrough"),
+        "downgrading_consistency" => Ok("downgrading_consistency"),
+        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Invalid retry policy: {}. Must be 'default', 'fallthrough' or 'downgrading_consistency'",
+            retry_policy
+        ))),
+    }
+}
+
+fn parse_speculative(
+    executions: Option<usize>,
+    delay_ms: Option<u64>,
+    percentile: Option<f64>,
+) -> PyResult<Option<Speculative>> {
+    match (executions, delay_ms, percentile) {
+        (None, None, None) => Ok(None),
+        (Some(0), _, _) => Ok(Some(Speculative::Disabled)),
+        (Some(executions), Some(delay_ms), None) => {
+            Ok(Some(Speculative::Delay(executions, delay_ms)))
+        }
+        (Some(executions), None, Some(percentile)) if percentile > 0.0 && percentile < 100.0 => {
+            Ok(Some(Speculative::Percentile(executions, percentile)))
+        }
+        (Some(_), None, Some(_)) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "speculative_percentile must be between 0 and 100",
+        )),
+        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "speculative_executions needs either speculative_delay_ms or speculative_percentile",
+        )),
+    }
+}
+
+// Profile settings applied by rsylla rather than the driver, carried by the statements the
+// profile is set on
+#[derive(Clone, Default)]
+pub struct ProfileOptions {
+    pub query_log: Option<bool>,
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+// Every field is optional: unset ones are inherited from the profile this one was derived
+// from, or take the driver defaults
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct ExecutionProfile {
+    consistency: Option<Consistency>,
+    serial_consistency: Option<SerialConsistency>,
+    timeout_ms: Option<u64>,
+    retry_policy: Option<&'static str>,
+    speculative: Option<Speculative>,
+    local_dc: Option<String>,
+    local_rack: Option<String>,
+    permit_dc_failover: Option<bool>,
+    token_aware: Option<bool>,
+    // Whether requests using the profile go to the session's query log
+    query_log: Option<bool>,
+    // Shared by every statement using the profile, derived profiles get their own bucket
+    rate_limiter: Option<Arc<RateLimiter>>,
+    // Carried over from the session builder, so profiles derived from the default keep it
+    latency_awareness: Option<scylla::policies::load_balancing::LatencyAwarenessBuilder>,
+    // Built once on first use, so statements sharing a profile share its policies
+    handle: Arc<OnceLock<ExecutionProfileHandle>>,
+}
+
+impl ExecutionProfile {
+    // The profile with the builder's load balancing settings filling in the unset ones
+    pub fn with_load_balancing(&self, base: &LoadBalancingConfig) -> Self {
+        ExecutionProfile {
+            local_dc: self.local_dc.clone().or(base.local_dc.clone()),
+            local_rack: self.local_rack.clone().or(base.local_rack.clone()),
+            permit_dc_failover: self.permit_dc_failover.or(Some(base.permit_dc_failover)),
+            token_aware: self.token_aware.or(Some(base.token_aware)),
+            latency_awareness: self
+                .latency_awareness
+                .clone()
+                .or(base.latency_awareness.clone()),
+            handle: Arc::new(OnceLock::new()),
+            ..self.clone()
+        }
+    }
+
+    fn load_balancing(&self) -> PyResult<LoadBalancingConfig> {
+        let mut config = LoadBalancingConfig::default();
+        if self.local_rack.is_some() && self.local_dc.is_none() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "local_rack requires local_dc to be set",
+            ));
+        }
+        config.local_dc.clone_from(&self.local_dc);
+        config.local_rack.clone_from(&self.local_rack);
+        if let Some(permit_dc_failover) = self.permit_dc_failover {
+            config.permit_dc_failover = permit_dc_failover;
+        }
+        if let Some(token_aware) = self.token_aware {
+            config.token_aware = token_aware;
+        }
+        config.latency_awareness.clone_from(&self.latency_awareness);
+        Ok(config)
+    }
+
+    pub fn build(
+        &self,
+        host_metrics: Option<Arc<HostMetrics>>,
+        circuit_breakers: Option<Arc<CircuitBreakers>>,
+    ) -> PyResult<ScyllaExecutionProfile> {
+        let mut builder = self
+            .load_balancing()?
+            .build_profile(host_metrics, circuit_breakers)
+            .to_builder();
+        if let Some(consistency) = self.consistency {
+            builder = builder.consistency(consistency);
+        }
+        if let Some(serial_consistency) = self.serial_consistency {
+            builder = builder.serial_consistency(Some(serial_consistency));
+        }
+        if let Some(timeout_ms) = self.timeout_ms {
+            builder = builder.request_timeout(Some(Duration::from_millis(timeout_ms)));
+        }
+        if let Some(retry_policy) = self.retry_policy {
+            let policy: Arc<dyn RetryPolicy> = match retry_policy {
+                "fallthrough" => Arc::new(FallthroughRetryPolicy::new()),
+                "downgrading_consistency" => Arc::new(DowngradingConsistencyRetryPolicy::new()),
+                _ => Arc::new(DefaultRetryPolicy::new()),
+            };
+            builder = builder.retry_policy(policy);
+        }
+        if let Some(speculative) = self.speculative {
+            let policy: Option<Arc<dyn SpeculativeExecutionPolicy>> = match speculative {
+                Speculative::Disabled => None,
+                Speculative::Delay(max_retry_count, delay_ms) => {
+                    Some(Arc::new(SimpleSpeculativeExecutionPolicy {
+                        max_retry_count,
+                        retry_interval: Duration::from_millis(delay_ms),
+                    }))
+                }
+                Speculative::Percentile(max_retry_count, percentile) => {
+                    Some(Arc::new(PercentileSpeculativeExecutionPolicy {
+                        max_retry_count,
+                        percentile,
+                    }))
+                }
+            };
+            builder = builder.speculative_execution_policy(policy);
+        }
+        Ok(builder.build())
+    }
+
+    pub fn options(&self) -> ProfileOptions {
+        ProfileOptions {
+            query_log: self.query_log,
+            rate_limiter: self.rate_limiter.clone(),
+        }
+    }
+
+    pub fn handle(&self) -> PyResult<ExecutionProfileHandle> {
+        if let Some(handle) = self.handle.get() {
+            return Ok(handle.clone());
+        }
+        let handle = self.build(None, None)?.into_handle();
+        Ok(self.handle.get_or_init(|| handle).clone())
+    }
+}
+
+#[pymethods]
+impl ExecutionProfile {
+    #[new]
+    #[pyo3(signature = (
+        consistency=None,
+        serial_consistency=None,
+        timeout_ms=None,
+        retry_policy=None,
+        speculative_executions=None,
+        speculative_delay_ms=None,
+        speculative_percentile=None,
+        local_dc=None,
+        local_rack=None,
+        permit_dc_failover=None,
+        token_aware=None,
+        query_log=None,
+        rate_limit=None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        consistency: Option<&str>,
+        serial_consistency: Option<&str>,
+        timeout_ms: Option<u64>,
+        retry_policy: Option<&str>,
+        speculative_executions: Option<usize>,
+        speculative_delay_ms: Option<u64>,
+        speculative_percentile: Option<f64>,
+        local_dc: Option<String>,
+        local_rack: Option<String>,
+        permit_dc_failover: Option<bool>,
+        token_aware: Option<bool>,
+        query_log: Option<bool>,
+        rate_limit: Option<f64>,
+    ) -> PyResult<Self> {
+        ExecutionProfile::default().derive(
+            consistency,
+            serial_consistency,
+            timeout_ms,
+            retry_policy,
+            speculative_executions,
+            speculative_delay_ms,
+            speculative_percentile,
+            local_dc,
+            local_rack,
+            permit_dc_failover,
+            token_aware,
+            query_log,
+            rate_limit,
+        )
+    }
+
+    // A new profile with the given fields replaced and the others kept
+    #[pyo3(signature = (
+        consistency=None,
+        serial_consistency=None,
+        timeout_ms=None,
+        retry_policy=None,
+        speculative_executions=None,
+        speculative_delay_ms=None,
+        speculative_percentile=None,
+        local_dc=None,
+        local_rack=None,
+        permit_dc_failover=None,
+        token_aware=None,
+        query_log=None,
+        rate_limit=None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn derive(
+        &self,
+        consistency: Option<&str>,
+        serial_consistency: Option<&str>,
+        timeout_ms: Option<u64>,
+        retry_policy: Option<&str>,
+        speculative_executions: Option<usize>,
+        speculative_delay_ms: Option<u64>,
+        speculative_percentile: Option<f64>,
+        local_dc: Option<String>,
+        local_rack: Option<String>,
+        permit_dc_failover: Option<bool>,
+        token_aware: Option<bool>,
+        query_log: Option<bool>,
+        rate_limit: Option<f64>,
+    ) -> PyResult<Self> {
+        let profile = ExecutionProfile {
+            consistency: consistency
+                .map(parse_consistency)
+                .transpose()?
+                .or(self.consistency),
+            serial_consistency: serial_consistency
+                .map(parse_serial_consistency)
+                .transpose()?
+                .or(self.serial_consistency),
+            timeout_ms: timeout_ms.or(self.timeout_ms),
+            retry_policy: retry_policy
+                .map(parse_retry_policy)
+                .transpose()?
+                .or(self.retry_policy),
+            speculative: parse_speculative(
+                speculative_executions,
+                speculative_delay_ms,
+                speculative_percentile,
+            )?
+            .or(self.speculative),
+            local_dc: local_dc.or(self.local_dc.clone()),
+            local_rack: local_rack.or(self.local_rack.clone()),
+            permit_dc_failover: permit_dc_failover.or(self.permit_dc_failover),
+            token_aware: token_aware.or(self.token_aware),
+            query_log: query_log.or(self.query_log),
+            rate_limiter: rate_limit
+                .or(self.rate_limit())
+                .map(|rate| RateLimiter::new(rate, None).map(Arc::new))
+                .transpose()?,
+            latency_awareness: self.latency_awareness.clone(),
+            handle: Arc::new(OnceLock::new()),
+        };
+        profile.load_balancing()?;
+        Ok(profile)
+    }
+
+    #[getter]
+    pub fn consistency(&self) -> Option<&'static str> {
+        self.consistency.map(consistency_to_str)
+    }
+
+    #[getter]
+    pub fn serial_consistency(&self) -> Option<&'static str> {
+        self.serial_consistency.map(serial_consistency_to_str)
+    }
+
+    #[getter]
+    pub fn timeout_ms(&self) -> Option<u64> {
+        self.timeout_ms
+    }
+
+    #[getter]
+    pub fn retry_policy(&self) -> Option<&'static str> {
+        self.retry_policy
+    }
+
+    #[getter]
+    pub fn speculative_executions(&self) -> Option<usize> {
+        match self.speculative? {
+            Speculative::Disabled => Some(0),
+            Speculative::Delay(executions, _) | Speculative::Percentile(executions, _) => {
+                Some(executions)
+            }
+        }
+    }
+
+    #[getter]
+    pub fn speculative_delay_ms(&self) -> Option<u64> {
+        match self.speculative? {
+            Speculative::Delay(_, delay_ms) => Some(delay_ms),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    pub fn speculative_percentile(&self) -> Option<f64> {
+        match self.speculative? {
+            Speculative::Percentile(_, percentile) => Some(percentile),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    pub fn local_dc(&self) -> Option<String> {
+        self.local_dc.clone()
+    }
+
+    #[getter]
+    pub fn local_rack(&self) -> Option<String> {
+        self.local_rack.clone()
+    }
+
+    #[getter]
+    pub fn permit_dc_failover(&self) -> Option<bool> {
+        self.permit_dc_failover
+    }
+
+    #[getter]
+    pub fn token_aware(&self) -> Option<bool> {
+        self.token_aware
+    }
+
+    #[getter]
+    pub fn query_log(&self) -> Option<bool> {
+        self.query_log
+    }
+
+    #[getter]
+    pub fn rate_limit(&self) -> Option<f64> {
+        self.rate_limiter.as_ref().map(|limiter| limiter.rate())
+    }
+
+    pub fn __repr__(&self) -> String {
+        let mut fields = Vec::new();
+        if let Some(consistency) = self.consistency() {
+            fields.push(format!("consistency={:?}", consistency));
+        }
+        if let Some(timeout_ms) = self.timeout_ms {
+            fields.push(format!("timeout_ms={}", timeout_ms));
+        }
+        if let Some(retry_policy) = self.retry_policy {
+            fields.push(format!("retry_policy={:?}", retry_policy));
+        }
+        if let Some(local_dc) = &self.local_dc {
+            fields.push(format!("local_dc={:?}", local_dc));
+        }
+        if let Some(rate_limit) = self.rate_limit() {
+            fields.push(format!("rate_limit={}", rate_limit));
+        }
+        format!("ExecutionProfile({})", fields.join(", "))
+    }
+}
@@ -5,10 +5,18 @@ use scylla::statement::unprepared::Statement as ScyllaQuery;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::builder::QueryBuilder;
+use crate::metadata::ColumnSpec;
+use crate::profile::{ExecutionProfile, ProfileOptions};
+use crate::routing::RoutingKey;
+
 #[pyclass]
 #[derive(Clone)]
 pub struct Query {
     pub(crate) inner: ScyllaQuery,
+    pub(crate) routing_key: Option<RoutingKey>,
+    // Settings of the profile set by with_profile() that the driver doesn't apply
+    pub(crate) options: ProfileOptions,
     query_string: String,
 }
 
@@ -18,10 +26,34 @@ impl Query {
     pub fn new(query: &str) -> Self {
         Query {
             inner: ScyllaQuery::new(query),
+            routing_key: None,
+            options: ProfileOptions::default(),
             query_string: query.to_string(),
         }
     }
 
+    #[staticmethod]
+    #[pyo3(signature = (table, columns=None))]
+    pub fn select(table: &str, columns: Option<Vec<String>>) -> PyResult<QueryBuilder> {
+        QueryBuilder::select(table, columns)
+    }
+
+    #[staticmethod]
+    pub fn insert_into(table: &str) -> PyResult<QueryBuilder> {
+        QueryBuilder::insert(table)
+    }
+
+    #[staticmethod]
+    pub fn update(table: &str) -> PyResult<QueryBuilder> {
+        QueryBuilder::update(table)
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (table, columns=None))]
+    pub fn delete_from(table: &str, columns: Option<Vec<String>>) -> PyResult<QueryBuilder> {
+        QueryBuilder::delete(table, columns)
+    }
+
     pub fn with_consistency(&mut self, consistency: &str) -> PyResult<Self> {
         let cons = parse_consistency(consistency)?;
         self.inner.set_consistency(cons);
@@ -35,6 +67,7 @@ impl Query {
     }
 
     pub fn with_page_size(&mut self, page_size: i32) -> PyResult<Self> {
+        check_page_size(Some(page_size))?;
         self.inner.set_page_size(page_size);
         Ok(self.clone())
     }
@@ -55,6 +88,27 @@ impl Query {
         Ok(self.clone())
     }
 
+    pub fn with_profile(&mut self, profile: &ExecutionProfile) -> PyResult<Self> {
+        self.inner
+            .set_execution_profile_handle(Some(profile.handle()?));
+        self.options = profile.options();
+        Ok(self.clone())
+    }
+
+    pub fn with_routing_key(
+        &mut self,
+        routing_key: &[u8],
+        keyspace: &str,
+        table: &str,
+    ) -> PyResult<Self> {
+        self.routing_key = Some(RoutingKey::new(keyspace, table, routing_key));
+        Ok(self.clone())
+    }
+
+    pub fn get_routing_token(&self) -> Option<i64> {
+        self.routing_key.as_ref().map(|key| key.token.value())
+    }
+
     pub fn is_idempotent(&self) -> bool {
         self.inner.get_is_idempotent()
     }
@@ -72,6 +126,8 @@ impl Query {
 #[derive(Clone)]
 pub struct PreparedStatement {
     pub(crate) prepared: Arc<ScyllaPreparedStatement>,
+    // Settings of the profile set by with_profile() that the driver doesn't apply
+    pub(crate) options: ProfileOptions,
 }
 
 #[pymethods]
@@ -82,6 +138,7 @@ impl PreparedStatement {
         new_prepared.set_consistency(cons);
         Ok(PreparedStatement {
             prepared: Arc::new(new_prepared),
+            options: self.options.clone(),
         })
     }
 
@@ -91,14 +148,17 @@ impl PreparedStatement {
         new_prepared.set_serial_consistency(Some(cons));
         Ok(PreparedStatement {
             prepared: Arc::new(new_prepared),
+            options: self.options.clone(),
         })
     }
 
     pub fn with_page_size(&self, page_size: i32) -> PyResult<Self> {
+        check_page_size(Some(page_size))?;
         let mut new_prepared = (*self.prepared).clone();
         new_prepared.set_page_size(page_size);
         Ok(PreparedStatement {
             prepared: Arc::new(new_prepared),
+            options: self.options.clone(),
         })
     }
 
@@ -107,6 +167,7 @@ impl PreparedStatement {
         new_prepared.set_timestamp(Some(timestamp));
         Ok(PreparedStatement {
             prepared: Arc::new(new_prepared),
+            options: self.options.clone(),
         })
     }
 
@@ -115,6 +176,16 @@ impl PreparedStatement {
         new_prepared.set_tracing(tracing);
         Ok(PreparedStatement {
             prepared: Arc::new(new_prepared),
+            options: self.options.clone(),
+        })
+    }
+
+    pub fn with_profile(&self, profile: &ExecutionProfile) -> PyResult<Self> {
+        let mut new_prepared = (*self.prepared).clone();
+        new_prepared.set_execution_profile_handle(Some(profile.handle()?));
+        Ok(PreparedStatement {
+            prepared: Arc::new(new_prepared),
+            options: profile.options(),
         })
     }
 
@@ -127,6 +198,7 @@ impl PreparedStatement {
         new_prepared.set_is_idempotent(idempotent);
         PreparedStatement {
             prepared: Arc::new(new_prepared),
+            options: self.options.clone(),
         }
     }
 
@@ -137,9 +209,55 @@ impl PreparedStatement {
     pub fn get_statement(&self) -> String {
         self.prepared.get_statement().to_string()
     }
+
+    pub fn is_lwt(&self) -> bool {
+        self.prepared.is_confirmed_lwt()
+    }
+
+    pub fn get_variable_col_specs(&self) -> Vec<ColumnSpec> {
+        self.prepared
+            .get_variable_col_specs()
+            .iter()
+            .map(ColumnSpec::from)
+            .collect()
+    }
+
+    pub fn get_variable_names(&self) -> Vec<String> {
+        self.prepared
+            .get_variable_col_specs()
+            .iter()
+            .map(|spec| spec.name().to_string())
+            .collect()
+    }
+
+    // Bind marker indexes of the partition key columns, in partition key order
+    pub fn get_partition_key_indexes(&self) -> Vec<u16> {
+        let mut indexes = self.prepared.get_variable_pk_indexes().to_vec();
+        indexes.sort_by_key(|index| index.sequence);
+        indexes.into_iter().map(|index| index.index).collect()
+    }
+
+    pub fn get_result_col_specs(&self) -> Vec<ColumnSpec> {
+        self.prepared
+            .get_current_result_set_col_specs()
+            .get()
+            .iter()
+            .map(ColumnSpec::from)
+            .collect()
+    }
 }
 
-fn parse_consistency(consistency: &str) -> PyResult<scylla::statement::Consistency> {
+// The driver panics on page sizes below 1
+pub(crate) fn check_page_size(page_size: Option<i32>) -> PyResult<()> {
+    if page_size.is_some_and(|page_size| page_size < 1) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "page_size must be at least 1",
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn parse_consistency(consistency: &str) -> PyResult<scylla::statement::Consistency> {
     match consistency.to_uppercase().as_str() {
         "ANY" => Ok(scylla::statement::Consistency::Any),
         "ONE" => Ok(scylla::statement::Consistency::One),
@@ -157,7 +275,9 @@ fn parse_consistency(consistency: &str) -> PyResult<scylla::statement::Consisten
     }
 }
 
-fn parse_serial_consistency(consistency: &str) -> PyResult<scylla::statement::SerialConsistency> {
+pub(crate) fn parse_serial_consistency(
+    consistency: &str,
+) -> PyResult<scylla::statement::SerialConsistency> {
     match consistency.to_uppercase().as_str() {
         "SERIAL" => Ok(scylla::statement::SerialConsistency::Serial),
         "LOCAL_SERIAL" | "LOCALSERIAL" => Ok(scylla::statement::SerialConsistency::LocalSerial),
@@ -167,3 +287,29 @@ fn parse_serial_consistency(consistency: &str) -> PyResult<scylla::statement::Se
         ))),
     }
 }
+
+pub(crate) fn consistency_to_str(consistency: scylla::statement::Consistency) -> &'static str {
+    use scylla::statement::Consistency;
+    match consistency {
+        Consistency::Any => "ANY",
+        Consistency::One => "ONE",
+        Consistency::Two => "TWO",
+        Consistency::Three => "THREE",
+        Consistency::Quorum => "QUORUM",
+        Consistency::All => "ALL",
+        Consistency::LocalQuorum => "LOCAL_QUORUM",
+        Consistency::EachQuorum => "EACH_QUORUM",
+        Consistency::LocalOne => "LOCAL_ONE",
+        Consistency::Serial => "SERIAL",
+        Consistency::LocalSerial => "LOCAL_SERIAL",
+    }
+}
+
+pub(crate) fn serial_consistency_to_str(
+    consistency: scylla::statement::SerialConsistency,
+) -> &'static str {
+    match consistency {
+        scylla::statement::SerialConsistency::Serial => "SERIAL",
+        scylla::statement::SerialConsistency::LocalSerial => "LOCAL_SERIAL",
+    }
+}
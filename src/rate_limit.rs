@@ -0,0 +1,75 @@
+use pyo3::prelude::*;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+// Token bucket refilled with `rate` tokens per second, holding up to `burst` of them. Every
+// request takes a token, waiting for one to be refilled when the bucket is empty.
+pub struct RateLimiter {
+    rate: f64,
+    burst: f64,
+    // Tokens left and when they were counted. Negative counts are tokens promised to requests
+    // still waiting for them, which keeps the waiting ones in order.
+    state: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+    pub fn new(rate: f64, burst: Option<u32>) -> PyResult<Self> {
+        if !rate.is_finite() || rate <= 0.0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Rate limit must be a positive number of requests per second",
+            ));
+        }
+        // One second worth of requests unless given
+        let burst = match burst {
+            Some(0) => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "Rate limit burst must be at least 1",
+                ))
+            }
+            Some(burst) => burst as f64,
+            None => rate.ceil(),
+        };
+        Ok(RateLimiter {
+            rate,
+            burst,
+            state: Mutex::new((burst, Instant::now())),
+        })
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub async fn acquire(&self) {
+        let wait = {
+            let mut state = self.state.lock().unwrap();
+            let now = Instant::now();
+            let (tokens, counted) = *state;
+            let refilled = now.duration_since(counted).as_secs_f64() * self.rate;
+            let tokens = (tokens + refilled).min(self.burst) - 1.0;
+            *state = (tokens, now);
+            if tokens >= 0.0 {
+                return;
+            }
+            Duration::from_secs_f64(-tokens / self.rate)
+        };
+        tokio::time::sleep(wait).await;
+    }
+}
+
+// Rate limiters a request waits for in turn: the session's, then its profile's
+#[derive(Clone, Default)]
+pub struct Throttle(Vec<Arc<RateLimiter>>);
+
+impl Throttle {
+    pub fn new(limiters: impl IntoIterator<Item = Arc<RateLimiter>>) -> Self {
+        Throttle(limiters.into_iter().collect())
+    }
+
+    pub async fn wait(&self) {
+        for limiter in &self.0 {
+            limiter.acquire().await;
+        }
+    }
+}
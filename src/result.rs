@@ -1,197 +1,792 @@
+use chrono::{DateTime, Utc};
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
-use scylla::response::query_result::{QueryResult as ScyllaQueryResult, QueryRowsResult};
+use pyo3::types::{PyBytes, PyDict, PyIterator, PyList, PyString, PyTuple};
+use scylla::client::session::Session as ScyllaSession;
+use scylla::deserialize::row::ColumnIterator;
+use scylla::frame::response::result::ColumnType;
+use scylla::observability::history::{HistoryCollector, HistoryListener, RequestHistoryResult};
+use scylla::response::query_result::{
+    ColumnSpecs, QueryResult as ScyllaQueryResult, QueryRowsResult,
+};
+use scylla::serialize::row::{RowSerializationContext, SerializeRow};
+use scylla::serialize::value::SerializeValue;
+use scylla::serialize::writers::{CellWriter, RowWriter, WrittenCellProof};
+use scylla::serialize::SerializationError;
 use scylla::value::{CqlValue, Row as ScyllaRow};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Instant;
 
-use crate::types::cql_value_to_py;
+use crate::error::{deserialization_error_to_py, tracing_error_to_py, ScyllaError};
+use crate::events::SchemaChange;
+use crate::metadata::ColumnSpec;
+use crate::session::run_request;
+use crate::stream::RowStream;
+use crate::tracing::TracingInfo;
+use crate::types::{cql_value_to_py, SerializableValue};
 
+#[pyclass(module = "rsylla._rsylla")]
+#[derive(Clone)]
+pub struct ExecutionInfo {
+    #[pyo3(get)]
+    pub(crate) coordinator: String,
+    #[pyo3(get)]
+    coordinator_is_replica: Option<bool>,
+    #[pyo3(get)]
+    pub(crate) attempts: usize,
+    #[pyo3(get)]
+    retries: usize,
+    #[pyo3(get)]
+    speculative_executions: usize,
+    #[pyo3(get)]
+    pub(crate) latency_ms: f64,
+    #[pyo3(get)]
+    request_size: usize,
+    #[pyo3(get)]
+    response_size: usize,
+    #[pyo3(get)]
+    pages: usize,
+    #[pyo3(get)]
+    queue_ms: f64,
+    #[pyo3(get)]
+    network_ms: f64,
+}
+
+#[pymethods]
+impl ExecutionInfo {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "ExecutionInfo(coordinator={}, coordinator_is_replica={:?}, attempts={}, retries={}, speculative_executions={}, latency_ms={:.3}, request_size={}, response_size={}, pages={}, queue_ms={:.3}, network_ms={:.3})",
+            self.coordinator,
+            self.coordinator_is_replica,
+            self.attempts,
+            self.retries,
+            self.speculative_executions,
+            self.latency_ms,
+            self.request_size,
+            self.response_size,
+            self.pages,
+            self.queue_ms,
+            self.network_ms
+        )
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(
+        Bound<'py, PyAny>,
+        (
+            String,
+            Option<bool>,
+            usize,
+            usize,
+            usize,
+            f64,
+            usize,
+            usize,
+            usize,
+            f64,
+            f64,
+        ),
+    )> {
+        let info = slf.borrow();
+        Ok((
+            slf.get_type().getattr("_from_snapshot")?,
+            (
+                info.coordinator.clone(),
+                info.coordinator_is_replica,
+                info.attempts,
+                info.retries,
+                info.speculative_executions,
+                info.latency_ms,
+                info.request_size,
+                info.response_size,
+                info.pages,
+                info.queue_ms,
+                info.network_ms,
+            ),
+        ))
+    }
+
+    #[staticmethod]
+    #[allow(clippy::too_many_arguments)]
+    pub fn _from_snapshot(
+        coordinator: String,
+        coordinator_is_replica: Option<bool>,
+        attempts: usize,
+        retries: usize,
+        speculative_executions: usize,
+        latency_ms: f64,
+        request_size: usize,
+        response_size: usize,
+        pages: usize,
+        queue_ms: f64,
+        network_ms: f64,
+    ) -> Self {
+        ExecutionInfo {
+            coordinator,
+            coordinator_is_replica,
+            attempts,
+            retries,
+            speculative_executions,
+            latency_ms,
+            request_size,
+            response_size,
+            pages,
+            queue_ms,
+            network_ms,
+        }
+    }
+}
+
+// Records the attempts of a single request through the driver's history listener
+pub struct ExecutionTracker {
+    history: Arc<HistoryCollector>,
+    started: Instant,
+    started_at: DateTime<Utc>,
+    request_size: usize,
+}
+
+impl ExecutionTracker {
+    pub fn new() -> Self {
+        ExecutionTracker {
+            history: Arc::new(HistoryCollector::new()),
+            started: Instant::now(),
+            started_at: Utc::now(),
+            request_size: 0,
+        }
+    }
+
+    pub fn listener(&self) -> Arc<dyn HistoryListener> {
+        self.history.clone()
+    }
+
+    // Size of the statement text or id and the values of a single request
+    pub fn set_request_size(&mut self, size: usize) {
+        self.request_size = size;
+    }
+}
+
+// Bound values that remember the size they were last serialized to
+pub struct MeasuredValues {
+    pub values: HashMap<String, SerializableValue>,
+    size: AtomicUsize,
+}
+
+impl MeasuredValues {
+    pub fn new(values: HashMap<String, SerializableValue>) -> Self {
+        MeasuredValues {
+            values,
+            size: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size.load(Ordering::Relaxed)
+    }
+}
+
+impl SerializeRow for MeasuredValues {
+    fn serialize(
+        &self,
+        ctx: &RowSerializationContext<'_>,
+        writer: &mut RowWriter,
+    ) -> Result<(), SerializationError> {
+        // Each value adds the size of its cell as it is written
+        self.size.store(0, Ordering::Relaxed);
+        let values: HashMap<&str, MeasuredValue<'_>> = self
+            .values
+            .iter()
+            .map(|(name, value)| {
+                let value = MeasuredValue {
+                    value,
+                    size: &self.size,
+                };
+                (name.as_str(), value)
+            })
+            .collect();
+        SerializeRow::serialize(&values, ctx, writer)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+struct MeasuredValue<'a> {
+    value: &'a SerializableValue,
+    size: &'a AtomicUsize,
+}
+
+impl SerializeValue for MeasuredValue<'_> {
+    fn serialize<'b>(
+        &self,
+        typ: &ColumnType,
+        writer: CellWriter<'b>,
+    ) -> Result<WrittenCellProof<'b>, SerializationError> {
+        // Byte strings are written straight into the request, their cell being the length
+        // followed by the bytes
+        let length = match self.value {
+            SerializableValue::Text(text) => Some(text.len()),
+            SerializableValue::Blob(blob) => Some(blob.len()),
+            SerializableValue::Buffer(buffer) => Some(buffer.len_bytes()),
+            _ => None,
+        };
+        if let Some(length) = length {
+            let proof = self.value.serialize(typ, writer)?;
+            self.size.fetch_add(4 + length, Ordering::Relaxed);
+            return Ok(proof);
+        }
+
+        // Other values are serialized on the side to be measured
+        let mut cell = Vec::new();
+        self.value.serialize(typ, CellWriter::new(&mut cell))?;
+        self.size.fetch_add(cell.len(), Ordering::Relaxed);
+        let (length, contents) = cell.split_at(4);
+        match i32::from_be_bytes(length.try_into().unwrap()) {
+            -1 => Ok(writer.set_null()),
+            -2 => Ok(writer.set_unset()),
+            _ => writer.set_value(contents).map_err(SerializationError::new),
+        }
+    }
+}
+
+// Outcome of execute_many, without keeping the individual results around
 #[pyclass]
+pub struct BulkResult {
+    #[pyo3(get)]
+    succeeded: usize,
+    #[pyo3(get)]
+    failed: usize,
+    // Rows returned by all successful statements
+    #[pyo3(get)]
+    rows: usize,
+    // (index in the parameter list, exception) for each failed statement
+    #[pyo3(get)]
+    errors: Py<PyList>,
+}
+
+impl BulkResult {
+    pub fn new(
+        py: Python,
+        succeeded: usize,
+        rows: usize,
+        errors: Vec<(usize, PyErr)>,
+    ) -> PyResult<Self> {
+        let failed = errors.len();
+        let errors = PyList::new(
+            py,
+            errors
+                .into_iter()
+                .map(|(index, err)| (index, err.into_value(py))),
+        )?;
+        Ok(BulkResult {
+            succeeded,
+            failed,
+            rows,
+            errors: errors.unbind(),
+        })
+    }
+}
+
+#[pymethods]
+impl BulkResult {
+    pub fn __bool__(&self) -> bool {
+        self.failed == 0
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "BulkResult(succeeded={}, failed={}, rows={})",
+            self.succeeded, self.failed, self.rows
+        )
+    }
+}
+
+// Rows received in one response, with the paging state to resume the request after them
+#[pyclass]
+pub struct Page {
+    #[pyo3(get)]
+    rows: Py<PyList>,
+    #[pyo3(get)]
+    paging_state: Option<Py<PyBytes>>,
+}
+
+#[pymethods]
+impl Page {
+    pub fn __len__(&self, py: Python) -> usize {
+        self.rows.bind(py).len()
+    }
+
+    pub fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        self.rows.bind(py).try_iter()
+    }
+
+    pub fn __repr__(&self, py: Python) -> String {
+        format!(
+            "Page(rows={}, has_more_pages={})",
+            self.rows.bind(py).len(),
+            self.paging_state.is_some()
+        )
+    }
+}
+
+#[derive(Clone)]
+enum ResultKind {
+    Rows,
+    Void,
+    SetKeyspace(String),
+    SchemaChange(SchemaChange),
+}
+
+#[pyclass(module = "rsylla._rsylla")]
 pub struct QueryResult {
-    // Store the rows result if available
-    rows_result: Option<QueryRowsResult>,
+    // Rows of every fetched page with the paging state following it, empty for results without
+    // rows
+    pages: Arc<[(QueryRowsResult, Option<Vec<u8>>)]>,
+    // Rows of an unpickled result, which has no pages
+    snapshot: Option<Vec<Row>>,
+    kind: ResultKind,
     tracing_id: Option<String>,
     warnings: Vec<String>,
-    current_row: usize,
+    execution_info: ExecutionInfo,
+    session: Option<Weak<ScyllaSession>>,
+    // Whether the session is the blocking Session, which get_tracing_info() then blocks for
+    blocking: bool,
+    // Shared by threads iterating the same result, each row is handed out once
+    current_row: AtomicUsize,
+    // Rows hold the serialized column values instead of deserializing them
+    raw: bool,
 }
 
 impl QueryResult {
     pub fn new(result: ScyllaQueryResult) -> Self {
+        Self::from_pages(vec![(result, None)])
+    }
+
+    // Result of a paged request, described by its first page
+    pub fn from_pages(pages: Vec<(ScyllaQueryResult, Option<Vec<u8>>)>) -> Self {
+        let result = &pages[0].0;
         let tracing_id = result.tracing_id().map(|id| id.to_string());
         let warnings: Vec<String> = result.warnings().map(|s| s.to_string()).collect();
-        let rows_result = result.into_rows_result().ok();
+        let mut execution_info = ExecutionInfo {
+            coordinator: result
+                .request_coordinator()
+                .connection_address()
+                .to_string(),
+            coordinator_is_replica: None,
+            attempts: 1,
+            retries: 0,
+            speculative_executions: 0,
+            latency_ms: 0.0,
+            request_size: 0,
+            response_size: 0,
+            pages: pages.len(),
+            queue_ms: 0.0,
+            network_ms: 0.0,
+        };
+        let pages: Vec<_> = pages
+            .into_iter()
+            .filter_map(|(page, state)| Some((page.into_rows_result().ok()?, state)))
+            .collect();
+        execution_info.response_size = pages.iter().map(|(page, _)| page.rows_bytes_size()).sum();
+        let kind = if pages.is_empty() {
+            ResultKind::Void
+        } else {
+            ResultKind::Rows
+        };
 
         QueryResult {
-            rows_result,
+            pages: pages.into(),
+            snapshot: None,
+            kind,
             tracing_id,
             warnings,
-            current_row: 0,
+            execution_info,
+            session: None,
+            blocking: false,
+            current_row: AtomicUsize::new(0),
+            raw: false,
         }
     }
-}
 
-#[pymethods]
-impl QueryResult {
-    pub fn rows(&self, py: Python) -> PyResult<Py<PyAny>> {
-        let py_list = PyList::empty(py);
+    // The driver consumes SET_KEYSPACE and SCHEMA_CHANGE responses, so they are told apart from
+    // VOID ones by the statement that was executed
+    pub fn with_statement(
+        mut self,
+        statement: &str,
+        keyspace: Option<&str>,
+        session: &ScyllaSession,
+    ) -> Self {
+        if matches!(self.kind, ResultKind::Void) {
+            let is_use = statement
+                .split_whitespace()
+                .next()
+                .is_some_and(|keyword| keyword.eq_ignore_ascii_case("USE"));
+            if is_use {
+                if let Some(keyspace) = session.get_keyspace() {
+                    self.kind = ResultKind::SetKeyspace(keyspace.to_string());
+                }
+            } else if let Some(change) = SchemaChange::from_statement(statement, keyspace) {
+                self.kind = ResultKind::SchemaChange(change);
+            }
+        }
+        self
+    }
+
+    fn column_names(&self) -> Arc<[String]> {
+        match self.pages.first() {
+            Some((page, _)) => column_names(page.column_specs()),
+            None => Arc::from([]),
+        }
+    }
 
-        if let Some(ref rows_result) = self.rows_result {
-            let rows: Vec<ScyllaRow> = rows_result
-                .rows()
-                .map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Row deserialization error: {}",
-                        e
-                    ))
-                })?
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Row deserialization error: {}",
-                        e
-                    ))
-                })?;
+    fn page_rows<'a>(
+        &self,
+        page: &'a QueryRowsResult,
+        names: &'a Arc<[String]>,
+    ) -> PyResult<Box<dyn Iterator<Item = PyResult<Row>> + 'a>> {
+        page_rows(page, names, self.raw)
+    }
+
+    fn all_rows(&self) -> PyResult<Vec<Row>> {
+        if let Some(rows) = &self.snapshot {
+            return Ok(rows.clone());
+        }
+        let names = self.column_names();
+        let mut rows = Vec::new();
+        for (page, _) in self.pages.iter() {
+            for row in self.page_rows(page, &names)? {
+                rows.push(row?);
+            }
+        }
+        Ok(rows)
+    }
+
+    fn first(&self) -> PyResult<Option<Row>> {
+        if let Some(rows) = &self.snapshot {
+            return Ok(rows.first().cloned());
+        }
+        let names = self.column_names();
+        for (page, _) in self.pages.iter() {
+            if let Some(row) = self.page_rows(page, &names)?.next() {
+                return row.map(Some);
+            }
+        }
+        Ok(None)
+    }
 
+    // Every row converted straight to a dict or tuple, without creating Row objects
+    fn convert_all<'py>(&self, py: Python<'py>, as_dict: bool) -> PyResult<Bound<'py, PyList>> {
+        let converted = PyList::empty(py);
+        let names = match &self.snapshot {
+            Some(rows) => rows.first().map_or(Arc::from([]), |row| row.names.clone()),
+            None => self.column_names(),
+        };
+        let keys: Vec<_> = names
+            .iter()
+            .map(|name| PyString::intern(py, name))
+            .collect();
+        let append = |values: Vec<Py<PyAny>>| -> PyResult<()> {
+            if !as_dict {
+                return converted.append(PyTuple::new(py, values)?);
+            }
+            let dict = PyDict::new(py);
+            for (key, value) in keys.iter().zip(values) {
+                dict.set_item(key, value)?;
+            }
+            converted.append(dict)
+        };
+        if let Some(rows) = &self.snapshot {
             for row in rows {
-                let py_row = Py::new(py, Row::new(&row))?;
-                py_list.append(py_row)?;
+                append(row.values(py)?.into_iter().map(Bound::unbind).collect())?;
             }
+            return Ok(converted);
         }
+        for (page, _) in self.pages.iter() {
+            if self.raw {
+                for row in self.page_rows(page, &names)? {
+                    append(row?.values(py)?.into_iter().map(Bound::unbind).collect())?;
+                }
+                continue;
+            }
+            let rows = page
+                .rows::<ScyllaRow>()
+                .map_err(deserialization_error_to_py)?;
+            for row in rows {
+                let row = row.map_err(deserialization_error_to_py)?;
+                let values = row
+                    .columns
+                    .iter()
+                    .map(|column| match column {
+                        Some(value) => cql_value_to_py(py, value),
+                        None => Ok(py.None()),
+                    })
+                    .collect::<PyResult<_>>()?;
+                append(values)?;
+            }
+        }
+        Ok(converted)
+    }
 
-        Ok(py_list.into())
+    // Keyspace and table the rows were selected from
+    pub(crate) fn selected_table(&self) -> Option<(String, String)> {
+        let (page, _) = self.pages.first()?;
+        let table_spec = page.column_specs().get_by_index(0)?.table_spec();
+        Some((
+            table_spec.ks_name().to_string(),
+            table_spec.table_name().to_string(),
+        ))
     }
 
-    pub fn first_row(&self) -> PyResult<Option<Row>> {
-        if let Some(ref rows_result) = self.rows_result {
-            let mut rows_iter = rows_result.rows().map_err(|e| {
-                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                    "Row deserialization error: {}",
-                    e
-                ))
-            })?;
+    // Documents of a SELECT JSON result
+    pub(crate) fn json_documents(&self) -> PyResult<Vec<Option<String>>> {
+        let mut documents = Vec::new();
+        for (page, _) in self.pages.iter() {
+            let rows = page
+                .rows::<(Option<String>,)>()
+                .map_err(deserialization_error_to_py)?;
+            for row in rows {
+                documents.push(row.map_err(deserialization_error_to_py)?.0);
+            }
+        }
+        Ok(documents)
+    }
+
+    pub fn with_raw(mut self) -> Self {
+        self.raw = true;
+        self
+    }
+
+    pub fn with_session(mut self, session: Arc<ScyllaSession>, blocking: bool) -> Self {
+        self.session = Some(Arc::downgrade(&session));
+        self.blocking = blocking;
+        self
+    }
 
-            if let Some(row_result) = rows_iter.next() {
-                let row: ScyllaRow = row_result.map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Row deserialization error: {}",
-                        e
-                    ))
-                })?;
-                Ok(Some(Row::new(&row)))
-            } else {
-                Ok(None)
+    pub fn with_tracker(mut self, tracker: ExecutionTracker) -> Self {
+        let info = &mut self.execution_info;
+        info.latency_ms = tracker.started.elapsed().as_secs_f64() * 1000.0;
+        info.request_size = tracker.request_size;
+        let history = tracker.history.take_structured_history();
+        // Time until the first attempt was sent is spent queued in the driver, the rest of each
+        // page's request waiting on the network and the coordinator
+        let mut first_sent = None;
+        info.network_ms = 0.0;
+        for request in &history.requests {
+            let Some(sent) = request
+                .non_speculative_fiber
+                .attempts
+                .first()
+                .map(|attempt| attempt.send_time)
+            else {
+                continue;
+            };
+            first_sent.get_or_insert(sent);
+            let finished = match &request.result {
+                Some(RequestHistoryResult::Success(time)) => *time,
+                Some(RequestHistoryResult::Error(time, _)) => *time,
+                None => continue,
+            };
+            info.network_ms += millis_between(sent, finished);
+        }
+        info.queue_ms = first_sent.map_or(0.0, |sent| millis_between(tracker.started_at, sent));
+        if let Some(request) = history.requests.first() {
+            let fibers =
+                std::iter::once(&request.non_speculative_fiber).chain(&request.speculative_fibers);
+            info.attempts = 0;
+            info.retries = 0;
+            for fiber in fibers {
+                info.attempts += fiber.attempts.len();
+                info.retries += fiber.attempts.len().saturating_sub(1);
             }
-        } else {
-            Ok(None)
+            info.speculative_executions = request.speculative_fibers.len();
         }
+        self
+    }
+
+    pub fn with_coordinator_is_replica(mut self, is_replica: Option<bool>) -> Self {
+        self.execution_info.coordinator_is_replica = is_replica;
+        self
+    }
+}
+
+#[pymethods]
+impl QueryResult {
+    pub fn rows(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let py_list = PyList::empty(py);
+        for row in self.all_rows()? {
+            py_list.append(Py::new(py, row)?)?;
+        }
+
+        Ok(py_list.into())
+    }
+
+    pub fn first_row(&self) -> PyResult<Option<Row>> {
+        self.first()
     }
 
     pub fn single_row(&self) -> PyResult<Row> {
-        if let Some(ref rows_result) = self.rows_result {
-            let rows: Vec<ScyllaRow> = rows_result
-                .rows()
-                .map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Row deserialization error: {}",
-                        e
-                    ))
-                })?
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Row deserialization error: {}",
-                        e
-                    ))
-                })?;
-
-            if rows.len() == 1 {
-                Ok(Row::new(&rows[0]))
-            } else if rows.is_empty() {
-                Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                    "No rows returned",
-                ))
-            } else {
-                Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                    "Expected single row, got {} rows",
-                    rows.len()
-                )))
-            }
-        } else {
+        let mut rows = self.all_rows()?;
+        if rows.len() == 1 {
+            Ok(rows.remove(0))
+        } else if rows.is_empty() {
             Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
                 "No rows returned",
             ))
+        } else {
+            Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Expected single row, got {} rows",
+                rows.len()
+            )))
+        }
+    }
+
+    // The only row, raising unless there is exactly one
+    pub fn one(&self) -> PyResult<Row> {
+        let len = self.__len__();
+        if len > 1 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Expected single row, got {} rows",
+                len
+            )));
+        }
+        self.first()?
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("No rows returned"))
+    }
+
+    // First column of the first row, None without rows
+    pub fn scalar(&self, py: Python) -> PyResult<Py<PyAny>> {
+        match self.first()? {
+            Some(row) if row.len() > 0 => row.value(py, 0),
+            _ => Ok(py.None()),
         }
     }
 
     pub fn first_row_typed(&self, py: Python) -> PyResult<Option<Py<PyAny>>> {
-        if let Some(ref rows_result) = self.rows_result {
-            let mut rows_iter = rows_result.rows().map_err(|e| {
-                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                    "Row deserialization error: {}",
-                    e
-                ))
-            })?;
+        match self.first()? {
+            Some(row) => Ok(Some(row.as_dict(py)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn rows_typed<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        self.convert_all(py, true)
+    }
+
+    // Rows deserialized on a worker thread while they are consumed
+    #[pyo3(signature = (buffer=10000))]
+    pub fn stream(&self, buffer: usize) -> RowStream {
+        match &self.snapshot {
+            Some(rows) => RowStream::from_rows(rows.clone()),
+            None => RowStream::start(self.pages.clone(), self.column_names(), self.raw, buffer),
+        }
+    }
+
+    // Every row as a dict, or a tuple, converted in a single pass
+    #[pyo3(signature = (as_dict=true))]
+    pub fn all<'py>(&self, py: Python<'py>, as_dict: bool) -> PyResult<Bound<'py, PyList>> {
+        self.convert_all(py, as_dict)
+    }
 
-            if let Some(row_result) = rows_iter.next() {
-                let row: ScyllaRow = row_result.map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Row deserialization error: {}",
-                        e
-                    ))
-                })?;
-                let py_row = Row::new(&row);
-                Ok(Some(py_row.as_dict(py)?))
-            } else {
-                Ok(None)
+    // Whether a conditional statement was applied, read from the [applied] column of its result
+    #[getter]
+    pub fn was_applied(&self, py: Python) -> PyResult<bool> {
+        let applied = self.first()?.and_then(|row| {
+            let index = row.position(APPLIED_COLUMN)?;
+            Some(row.value(py, index))
+        });
+        match applied {
+            Some(applied) => applied?.extract(py),
+            None => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "The result has no [applied] column, the statement is not conditional",
+            )),
+        }
+    }
+
+    // Current values reported by the server when the condition of a statement failed
+    pub fn existing<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        if self.was_applied(py)? {
+            return Ok(None);
+        }
+        let Some(row) = self.first()? else {
+            return Ok(None);
+        };
+        let existing = PyDict::new(py);
+        for (index, name) in row.names.iter().enumerate() {
+            if name != APPLIED_COLUMN {
+                existing.set_item(name, row.value(py, index)?)?;
             }
-        } else {
-            Ok(None)
-        }
-    }
-
-    pub fn rows_typed(&self, py: Python) -> PyResult<Vec<Py<PyAny>>> {
-        let mut result = Vec::new();
-
-        if let Some(ref rows_result) = self.rows_result {
-            let rows: Vec<ScyllaRow> = rows_result
-                .rows()
-                .map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Row deserialization error: {}",
-                        e
-                    ))
-                })?
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Row deserialization error: {}",
-                        e
-                    ))
-                })?;
+        }
+        Ok(Some(existing))
+    }
 
-            for row in rows {
-                let py_row = Row::new(&row);
-                result.push(py_row.as_dict(py)?);
+    pub fn pages<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        let names = self.column_names();
+        let pages = PyList::empty(py);
+        if let Some(rows) = &self.snapshot {
+            let rows = PyList::new(py, rows.clone())?.unbind();
+            pages.append(Page {
+                rows,
+                paging_state: None,
+            })?;
+        }
+        for (page, paging_state) in self.pages.iter() {
+            let rows = PyList::empty(py);
+            for row in self.page_rows(page, &names)? {
+                rows.append(row?)?;
             }
+            let page = Page {
+                rows: rows.unbind(),
+                paging_state: paging_state
+                    .as_ref()
+                    .map(|state| PyBytes::new(py, state).unbind()),
+            };
+            pages.append(page)?;
         }
+        pages.try_iter()
+    }
 
-        Ok(result)
+    // rows, void, set_keyspace or schema_change
+    #[getter]
+    pub fn kind(&self) -> &'static str {
+        match self.kind {
+            ResultKind::Rows => "rows",
+            ResultKind::Void => "void",
+            ResultKind::SetKeyspace(_) => "set_keyspace",
+            ResultKind::SchemaChange(_) => "schema_change",
+        }
     }
 
-    pub fn col_specs(&self, py: Python) -> PyResult<Py<PyAny>> {
-        let py_list = PyList::empty(py);
+    #[getter]
+    pub fn set_keyspace(&self) -> Option<String> {
+        match &self.kind {
+            ResultKind::SetKeyspace(keyspace) => Some(keyspace.clone()),
+            _ => None,
+        }
+    }
 
-        if let Some(ref rows_result) = self.rows_result {
-            let specs = rows_result.column_specs();
-            for spec in specs.iter() {
-                let dict = PyDict::new(py);
-                dict.set_item("table_spec", format!("{:?}", spec.table_spec()))?;
-                dict.set_item("name", spec.name().to_string())?;
-                dict.set_item("typ", format!("{:?}", spec.typ()))?;
-                py_list.append(dict)?;
-            }
+    #[getter]
+    pub fn schema_change(&self) -> Option<SchemaChange> {
+        match &self.kind {
+            ResultKind::SchemaChange(change) => Some(change.clone()),
+            _ => None,
         }
+    }
 
-        Ok(py_list.into())
+    pub fn col_specs(&self) -> Vec<ColumnSpec> {
+        self.pages
+            .first()
+            .map(|(rows_result, _)| {
+                rows_result
+                    .column_specs()
+                    .iter()
+                    .map(ColumnSpec::from)
+                    .collect()
+            })
+            .unwrap_or_default()
     }
 
     pub fn tracing_id(&self) -> Option<String> {
@@ -202,91 +797,286 @@ impl QueryResult {
         self.warnings.clone()
     }
 
+    pub fn get_tracing_info<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let tracing_id = self
+            .tracing_id
+            .as_ref()
+            .and_then(|id| id.parse::<uuid::Uuid>().ok())
+            .ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "Tracing was not enabled for this request",
+                )
+            })?;
+        let session = self
+            .session
+            .as_ref()
+            .ok_or_else(|| PyErr::new::<ScyllaError, _>("Result is not bound to a session"))?
+            .upgrade()
+            .ok_or_else(|| PyErr::new::<ScyllaError, _>("Session is closed"))?;
+
+        run_request(py, self.blocking, async move {
+            let info = session
+                .get_tracing_info(&tracing_id)
+                .await
+                .map_err(tracing_error_to_py)?;
+
+            Ok(TracingInfo::from(info))
+        })
+    }
+
+    pub fn execution_info(&self) -> ExecutionInfo {
+        self.execution_info.clone()
+    }
+
     pub fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
         slf
     }
 
-    pub fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<Row> {
-        if let Some(ref rows_result) = slf.rows_result {
-            if let Ok(rows) = rows_result.rows::<ScyllaRow>() {
-                let rows_vec: Vec<ScyllaRow> = rows.filter_map(|r| r.ok()).collect();
-                if slf.current_row < rows_vec.len() {
-                    let row = Row::new(&rows_vec[slf.current_row]);
-                    slf.current_row += 1;
-                    return Some(row);
-                }
+    pub fn __next__(&self) -> Option<Row> {
+        let len = self.__len__();
+        let mut index = self
+            .current_row
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |index| {
+                (index < len).then_some(index + 1)
+            })
+            .ok()?;
+        if let Some(rows) = &self.snapshot {
+            return rows.get(index).cloned();
+        }
+        for (page, _) in self.pages.iter() {
+            if index < page.rows_num() {
+                let names = column_names(page.column_specs());
+                return self.page_rows(page, &names).ok()?.nth(index)?.ok();
             }
+            index -= page.rows_num();
         }
         None
     }
 
     pub fn __len__(&self) -> usize {
-        if let Some(ref rows_result) = self.rows_result {
-            rows_result.rows_num()
-        } else {
-            0
+        match &self.snapshot {
+            Some(rows) => rows.len(),
+            None => self.pages.iter().map(|(page, _)| page.rows_num()).sum(),
         }
     }
 
     pub fn __bool__(&self) -> bool {
-        if let Some(ref rows_result) = self.rows_result {
-            rows_result.rows_num() > 0
-        } else {
-            false
+        self.__len__() > 0
+    }
+
+    // Pickled as a snapshot of its rows and execution details, without column specs and session
+    #[allow(clippy::type_complexity)]
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyTuple>)> {
+        let result = slf.borrow();
+        let args = (
+            result.all_rows()?,
+            result.tracing_id.clone(),
+            result.warnings.clone(),
+            result.execution_info.clone(),
+            result.kind(),
+            result.set_keyspace(),
+            result.schema_change(),
+        )
+            .into_pyobject(slf.py())?;
+        Ok((slf.get_type().getattr("_from_snapshot")?, args))
+    }
+
+    #[staticmethod]
+    pub fn _from_snapshot(
+        rows: Vec<Row>,
+        tracing_id: Option<String>,
+        warnings: Vec<String>,
+        execution_info: ExecutionInfo,
+        kind: &str,
+        set_keyspace: Option<String>,
+        schema_change: Option<SchemaChange>,
+    ) -> Self {
+        let kind = match (kind, set_keyspace, schema_change) {
+            ("set_keyspace", Some(keyspace), _) => ResultKind::SetKeyspace(keyspace),
+            ("schema_change", _, Some(change)) => ResultKind::SchemaChange(change),
+            ("void", _, _) => ResultKind::Void,
+            _ => ResultKind::Rows,
+        };
+        QueryResult {
+            pages: Arc::from([]),
+            snapshot: Some(rows),
+            kind,
+            tracing_id,
+            warnings,
+            execution_info,
+            session: None,
+            blocking: false,
+            current_row: AtomicUsize::new(0),
+            raw: false,
         }
     }
 }
 
-#[pyclass]
+fn millis_between(start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+    (end - start)
+        .num_microseconds()
+        .map_or(0.0, |micros| micros.max(0) as f64 / 1000.0)
+}
+
+pub fn column_names(specs: ColumnSpecs) -> Arc<[String]> {
+    specs.iter().map(|spec| spec.name().to_string()).collect()
+}
+
+const APPLIED_COLUMN: &str = "[applied]";
+
+// Deserializes the rows of a page, or keeps the serialized bytes of their columns in raw mode
+pub(crate) fn page_rows<'a>(
+    page: &'a QueryRowsResult,
+    names: &'a Arc<[String]>,
+    raw: bool,
+) -> PyResult<Box<dyn Iterator<Item = PyResult<Row>> + 'a>> {
+    if raw {
+        let rows = page
+            .rows::<ColumnIterator>()
+            .map_err(deserialization_error_to_py)?;
+        return Ok(Box::new(rows.map(move |row| {
+            Row::from_columns(row.map_err(deserialization_error_to_py)?, names)
+        })));
+    }
+    let rows = page
+        .rows::<ScyllaRow>()
+        .map_err(deserialization_error_to_py)?;
+    Ok(Box::new(rows.map(move |row| {
+        Ok(Row::from_row(
+            row.map_err(deserialization_error_to_py)?,
+            names,
+        ))
+    })))
+}
+
+// Values shown by Row.__repr__ are cut to this many characters
+const REPR_VALUE_WIDTH: usize = 40;
+
+// Values are converted to Python on access, except for unpickled rows which already hold them
+#[derive(Clone)]
+enum Columns {
+    Cql(Vec<Option<CqlValue>>),
+    Python(Arc<[Py<PyAny>]>),
+    Raw(Vec<Option<Vec<u8>>>),
+}
+
+#[pyclass(frozen, module = "rsylla._rsylla")]
 #[derive(Clone)]
 pub struct Row {
-    columns: Vec<Option<CqlValue>>,
+    columns: Columns,
+    // Shared by all rows of a result
+    names: Arc<[String]>,
 }
 
 impl Row {
-    pub fn new(row: &ScyllaRow) -> Self {
+    pub fn new(row: &ScyllaRow, names: &Arc<[String]>) -> Self {
         Row {
-            columns: row.columns.clone(),
+            columns: Columns::Cql(row.columns.clone()),
+            names: names.clone(),
         }
     }
+
+    fn from_row(row: ScyllaRow, names: &Arc<[String]>) -> Self {
+        Row {
+            columns: Columns::Cql(row.columns),
+            names: names.clone(),
+        }
+    }
+
+    // Keeps the serialized bytes of each column
+    fn from_columns(columns: ColumnIterator, names: &Arc<[String]>) -> PyResult<Self> {
+        let columns = columns
+            .map(|column| {
+                let column = column.map_err(deserialization_error_to_py)?;
+                Ok(column.slice.map(|slice| slice.as_slice().to_vec()))
+            })
+            .collect::<PyResult<_>>()?;
+        Ok(Row {
+            columns: Columns::Raw(columns),
+            names: names.clone(),
+        })
+    }
+
+    fn len(&self) -> usize {
+        match &self.columns {
+            Columns::Cql(columns) => columns.len(),
+            Columns::Python(columns) => columns.len(),
+            Columns::Raw(columns) => columns.len(),
+        }
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|column| column == name)
+    }
+
+    fn value(&self, py: Python, index: usize) -> PyResult<Py<PyAny>> {
+        match &self.columns {
+            Columns::Cql(columns) => match &columns[index] {
+                Some(val) => cql_value_to_py(py, val),
+                None => Ok(py.None()),
+            },
+            Columns::Python(columns) => Ok(columns[index].clone_ref(py)),
+            Columns::Raw(columns) => match &columns[index] {
+                Some(bytes) => Ok(PyBytes::new(py, bytes).into()),
+                None => Ok(py.None()),
+            },
+        }
+    }
+
+    fn values<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyAny>>> {
+        (0..self.len())
+            .map(|index| Ok(self.value(py, index)?.into_bound(py)))
+            .collect()
+    }
+
+    // Value of the `function(column)` selector, matched like the server names unaliased ones
+    fn selector(&self, py: Python, function: &str, column: &str) -> PyResult<Py<PyAny>> {
+        let name = format!("{}({})", function, column);
+        let index = self
+            .position(&name)
+            .or_else(|| {
+                self.names
+                    .iter()
+                    .position(|column| column.eq_ignore_ascii_case(&name))
+            })
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyKeyError, _>(name))?;
+        self.value(py, index)
+    }
 }
 
 #[pymethods]
 impl Row {
     pub fn columns(&self, py: Python) -> PyResult<Py<PyAny>> {
-        let py_list = PyList::empty(py);
-        for column in &self.columns {
-            let value = match column {
-                Some(val) => cql_value_to_py(py, val)?,
-                None => py.None(),
-            };
-            py_list.append(value)?;
-        }
-        Ok(py_list.into())
+        Ok(PyList::new(py, self.values(py)?)?.into())
     }
 
     pub fn as_dict(&self, py: Python) -> PyResult<Py<PyAny>> {
         let dict = PyDict::new(py);
-
-        // Note: In a real implementation, you'd need column names from the result metadata
-        // For now, we'll use indices as keys
-        for (i, column) in self.columns.iter().enumerate() {
-            let value = match column {
-                Some(val) => cql_value_to_py(py, val)?,
-                None => py.None(),
-            };
-            dict.set_item(format!("col_{}", i), value)?;
+        for (name, value) in self.names.iter().zip(self.values(py)?) {
+            dict.set_item(name, value)?;
         }
-
         Ok(dict.into())
     }
 
-    pub fn get(&self, py: Python, index: usize) -> PyResult<Py<PyAny>> {
-        if index < self.columns.len() {
-            match &self.columns[index] {
-                Some(val) => cql_value_to_py(py, val),
-                None => Ok(py.None()),
-            }
+    // Column names are looked up like dictionary keys, indexes are positional
+    #[pyo3(signature = (key, default=None))]
+    pub fn get(
+        &self,
+        py: Python,
+        key: &Bound<'_, PyAny>,
+        default: Option<Py<PyAny>>,
+    ) -> PyResult<Py<PyAny>> {
+        if let Ok(name) = key.extract::<&str>() {
+            return match self.position(name) {
+                Some(index) => self.value(py, index),
+                None => Ok(default.unwrap_or_else(|| py.None())),
+            };
+        }
+        let index = key.extract::<usize>()?;
+        if index < self.len() {
+            self.value(py, index)
         } else {
             Err(PyErr::new::<pyo3::exceptions::PyIndexError, _>(format!(
                 "Column index {} out of range",
@@ -295,23 +1085,75 @@ impl Row {
         }
     }
 
+    pub fn keys(&self) -> Vec<String> {
+        self.names.to_vec()
+    }
+
+    // Write timestamps are microseconds since epoch, returned as an aware UTC datetime
+    pub fn writetime(&self, py: Python, column: &str) -> PyResult<Py<PyAny>> {
+        let value = self.selector(py, "writetime", column)?;
+        let Some(micros) = value.extract::<Option<i64>>(py)? else {
+            return Ok(py.None());
+        };
+        let datetime = py.import("datetime")?;
+        let epoch = datetime.getattr("datetime")?.call1((
+            1970,
+            1,
+            1,
+            0,
+            0,
+            0,
+            0,
+            datetime.getattr("timezone")?.getattr("utc")?,
+        ))?;
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("microseconds", micros)?;
+        let offset = datetime.getattr("timedelta")?.call((), Some(&kwargs))?;
+        Ok(epoch.add(offset)?.unbind())
+    }
+
+    // Remaining time to live in seconds, None for data that doesn't expire
+    pub fn ttl(&self, py: Python, column: &str) -> PyResult<Option<i64>> {
+        self.selector(py, "ttl", column)?.extract(py)
+    }
+
+    #[pyo3(name = "values")]
+    pub fn py_values<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyAny>>> {
+        self.values(py)
+    }
+
+    pub fn items<'py>(&self, py: Python<'py>) -> PyResult<Vec<(String, Bound<'py, PyAny>)>> {
+        Ok(self.names.iter().cloned().zip(self.values(py)?).collect())
+    }
+
+    pub fn __contains__(&self, key: &Bound<'_, PyAny>) -> bool {
+        key.extract::<&str>()
+            .is_ok_and(|name| self.position(name).is_some())
+    }
+
     pub fn __len__(&self) -> usize {
-        self.columns.len()
+        self.len()
     }
 
-    pub fn __getitem__(&self, py: Python, index: isize) -> PyResult<Py<PyAny>> {
-        let len = self.columns.len() as isize;
+    pub fn __getitem__(&self, py: Python, key: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        if let Ok(name) = key.extract::<&str>() {
+            return match self.position(name) {
+                Some(index) => self.value(py, index),
+                None => Err(PyErr::new::<pyo3::exceptions::PyKeyError, _>(
+                    name.to_string(),
+                )),
+            };
+        }
+        let index = key.extract::<isize>()?;
+        let len = self.len() as isize;
         let idx = if index < 0 {
             (len + index) as usize
         } else {
             index as usize
         };
 
-        if idx < self.columns.len() {
-            match &self.columns[idx] {
-                Some(val) => cql_value_to_py(py, val),
-                None => Ok(py.None()),
-            }
+        if idx < self.len() {
+            self.value(py, idx)
         } else {
             Err(PyErr::new::<pyo3::exceptions::PyIndexError, _>(format!(
                 "Column index {} out of range",
@@ -320,7 +1162,92 @@ impl Row {
         }
     }
 
-    pub fn __repr__(&self) -> String {
-        format!("Row(columns={})", self.columns.len())
+    pub fn __eq__(&self, py: Python, other: &Self) -> PyResult<bool> {
+        if self.names != other.names {
+            return Ok(false);
+        }
+        match (&self.columns, &other.columns) {
+            (Columns::Cql(columns), Columns::Cql(other)) => Ok(columns == other),
+            _ => PyList::new(py, self.values(py)?)?.eq(PyList::new(py, other.values(py)?)?),
+        }
+    }
+
+    // Consistent with __eq__, and like for tuples fails for collection values
+    pub fn __hash__(&self, py: Python) -> PyResult<isize> {
+        PyTuple::new(py, self.values(py)?)?.hash()
+    }
+
+    pub fn __repr__(&self, py: Python) -> PyResult<String> {
+        let mut fields = Vec::with_capacity(self.len());
+        for (name, value) in self.names.iter().zip(self.values(py)?) {
+            let mut value = value.repr()?.to_string();
+            if value.chars().count() > REPR_VALUE_WIDTH {
+                value = value.chars().take(REPR_VALUE_WIDTH - 3).collect::<String>() + "...";
+            }
+            fields.push(format!("{}={}", name, value));
+        }
+        Ok(format!("Row({})", fields.join(", ")))
+    }
+
+    // Pickled as column names and plain Python values
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyTuple>)> {
+        let row = slf.get();
+        let args = (row.keys(), row.values(slf.py())?).into_pyobject(slf.py())?;
+        Ok((slf.get_type().getattr("_from_snapshot")?, args))
+    }
+
+    #[staticmethod]
+    pub fn _from_snapshot(names: Vec<String>, values: Vec<Py<PyAny>>) -> PyResult<Self> {
+        if names.len() != values.len() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "A row needs one value per column name",
+            ));
+        }
+        Ok(Row {
+            columns: Columns::Python(values.into()),
+            names: names.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scylla::frame::response::result::{ColumnSpec, NativeType, TableSpec};
+
+    #[test]
+    fn measured_size_is_the_written_size() {
+        let table = TableSpec::borrowed("ks", "t");
+        let specs = [
+            ColumnSpec::borrowed("id", ColumnType::Native(NativeType::Int), table.clone()),
+            ColumnSpec::borrowed("name", ColumnType::Native(NativeType::Text), table.clone()),
+            ColumnSpec::borrowed("data", ColumnType::Native(NativeType::Blob), table.clone()),
+            ColumnSpec::borrowed("note", ColumnType::Native(NativeType::Text), table),
+        ];
+        let values = MeasuredValues::new(HashMap::from([
+            ("id".to_string(), SerializableValue::Int(7)),
+            (
+                "name".to_string(),
+                SerializableValue::Text("rsylla".to_string()),
+            ),
+            ("data".to_string(), SerializableValue::Blob(vec![1; 1000])),
+            ("note".to_string(), SerializableValue::Null),
+        ]));
+
+        let mut buffer = Vec::new();
+        let mut writer = RowWriter::new(&mut buffer);
+        let ctx = RowSerializationContext::from_specs(&specs);
+        values.serialize(&ctx, &mut writer).unwrap();
+        assert_eq!(writer.value_count(), 4);
+        assert_eq!(values.size(), buffer.len());
+        assert_eq!(values.size(), 8 + 10 + 1004 + 4);
+
+        // Serializing again, e.g. for a retry, doesn't add up
+        values
+            .serialize(&ctx, &mut RowWriter::new(&mut Vec::new()))
+            .unwrap();
+        assert_eq!(values.size(), 1026);
     }
 }
@@ -0,0 +1,324 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyIterator};
+use scylla::client::session::Session as ScyllaSession;
+use scylla::cluster::{ClusterState, NodeRef};
+use scylla::errors::RequestAttemptError;
+use scylla::frame::response::result::TableSpec;
+use scylla::policies::load_balancing::{FallbackPlan, LoadBalancingPolicy, RoutingInfo};
+use scylla::response::query_result::QueryResult as ScyllaQueryResult;
+use scylla::routing::partitioner::{
+    CDCPartitioner, Murmur3Partitioner, Partitioner, PartitionerHasher,
+};
+use scylla::routing::{Shard, ShardCount, Sharder, Token};
+use scylla::serialize::value::SerializeValue;
+use scylla::serialize::writers::CellWriter;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cluster::HostInfo;
+use crate::error::serialization_error_to_py;
+use crate::metadata::TableMetadata;
+use crate::types::{py_dict_to_typed_values, EnumBinding};
+
+// Routing information supplied explicitly for statements the driver can't analyze itself
+#[derive(Clone, Debug)]
+pub struct RoutingKey {
+    pub table: TableSpec<'static>,
+    pub token: Token,
+}
+
+impl RoutingKey {
+    pub fn new(keyspace: &str, table: &str, routing_key: &[u8]) -> Self {
+        RoutingKey {
+            table: TableSpec::owned(keyspace.to_string(), table.to_string()),
+            token: Murmur3Partitioner.hash_one(routing_key),
+        }
+    }
+}
+
+// Wraps the session's policy, feeding it the token computed from an explicit routing key
+#[derive(Debug)]
+pub struct RoutingKeyPolicy {
+    inner: Arc<dyn LoadBalancingPolicy>,
+    routing_key: RoutingKey,
+}
+
+impl RoutingKeyPolicy {
+    pub fn new(inner: Arc<dyn LoadBalancingPolicy>, routing_key: RoutingKey) -> Self {
+        RoutingKeyPolicy { inner, routing_key }
+    }
+
+    fn routing_info<'a>(&'a self, request: &RoutingInfo<'a>) -> RoutingInfo<'a> {
+        let mut info = request.clone();
+        info.token = Some(self.routing_key.token);
+        info.table = Some(&self.routing_key.table);
+        info
+    }
+}
+
+impl LoadBalancingPolicy for RoutingKeyPolicy {
+    fn pick<'a>(
+        &'a self,
+        request: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> Option<(NodeRef<'a>, Option<Shard>)> {
+        let info = self.routing_info(request);
+        let (node, shard) = self.inner.pick(&info, cluster)?;
+        // Re-borrow the node from the cluster state so it outlives the local routing info
+        cluster
+            .get_node_by_host_id(node.host_id)
+            .map(|node| (node, shard))
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        request: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> FallbackPlan<'a> {
+        let info = self.routing_info(request);
+        let plan: Vec<_> = self
+            .inner
+            .fallback(&info, cluster)
+            .map(|(node, shard)| (node.host_id, shard))
+            .collect();
+        Box::new(plan.into_iter().filter_map(move |(host_id, shard)| {
+            cluster
+                .get_node_by_host_id(host_id)
+                .map(|node| (node, shard))
+        }))
+    }
+
+    fn on_request_success(&self, request: &RoutingInfo, latency: Duration, node: NodeRef<'_>) {
+        self.inner
+            .on_request_success(&self.routing_info(request), latency, node);
+    }
+
+    fn on_request_failure(
+        &self,
+        request: &RoutingInfo,
+        latency: Duration,
+        node: NodeRef<'_>,
+        error: &RequestAttemptError,
+    ) {
+        self.inner
+            .on_request_failure(&self.routing_info(request), latency, node, error);
+    }
+
+    fn name(&self) -> String {
+        format!("RoutingKeyPolicy({})", self.inner.name())
+    }
+}
+
+// Returns None when the token or table of the request is unknown, or when the replicas of a
+// tablet table aren't known yet
+pub fn coordinator_is_replica(
+    session: &ScyllaSession,
+    table: Option<&TableSpec>,
+    token: Option<Token>,
+    result: &ScyllaQueryResult,
+) -> Option<bool> {
+    let (table, token) = (table?, token?);
+    let coordinator = result.request_coordinator().node().host_id;
+    let replicas =
+        session
+            .get_cluster_state()
+            .get_token_endpoints(table.ks_name(), table.table_name(), token);
+    if replicas.is_empty() {
+        return None;
+    }
+    Some(replicas.iter().any(|(node, _)| node.host_id == coordinator))
+}
+
+// Range of tokens (start, end] owned by the same replicas
+#[pyclass]
+#[derive(Clone)]
+pub struct TokenRange {
+    #[pyo3(get)]
+    pub(crate) start: i64,
+    #[pyo3(get)]
+    pub(crate) end: i64,
+    #[pyo3(get)]
+    replicas: Vec<HostInfo>,
+}
+
+#[pymethods]
+impl TokenRange {
+    pub fn contains(&self, token: i64) -> bool {
+        if self.start < self.end {
+            self.start < token && token <= self.end
+        } else {
+            // The range wraps around the end of the ring
+            token > self.start || token <= self.end
+        }
+    }
+
+    // Unpacks as (start, end, replicas)
+    pub fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        (self.start, self.end, self.replicas.clone())
+            .into_pyobject(py)?
+            .try_iter()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "TokenRange(start={}, end={}, replicas={})",
+            self.start,
+            self.end,
+            self.replicas.len()
+        )
+    }
+}
+
+pub fn replicas_for_token(
+    session: &ScyllaSession,
+    keyspace: &str,
+    table: &str,
+    token: Token,
+) -> Vec<HostInfo> {
+    session
+        .get_cluster_state()
+        .get_token_endpoints(keyspace, table, token)
+        .iter()
+        .map(|(node, _)| HostInfo::from_node(node))
+        .collect()
+}
+
+// Without a keyspace each range is owned by the node holding its end token only
+pub fn token_ring(session: &ScyllaSession, keyspace: Option<&str>) -> Vec<TokenRange> {
+    let cluster_state = session.get_cluster_state();
+    let ring: Vec<_> = cluster_state.replica_locator().ring().iter().collect();
+    let Some((last, _)) = ring.last() else {
+        return Vec::new();
+    };
+
+    let mut start = last.value();
+    let mut ranges = Vec::with_capacity(ring.len());
+    for (token, node) in ring {
+        let replicas = match keyspace {
+            Some(keyspace) => cluster_state
+                .get_token_endpoints(keyspace, "", *token)
+                .iter()
+                .map(|(node, _)| HostInfo::from_node(node))
+                .collect(),
+            None => vec![HostInfo::from_node(node)],
+        };
+        ranges.push(TokenRange {
+            start,
+            end: token.value(),
+            replicas,
+        });
+        start = token.value();
+    }
+    ranges
+}
+
+// Splits every range of the ring in proportion to its size, so that all ranges together
+// make up about splits_per_node pieces per node. Ranges are never merged.
+pub fn split_token_ring(
+    ring: Vec<TokenRange>,
+    nodes: usize,
+    splits_per_node: usize,
+) -> Vec<TokenRange> {
+    let target = (nodes * splits_per_node) as f64;
+    let mut ranges = Vec::with_capacity(ring.len());
+    for range in ring {
+        // Distance along the ring; a ring with a single token is one range covering everything
+        let size = match range.end.wrapping_sub(range.start) as u64 {
+            0 => u64::MAX,
+            size => size,
+        };
+        let splits = ((size as f64 / 2f64.powi(64)) * target).round().max(1.0) as u64;
+        let step = size / splits;
+
+        let mut start = range.start;
+        for split in 1..=splits {
+            let end = if split == splits {
+                range.end
+            } else {
+                start.wrapping_add(step as i64)
+            };
+            ranges.push(TokenRange {
+                start,
+                end,
+                replicas: range.replicas.clone(),
+            });
+            start = end;
+        }
+    }
+    ranges
+}
+
+fn hash_partition_key(hasher: &mut impl PartitionerHasher, components: &[Vec<u8>]) -> PyResult<()> {
+    if let [value] = components {
+        hasher.write(value);
+        return Ok(());
+    }
+    // Compound keys hash each component as <u16 length><bytes><0x00>
+    for value in components {
+        let len = u16::try_from(value.len()).map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Partition key component too long: {} bytes",
+                value.len()
+            ))
+        })?;
+        hasher.write(&len.to_be_bytes());
+        hasher.write(value);
+        hasher.write(&[0u8]);
+    }
+    Ok(())
+}
+
+#[pyfunction]
+pub fn token_for(partition_key: &Bound<'_, PyDict>, table: &TableMetadata) -> PyResult<i64> {
+    let columns = table
+        .partition_key
+        .iter()
+        .map(String::as_str)
+        .zip(&table.partition_key_types)
+        .collect::<Vec<_>>();
+    let values = py_dict_to_typed_values(Some(partition_key), &columns, EnumBinding::default())?;
+    if values.len() != table.partition_key.len() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Expected values for partition key columns {:?}",
+            table.partition_key
+        )));
+    }
+
+    let mut components = Vec::with_capacity(values.len());
+    for (name, typ) in table.partition_key.iter().zip(&table.partition_key_types) {
+        let value = values.get(name).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Missing value for partition key column {}",
+                name
+            ))
+        })?;
+        let mut component = Vec::new();
+        value
+            .serialize(typ, CellWriter::new_without_size(&mut component))
+            .map_err(serialization_error_to_py)?;
+        components.push(component);
+    }
+
+    let token = match table.partitioner.as_deref() {
+        Some(name) if name.ends_with("CDCPartitioner") => {
+            let mut hasher = CDCPartitioner.build_hasher();
+            hash_partition_key(&mut hasher, &components)?;
+            hasher.finish()
+        }
+        _ => {
+            let mut hasher = Murmur3Partitioner.build_hasher();
+            hash_partition_key(&mut hasher, &components)?;
+            hasher.finish()
+        }
+    };
+    Ok(token.value())
+}
+
+#[pyfunction]
+#[pyo3(signature = (token, shard_count, msb_ignore=12))]
+pub fn shard_for(token: i64, shard_count: u16, msb_ignore: u8) -> PyResult<u32> {
+    let shard_count = ShardCount::new(shard_count).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>("shard_count must be positive")
+    })?;
+    Ok(Sharder::new(shard_count, msb_ignore).shard_of(Token::new(token)))
+}
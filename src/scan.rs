@@ -0,0 +1,256 @@
+use futures::stream::{self, StreamExt};
+use pyo3::prelude::*;
+use scylla::client::session::Session as ScyllaSession;
+use scylla::value::Row as ScyllaRow;
+use std::sync::{Arc, Weak};
+use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
+
+use crate::builder::top_level_words;
+use crate::error::{
+    deserialization_error_to_py, next_row_error_to_py, pager_error_to_py, prepare_error_to_py,
+    ScyllaError,
+};
+use crate::metadata::lookup_table;
+use crate::rate_limit::Throttle;
+use crate::result::{column_names, Row};
+use crate::routing::TokenRange;
+use crate::shutdown::InFlight;
+use crate::stream::RowReceiver;
+
+// Turns a table name or a SELECT into a query restricted to a token range (?, ?]
+pub fn scan_query(session: &ScyllaSession, table_or_query: &str) -> PyResult<String> {
+    let statement = table_or_query.trim().trim_end_matches(';').trim_end();
+    let words = top_level_words(statement);
+    let select = match words.first() {
+        Some((_, word)) if word == "SELECT" => statement.to_string(),
+        _ => format!("SELECT * FROM {}", statement),
+    };
+
+    let words = top_level_words(&select);
+    let from = words
+        .iter()
+        .find(|(_, word)| word == "FROM")
+        .map(|(from, _)| from + "FROM".len())
+        .unwrap_or(select.len());
+    let rest = &select[from..];
+    let table = table_name(rest);
+    if table.is_empty() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Expected SELECT ... FROM <table>, got: {}",
+            statement
+        )));
+    }
+    let table_end = from + rest.len() - rest.trim_start().len() + table.len();
+
+    let (_, _, table) = lookup_table(session, table)?;
+    let partition_key = table.partition_key;
+
+    let token = format!(
+        "token({})",
+        partition_key
+            .iter()
+            .map(|column| format!("\"{}\"", column.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let condition = format!("{} > ? AND {} <= ?", token, token);
+
+    // Without a WHERE clause, whatever follows the table (LIMIT, ALLOW FILTERING, ...) has to
+    // come after the one added
+    let clause = words
+        .iter()
+        .find(|(offset, word)| *offset > from && word == "WHERE");
+    Ok(match clause {
+        Some((pos, _)) => format!(
+            "{}WHERE {} AND{}",
+            &select[..*pos],
+            condition,
+            &select[pos + "WHERE".len()..]
+        ),
+        None => format!(
+            "{} WHERE {}{}",
+            &select[..table_end],
+            condition,
+            &select[table_end..]
+        ),
+    })
+}
+
+// Table name at the start of the text following FROM, optionally quoted and qualified with
+// its keyspace
+fn table_name(rest: &str) -> &str {
+    let rest = rest.trim_start();
+    let mut quoted = false;
+    let end = rest
+        .char_indices()
+        .find(|&(_, c)| {
+            if c == '"' {
+                quoted = !quoted;
+            }
+            !quoted && c.is_whitespace()
+        })
+        .map_or(rest.len(), |(end, _)| end);
+    &rest[..end]
+}
+
+// Token bounds (start, end] covering the whole ring, with the wrapping range split in two
+pub fn scan_bounds(ring: &[TokenRange]) -> Vec<(i64, i64)> {
+    let mut bounds = Vec::with_capacity(ring.len() + 1);
+    for range in ring {
+        if range.start < range.end {
+            bounds.push((range.start, range.end));
+        } else {
+            if range.start < i64::MAX {
+                bounds.push((range.start, i64::MAX));
+            }
+            bounds.push((i64::MIN, range.end));
+        }
+    }
+    bounds
+}
+
+// Rows of a full scan, fetched in the background by concurrent token range queries
+#[pyclass]
+pub struct FullScan {
+    rows: RowReceiver,
+    task: AbortHandle,
+}
+
+impl FullScan {
+    // The scan only holds on to the session while a query runs, so closing the session stops it
+    // from starting the next token range. Each range query waits for the session's rate limiters
+    // and counts as a request in flight, like execute().
+    pub fn start(
+        session: Weak<ScyllaSession>,
+        in_flight: Arc<InFlight>,
+        throttle: Throttle,
+        query: String,
+        bounds: Vec<(i64, i64)>,
+        parallelism: usize,
+        page_size: Option<i32>,
+    ) -> Self {
+        let (sender, pages) = mpsc::channel(parallelism);
+        let task = pyo3_async_runtimes::tokio::get_runtime().spawn(async move {
+            let prepared = match session.upgrade() {
+                Some(session) => {
+                    in_flight
+                        .track(async { session.prepare(query).await.map_err(prepare_error_to_py) })
+                        .await
+                }
+                None => Err(session_closed()),
+            };
+            let mut prepared = match prepared {
+                Ok(prepared) => prepared,
+                Err(err) => {
+                    let _ = sender.send(Err(err)).await;
+                    return;
+                }
+            };
+            if let Some(page_size) = page_size {
+                prepared.set_page_size(page_size);
+            }
+
+            let ranges = bounds.len();
+            let mut scanned = 0;
+            let sessions = bounds
+                .into_iter()
+                .map_while(|bounds| Some((session.upgrade()?, bounds)));
+            let (prepared, sender, throttle) = (&prepared, &sender, &throttle);
+            stream::iter(sessions)
+                .map(|(session, bounds)| {
+                    let range = in_flight.track(async move {
+                        throttle.wait().await;
+                        scan_range(session, prepared, bounds, sender).await
+                    });
+                    async move {
+                        if let Err(err) = range.await {
+                            let _ = sender.send(Err(err)).await;
+                        }
+                    }
+                })
+                .buffer_unordered(parallelism)
+                .for_each(|_| {
+                    scanned += 1;
+                    async {}
+                })
+                .await;
+            if scanned < ranges {
+                let _ = sender.send(Err(session_closed())).await;
+            }
+        });
+
+        FullScan {
+            rows: RowReceiver::new(pages),
+            task: task.abort_handle(),
+        }
+    }
+}
+
+fn session_closed() -> PyErr {
+    PyErr::new::<ScyllaError, _>("Session was closed before the scan completed")
+}
+
+// Sends the rows of one token range, failing with the first error
+async fn scan_range(
+    session: Arc<ScyllaSession>,
+    prepared: &scylla::statement::prepared::PreparedStatement,
+    bounds: (i64, i64),
+    sender: &mpsc::Sender<PyResult<Vec<Row>>>,
+) -> PyResult<()> {
+    let rows = session
+        .execute_iter(prepared.clone(), bounds)
+        .await
+        .map_err(pager_error_to_py)?
+        .rows_stream::<ScyllaRow>()
+        .map_err(deserialization_error_to_py)?;
+
+    let names = column_names(prepared.get_current_result_set_col_specs().get());
+
+    // Hand over whatever has been received so far instead of row by row
+    let mut chunks = rows.ready_chunks(1024);
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk
+            .into_iter()
+            .map(|row| row.map(|row| Row::new(&row, &names)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(next_row_error_to_py)?;
+        if sender.send(Ok(chunk)).await.is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[pymethods]
+impl FullScan {
+    pub fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.rows.next_async(py)
+    }
+
+    pub fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __next__(&self, py: Python) -> PyResult<Option<Row>> {
+        self.rows.next_blocking(py)
+    }
+
+    pub fn cancel(&self) {
+        self.task.abort();
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("FullScan(finished={})", self.task.is_finished())
+    }
+}
+
+impl Drop for FullScan {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
@@ -0,0 +1,113 @@
+use pyo3::prelude::*;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, Notify, Semaphore};
+
+use crate::error::ScyllaError;
+
+// Requests in flight on a session, and the signal abandoning them when a shutdown's grace period
+// runs out
+pub struct InFlight {
+    count: AtomicUsize,
+    drained: Notify,
+    abandon: watch::Sender<bool>,
+    // Slots of max_inflight_requests(), requests past the limit wait for one in FIFO order
+    max_requests: Option<usize>,
+    slots: Option<Semaphore>,
+}
+
+struct InFlightGuard(Arc<InFlight>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.0.count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.drained.notify_waiters();
+        }
+    }
+}
+
+impl InFlight {
+    pub fn new(max_requests: Option<usize>) -> Arc<Self> {
+        Arc::new(InFlight {
+            count: AtomicUsize::new(0),
+            drained: Notify::new(),
+            abandon: watch::channel(false).0,
+            max_requests,
+            slots: max_requests.map(Semaphore::new),
+        })
+    }
+
+    // Counts the request as in flight until it completes, or fails it once it is abandoned. With
+    // a limit the request isn't sent before it gets a slot.
+    pub fn track<F, T>(self: &Arc<Self>, request: F) -> impl Future<Output = PyResult<T>>
+    where
+        F: Future<Output = PyResult<T>>,
+    {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        let guard = InFlightGuard(self.clone());
+        let mut abandoned = self.abandon.subscribe();
+        let in_flight = self.clone();
+        async move {
+            let _guard = guard;
+            let request = async {
+                let _slot = match &in_flight.slots {
+                    Some(slots) => Some(
+                        slots
+                            .acquire()
+                            .await
+                            .map_err(|_| PyErr::new::<ScyllaError, _>("Session is closed"))?,
+                    ),
+                    None => None,
+                };
+                request.await
+            };
+            tokio::select! {
+                result = request => result,
+                _ = abandoned.wait_for(|abandoned| *abandoned) => Err(PyErr::new::<ScyllaError, _>(
+                    "Session was shut down before the request completed",
+                )),
+            }
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+
+    // Requests waiting for a slot of max_inflight_requests()
+    pub fn waiting(&self) -> usize {
+        match (self.max_requests, &self.slots) {
+            (Some(max_requests), Some(slots)) => self
+                .count()
+                .saturating_sub(max_requests - slots.available_permits()),
+            _ => 0,
+        }
+    }
+
+    // Waits until no request is in flight, abandoning those left after the grace period.
+    // Returns whether every request completed in time.
+    pub async fn drain(&self, grace_period: Option<Duration>) -> bool {
+        let drained = async {
+            loop {
+                let notified = self.drained.notified();
+                if self.count() == 0 {
+                    return;
+                }
+                notified.await;
+            }
+        };
+        let completed = match grace_period {
+            Some(grace_period) => tokio::time::timeout(grace_period, drained).await.is_ok(),
+            None => {
+                drained.await;
+                true
+            }
+        };
+        if !completed {
+            self.abandon.send_replace(true);
+        }
+        completed
+    }
+}
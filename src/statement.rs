@@ -0,0 +1,215 @@
+use pyo3::prelude::*;
+use scylla::statement::unprepared::Statement as ScyllaQuery;
+use std::time::Duration;
+
+use crate::builder::{with_bypass_cache, with_using_option};
+use crate::profile::{ExecutionProfile, ProfileOptions};
+use crate::query::{
+    check_page_size, consistency_to_str, parse_consistency, parse_serial_consistency,
+    serial_consistency_to_str,
+};
+use crate::routing::RoutingKey;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct Statement {
+    pub(crate) inner: ScyllaQuery,
+    pub(crate) routing_key: Option<RoutingKey>,
+    query_string: String,
+    ttl: Option<i32>,
+    server_timeout: Option<Duration>,
+    bypass_cache: bool,
+    profile: Option<ExecutionProfile>,
+}
+
+// CQL duration literal in the largest unit that keeps it exact
+fn duration_literal(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros.is_multiple_of(1_000_000) {
+        format!("{}s", micros / 1_000_000)
+    } else if micros.is_multiple_of(1_000) {
+        format!("{}ms", micros / 1_000)
+    } else {
+        format!("{}us", micros)
+    }
+}
+
+impl Statement {
+    // Settings of the statement's profile that the driver doesn't apply
+    pub(crate) fn options(&self) -> ProfileOptions {
+        self.profile
+            .as_ref()
+            .map(|profile| profile.options())
+            .unwrap_or_default()
+    }
+}
+
+#[pymethods]
+impl Statement {
+    #[new]
+    #[pyo3(signature = (
+        query,
+        consistency=None,
+        serial_consistency=None,
+        timeout_ms=None,
+        page_size=None,
+        idempotent=false,
+        tracing=false,
+        timestamp=None,
+        routing_key=None,
+        profile=None,
+        ttl=None,
+        server_timeout=None,
+        bypass_cache=false
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        query: &str,
+        consistency: Option<&str>,
+        serial_consistency: Option<&str>,
+        timeout_ms: Option<u64>,
+        page_size: Option<i32>,
+        idempotent: bool,
+        tracing: bool,
+        timestamp: Option<i64>,
+        routing_key: Option<(Vec<u8>, String, String)>,
+        profile: Option<ExecutionProfile>,
+        ttl: Option<i32>,
+        server_timeout: Option<Duration>,
+        bypass_cache: bool,
+    ) -> PyResult<Self> {
+        let mut cql = query.to_string();
+        if let Some(ttl) = ttl {
+            if ttl < 0 {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "ttl must not be negative",
+                ));
+            }
+            cql = with_using_option(&cql, &format!("TTL {}", ttl))?;
+        }
+        if let Some(server_timeout) = server_timeout {
+            // Scylla reads durations down to microseconds
+            if server_timeout.as_micros() == 0 {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "server_timeout must be at least one microsecond",
+                ));
+            }
+            cql = with_using_option(
+                &cql,
+                &format!("TIMEOUT {}", duration_literal(server_timeout)),
+            )?;
+        }
+        if bypass_cache {
+            cql = with_bypass_cache(&cql)?;
+        }
+        let mut inner = ScyllaQuery::new(cql);
+        if let Some(profile) = &profile {
+            inner.set_execution_profile_handle(Some(profile.handle()?));
+        }
+        if let Some(consistency) = consistency {
+            inner.set_consistency(parse_consistency(consistency)?);
+        }
+        if let Some(serial_consistency) = serial_consistency {
+            inner.set_serial_consistency(Some(parse_serial_consistency(serial_consistency)?));
+        }
+        check_page_size(page_size)?;
+        if let Some(page_size) = page_size {
+            inner.set_page_size(page_size);
+        }
+        inner.set_request_timeout(timeout_ms.map(Duration::from_millis));
+        inner.set_is_idempotent(idempotent);
+        inner.set_tracing(tracing);
+        inner.set_timestamp(timestamp);
+
+        Ok(Statement {
+            inner,
+            routing_key: routing_key
+                .map(|(key, keyspace, table)| RoutingKey::new(&keyspace, &table, &key)),
+            query_string: query.to_string(),
+            ttl,
+            server_timeout,
+            bypass_cache,
+            profile,
+        })
+    }
+
+    #[getter]
+    pub fn query(&self) -> String {
+        self.query_string.clone()
+    }
+
+    #[getter]
+    pub fn consistency(&self) -> Option<String> {
+        self.inner
+            .get_consistency()
+            .map(|c| consistency_to_str(c).to_string())
+    }
+
+    #[getter]
+    pub fn serial_consistency(&self) -> Option<String> {
+        self.inner
+            .get_serial_consistency()
+            .map(|c| serial_consistency_to_str(c).to_string())
+    }
+
+    #[getter]
+    pub fn timeout_ms(&self) -> Option<u64> {
+        self.inner
+            .get_request_timeout()
+            .map(|timeout| timeout.as_millis() as u64)
+    }
+
+    #[getter]
+    pub fn page_size(&self) -> i32 {
+        self.inner.get_page_size()
+    }
+
+    #[getter]
+    pub fn idempotent(&self) -> bool {
+        self.inner.get_is_idempotent()
+    }
+
+    #[getter]
+    pub fn tracing(&self) -> bool {
+        self.inner.get_tracing()
+    }
+
+    #[getter]
+    pub fn timestamp(&self) -> Option<i64> {
+        self.inner.get_timestamp()
+    }
+
+    #[getter]
+    pub fn ttl(&self) -> Option<i32> {
+        self.ttl
+    }
+
+    #[getter]
+    pub fn server_timeout(&self) -> Option<Duration> {
+        self.server_timeout
+    }
+
+    #[getter]
+    pub fn bypass_cache(&self) -> bool {
+        self.bypass_cache
+    }
+
+    #[getter]
+    pub fn cql(&self) -> String {
+        self.inner.contents.clone()
+    }
+
+    #[getter]
+    pub fn profile(&self) -> Option<ExecutionProfile> {
+        self.profile.clone()
+    }
+
+    #[getter]
+    pub fn routing_token(&self) -> Option<i64> {
+        self.routing_key.as_ref().map(|key| key.token.value())
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("Statement({:?})", self.query_string)
+    }
+}
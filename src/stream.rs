@@ -0,0 +1,151 @@
+use pyo3::prelude::*;
+use scylla::response::query_result::QueryRowsResult;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+
+use crate::aio::future_into_py;
+use crate::result::{page_rows, Row};
+use crate::session::wait;
+
+// Rows handed over to the consumer at once
+const CHUNK_ROWS: usize = 1024;
+
+struct ReceivedRows {
+    chunks: mpsc::Receiver<PyResult<Vec<Row>>>,
+    rows: VecDeque<Row>,
+}
+
+// Rows sent in chunks by a background task, handed out one at a time to Python iterators
+pub struct RowReceiver {
+    state: Arc<Mutex<ReceivedRows>>,
+}
+
+impl RowReceiver {
+    pub fn new(chunks: mpsc::Receiver<PyResult<Vec<Row>>>) -> Self {
+        RowReceiver {
+            state: Arc::new(Mutex::new(ReceivedRows {
+                chunks,
+                rows: VecDeque::new(),
+            })),
+        }
+    }
+
+    // Rows already converted, with nothing left to receive
+    pub fn from_rows(rows: Vec<Row>) -> Self {
+        let (_, chunks) = mpsc::channel(1);
+        RowReceiver {
+            state: Arc::new(Mutex::new(ReceivedRows {
+                chunks,
+                rows: rows.into(),
+            })),
+        }
+    }
+
+    // For __anext__, raising StopAsyncIteration after the last row
+    pub fn next_async<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let row = next_row(self.state.clone());
+        future_into_py(py, async move {
+            row.await?
+                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyStopAsyncIteration, _>(()))
+        })
+    }
+
+    // For __next__, returning None after the last row
+    pub fn next_blocking(&self, py: Python) -> PyResult<Option<Row>> {
+        wait(py, next_row(self.state.clone()))
+    }
+}
+
+async fn next_row(state: Arc<Mutex<ReceivedRows>>) -> PyResult<Option<Row>> {
+    let mut state = state.lock().await;
+    loop {
+        if let Some(row) = state.rows.pop_front() {
+            return Ok(Some(row));
+        }
+        match state.chunks.recv().await {
+            Some(Ok(rows)) => state.rows.extend(rows),
+            Some(Err(err)) => return Err(err),
+            None => return Ok(None),
+        }
+    }
+}
+
+// Rows of a result, deserialized on a worker thread up to `buffer` rows ahead of the consumer
+#[pyclass]
+pub struct RowStream {
+    rows: RowReceiver,
+}
+
+impl RowStream {
+    pub fn start(
+        pages: Arc<[(QueryRowsResult, Option<Vec<u8>>)]>,
+        names: Arc<[String]>,
+        raw: bool,
+        buffer: usize,
+    ) -> Self {
+        let chunk_rows = buffer.clamp(1, CHUNK_ROWS);
+        let (sender, chunks) = mpsc::channel(buffer.div_ceil(chunk_rows).max(1));
+        // The worker stops at the first failed send, once the stream is dropped
+        pyo3_async_runtimes::tokio::get_runtime().spawn_blocking(move || {
+            for (page, _) in pages.iter() {
+                let rows = match page_rows(page, &names, raw) {
+                    Ok(rows) => rows,
+                    Err(err) => {
+                        let _ = sender.blocking_send(Err(err));
+                        return;
+                    }
+                };
+                let mut chunk = Vec::with_capacity(chunk_rows);
+                for row in rows {
+                    match row {
+                        Ok(row) => chunk.push(row),
+                        Err(err) => {
+                            let _ = sender.blocking_send(Err(err));
+                            return;
+                        }
+                    }
+                    if chunk.len() == chunk_rows {
+                        let full = std::mem::replace(&mut chunk, Vec::with_capacity(chunk_rows));
+                        if sender.blocking_send(Ok(full)).is_err() {
+                            return;
+                        }
+                    }
+                }
+                if !chunk.is_empty() && sender.blocking_send(Ok(chunk)).is_err() {
+                    return;
+                }
+            }
+        });
+
+        RowStream {
+            rows: RowReceiver::new(chunks),
+        }
+    }
+
+    // Rows already converted, e.g. of an unpickled result
+    pub fn from_rows(rows: Vec<Row>) -> Self {
+        RowStream {
+            rows: RowReceiver::from_rows(rows),
+        }
+    }
+}
+
+#[pymethods]
+impl RowStream {
+    pub fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.rows.next_async(py)
+    }
+
+    pub fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __next__(&self, py: Python) -> PyResult<Option<Row>> {
+        self.rows.next_blocking(py)
+    }
+}
@@ -0,0 +1,67 @@
+use pyo3::prelude::*;
+
+use crate::builder::top_level_words;
+use crate::error::StrictModeViolation;
+use crate::prepare_cache::is_preparable;
+
+// Statements a session refuses to send, checked before they go out
+#[derive(Clone, Copy)]
+pub struct StrictMode {
+    pub unprepared: bool,
+    pub allow_filtering: bool,
+    pub unbounded_selects: bool,
+}
+
+impl StrictMode {
+    pub fn all() -> Self {
+        StrictMode {
+            unprepared: true,
+            allow_filtering: true,
+            unbounded_selects: true,
+        }
+    }
+
+    // `paged` tells whether the result is fetched page by page rather than in one response
+    pub fn check(&self, cql: &str, prepared: bool, paged: bool) -> PyResult<()> {
+        // Schema changes and USE are sent as they are
+        if self.unprepared && !prepared && is_preparable(cql) {
+            return Err(violation(cql, "unprepared statements are not allowed"));
+        }
+        let words = top_level_words(cql);
+        let follows = |first: &str, second: &str| {
+            words
+                .windows(2)
+                .any(|pair| pair[0].1 == first && pair[1].1 == second)
+        };
+        if self.allow_filtering && follows("ALLOW", "FILTERING") {
+            return Err(violation(cql, "ALLOW FILTERING is not allowed"));
+        }
+        let select = words.first().is_some_and(|(_, word)| word == "SELECT");
+        // PER PARTITION LIMIT still reads every partition
+        let limited = words
+            .iter()
+            .enumerate()
+            .any(|(i, (_, word))| word == "LIMIT" && (i == 0 || words[i - 1].1 != "PARTITION"));
+        if self.unbounded_selects && select && !limited && !paged {
+            return Err(violation(
+                cql,
+                "SELECT needs a LIMIT or paging (page_size or max_rows_in_memory)",
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn check_batch<'a>(&self, mut unprepared: impl Iterator<Item = &'a str>) -> PyResult<()> {
+        match unprepared.next() {
+            Some(cql) if self.unprepared => Err(violation(
+                cql,
+                "unprepared statements are not allowed in batches",
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn violation(cql: &str, reason: &str) -> PyErr {
+    PyErr::new::<StrictModeViolation, _>(format!("Strict mode: {}: {}", reason, cql))
+}
@@ -0,0 +1,420 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::error::ScyllaError;
+use crate::result::QueryResult;
+
+#[pyclass]
+pub struct SlowQuery {
+    #[pyo3(get)]
+    statement: String,
+    #[pyo3(get)]
+    values: Option<Py<PyDict>>,
+    #[pyo3(get)]
+    latency_ms: f64,
+    #[pyo3(get)]
+    coordinator: Option<String>,
+    #[pyo3(get)]
+    error: Option<String>,
+}
+
+#[pymethods]
+impl SlowQuery {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "SlowQuery(statement={:?}, latency_ms={:.3}, coordinator={:?})",
+            self.statement, self.latency_ms, self.coordinator
+        )
+    }
+}
+
+#[pyclass]
+pub struct RequestContext {
+    #[pyo3(get)]
+    statement: String,
+    #[pyo3(get)]
+    values: Option<Py<PyDict>>,
+    #[pyo3(get)]
+    keyspace: Option<String>,
+    #[pyo3(get)]
+    annotations: Py<PyDict>,
+}
+
+#[pymethods]
+impl RequestContext {
+    pub fn __repr__(&self) -> String {
+        format!("RequestContext(statement={:?})", self.statement)
+    }
+}
+
+pub struct Interceptor {
+    before: Option<Py<PyAny>>,
+    after: Option<Py<PyAny>>,
+}
+
+impl Interceptor {
+    pub fn new(before: Option<Py<PyAny>>, after: Option<Py<PyAny>>) -> Self {
+        Interceptor { before, after }
+    }
+}
+
+pub struct SlowQueryLog {
+    threshold: Duration,
+    callback: Option<Py<PyAny>>,
+    include_values: bool,
+}
+
+impl SlowQueryLog {
+    pub fn new(threshold: Duration, callback: Option<Py<PyAny>>, include_values: bool) -> Self {
+        SlowQueryLog {
+            threshold,
+            callback,
+            include_values,
+        }
+    }
+
+    fn report(&self, py: Python, slow_query: SlowQuery) -> PyResult<()> {
+        match &self.callback {
+            Some(callback) => {
+                callback.call1(py, (slow_query,))?;
+            }
+            None => {
+                let logger = py
+                    .import("logging")?
+                    .call_method1("getLogger", ("rsylla.slow_query",))?;
+                logger.call_method1(
+                    "warning",
+                    (
+                        "Slow query (%.1f ms, coordinator %s): %s",
+                        slow_query.latency_ms,
+                        slow_query.coordinator.as_deref().unwrap_or("unknown"),
+                        slow_query.statement,
+                    ),
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+const REDACTED: &str = "<redacted>";
+
+// How bound values are masked before they are logged
+pub enum Redaction {
+    Columns(HashSet<String>),
+    // Called with the column name and value, returns what to log instead
+    Callable(Py<PyAny>),
+}
+
+impl Redaction {
+    pub fn parse(redact: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if redact.is_callable() {
+            return Ok(Redaction::Callable(redact.clone().unbind()));
+        }
+        if redact.is_instance_of::<pyo3::types::PyString>() {
+            return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                "redact must be a collection of column names or a callable, not a string",
+            ));
+        }
+        let columns = redact
+            .try_iter()?
+            .map(|column| Ok(column?.extract::<String>()?.to_lowercase()))
+            .collect::<PyResult<_>>()?;
+        Ok(Redaction::Columns(columns))
+    }
+
+    fn apply<'py>(&self, values: &Bound<'py, PyDict>) -> PyResult<Bound<'py, PyDict>> {
+        let py = values.py();
+        let redacted = PyDict::new(py);
+        for (name, value) in values.iter() {
+            let value = match self {
+                Redaction::Columns(columns) => {
+                    let column = name.extract::<String>()?.to_lowercase();
+                    if columns.contains(&column) {
+                        REDACTED.into_pyobject(py)?.into_any()
+                    } else {
+                        value
+                    }
+                }
+                Redaction::Callable(redact) => redact.bind(py).call1((&name, value))?,
+            };
+            redacted.set_item(name, value)?;
+        }
+        Ok(redacted)
+    }
+}
+
+// Logs every statement with its bound values when the request starts
+pub struct QueryLog {
+    logger: Py<PyAny>,
+    level: i32,
+    include_values: bool,
+    redaction: Option<Redaction>,
+}
+
+impl QueryLog {
+    pub fn new(
+        logger: Py<PyAny>,
+        level: i32,
+        include_values: bool,
+        redaction: Option<Redaction>,
+    ) -> Self {
+        QueryLog {
+            logger,
+            level,
+            include_values,
+            redaction,
+        }
+    }
+
+    fn record(
+        &self,
+        py: Python,
+        statement: &str,
+        keyspace: Option<&str>,
+        values: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<()> {
+        let logger = self.logger.bind(py);
+        if !logger
+            .call_method1("isEnabledFor", (self.level,))?
+            .is_truthy()?
+        {
+            return Ok(());
+        }
+        let values = match (values, &self.redaction) {
+            (Some(values), _) if !self.include_values || values.is_empty() => None,
+            (Some(values), Some(redaction)) => Some(redaction.apply(values)?),
+            (Some(values), None) => Some(values.copy()?),
+            (None, _) => None,
+        };
+
+        let extra = PyDict::new(py);
+        extra.set_item("cql_statement", statement)?;
+        extra.set_item("cql_keyspace", keyspace)?;
+        extra.set_item("cql_values", &values)?;
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("extra", extra)?;
+        match values {
+            Some(values) => logger.call_method(
+                "log",
+                (self.level, "CQL %s %s", statement, values),
+                Some(&kwargs),
+            )?,
+            None => logger.call_method("log", (self.level, "CQL %s", statement), Some(&kwargs))?,
+        };
+        Ok(())
+    }
+}
+
+// Request hooks configured on the session builder and shared by the session
+#[derive(Clone, Default)]
+pub struct Observers {
+    pub tracer: Option<Arc<Py<PyAny>>>,
+    pub slow_query_log: Option<Arc<SlowQueryLog>>,
+    pub query_log: Option<Arc<QueryLog>>,
+    pub interceptors: Vec<Arc<Interceptor>>,
+}
+
+impl Observers {
+    pub fn start(
+        &self,
+        py: Python,
+        statement: &str,
+        keyspace: Option<&str>,
+        values: Option<&Bound<'_, PyDict>>,
+        logged: bool,
+    ) -> PyResult<RequestObserver> {
+        if let Some(log) = self.query_log.as_ref().filter(|_| logged) {
+            // Logging must not fail the request itself
+            log.record(py, statement, keyspace, values)
+                .unwrap_or_else(|err| err.write_unraisable(py, Some(log.logger.bind(py))));
+        }
+
+        let slow_query = match &self.slow_query_log {
+            Some(log) => {
+                // Copy the values so later mutation by the caller doesn't leak into the report
+                let values = match values {
+                    Some(values) if log.include_values => Some(values.copy()?.unbind()),
+                    _ => None,
+                };
+                Some((log.clone(), statement.to_string(), values))
+            }
+            None => None,
+        };
+
+        let context = if self.interceptors.is_empty() {
+            None
+        } else {
+            let context = Py::new(
+                py,
+                RequestContext {
+                    statement: statement.to_string(),
+                    values: values.map(|values| values.clone().unbind()),
+                    keyspace: keyspace.map(str::to_string),
+                    annotations: PyDict::new(py).unbind(),
+                },
+            )?;
+            // A failing before hook aborts the request
+            for interceptor in &self.interceptors {
+                if let Some(before) = &interceptor.before {
+                    before.call1(py, (context.clone_ref(py),))?;
+                }
+            }
+            Some((self.interceptors.clone(), context))
+        };
+
+        let span = match &self.tracer {
+            Some(tracer) => Some(RequestSpan::start(py, tracer, statement, keyspace)?),
+            None => None,
+        };
+
+        Ok(RequestObserver {
+            span,
+            slow_query,
+            context,
+            started: Instant::now(),
+        })
+    }
+}
+
+// Observes a single request from the calling thread until its result is known
+pub struct RequestObserver {
+    span: Option<RequestSpan>,
+    slow_query: Option<(Arc<SlowQueryLog>, String, Option<Py<PyDict>>)>,
+    context: Option<(Vec<Arc<Interceptor>>, Py<RequestContext>)>,
+    started: Instant,
+}
+
+impl RequestObserver {
+    pub fn finish(mut self, result: &PyResult<QueryResult>) {
+        self.report(result);
+    }
+
+    fn report(&mut self, result: &PyResult<QueryResult>) {
+        let latency = self.started.elapsed();
+        let span = self.span.take();
+        let context = self.context.take();
+        let slow_query = self
+            .slow_query
+            .take()
+            .filter(|(log, _, _)| latency >= log.threshold);
+        if span.is_none() && slow_query.is_none() && context.is_none() {
+            return;
+        }
+
+        Python::attach(|py| {
+            if let Some(span) = span {
+                span.finish(py, result);
+            }
+            if let Some((interceptors, context)) = context {
+                let info = result.as_ref().ok().map(|result| result.execution_info());
+                let error = result.as_ref().err().map(|err| err.value(py).clone());
+                for after in interceptors.iter().filter_map(|i| i.after.as_ref()) {
+                    if let Err(err) =
+                        after.call1(py, (context.clone_ref(py), info.clone(), error.clone()))
+                    {
+                        err.write_unraisable(py, Some(after.bind(py)));
+                    }
+                }
+            }
+            if let Some((log, statement, values)) = slow_query {
+                let slow_query = SlowQuery {
+                    statement,
+                    values,
+                    latency_ms: latency.as_secs_f64() * 1000.0,
+                    coordinator: result
+                        .as_ref()
+                        .ok()
+                        .map(|result| result.execution_info().coordinator),
+                    error: result.as_ref().err().map(|err| err.to_string()),
+                };
+                log.report(py, slow_query).unwrap_or_else(|err| {
+                    // Observability hooks must not fail the request itself
+                    err.write_unraisable(py, None);
+                });
+            }
+        })
+    }
+}
+
+// A request dropped before its result is known was cancelled, by the awaiting task, a deadline
+// or a signal. Spans still end and after hooks still run, with the cancellation as the error.
+impl Drop for RequestObserver {
+    fn drop(&mut self) {
+        self.report(&Err(PyErr::new::<ScyllaError, _>(
+            "Request was cancelled before it completed",
+        )));
+    }
+}
+
+// Client span of a single request, started on the calling thread so it picks up the active context
+struct RequestSpan {
+    span: Py<PyAny>,
+}
+
+impl RequestSpan {
+    fn start(
+        py: Python,
+        tracer: &Py<PyAny>,
+        statement: &str,
+        keyspace: Option<&str>,
+    ) -> PyResult<Self> {
+        let operation = statement
+            .split_whitespace()
+            .next()
+            .unwrap_or("QUERY")
+            .to_uppercase();
+
+        let attributes = PyDict::new(py);
+        attributes.set_item("db.system", "scylladb")?;
+        attributes.set_item("db.operation", &operation)?;
+        attributes.set_item("db.statement", statement)?;
+        if let Some(keyspace) = keyspace {
+            attributes.set_item("db.namespace", keyspace)?;
+        }
+
+        let kwargs = PyDict::new(py);
+        let span_kind = py.import("opentelemetry.trace")?.getattr("SpanKind")?;
+        kwargs.set_item("kind", span_kind.getattr("CLIENT")?)?;
+        kwargs.set_item("attributes", attributes)?;
+        let span = tracer
+            .bind(py)
+            .call_method("start_span", (operation,), Some(&kwargs))?;
+
+        Ok(RequestSpan {
+            span: span.unbind(),
+        })
+    }
+
+    fn finish(self, py: Python, result: &PyResult<QueryResult>) {
+        self.record(py, result).unwrap_or_else(|err| {
+            err.write_unraisable(py, Some(self.span.bind(py)));
+        });
+    }
+
+    fn record(&self, py: Python, result: &PyResult<QueryResult>) -> PyResult<()> {
+        let span = self.span.bind(py);
+        match result {
+            Ok(result) => {
+                let info = result.execution_info();
+                span.call_method1("set_attribute", ("server.address", info.coordinator))?;
+                span.call_method1("set_attribute", ("rsylla.attempts", info.attempts))?;
+                span.call_method1("set_attribute", ("rsylla.latency_ms", info.latency_ms))?;
+            }
+            Err(err) => {
+                let status = py.import("opentelemetry.trace")?;
+                let error = status.getattr("StatusCode")?.getattr("ERROR")?;
+                let status = status
+                    .getattr("Status")?
+                    .call1((error, err.value(py).str()?))?;
+                span.call_method1("record_exception", (err.value(py),))?;
+                span.call_method1("set_status", (status,))?;
+            }
+        }
+        span.call_method0("end")?;
+        Ok(())
+    }
+}
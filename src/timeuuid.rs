@@ -0,0 +1,149 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyString};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+use crate::types::{datetime_to_micros, is_instance_of_class};
+
+// 100ns intervals between the start of the Gregorian calendar and the Unix epoch
+const GREGORIAN_OFFSET: i64 = 0x01B2_1DD2_1381_4000;
+const TICKS_PER_MICRO: i64 = 10;
+
+// Clock sequence and node bounding the timeuuids of a timestamp, as compared by Scylla,
+// which orders them as signed bytes
+const MIN_CLOCK_SEQ_AND_NODE: u64 = 0x8080_8080_8080_8080;
+const MAX_CLOCK_SEQ_AND_NODE: u64 = 0x7f7f_7f7f_7f7f_7f7f;
+
+// Ticks of the last timeuuid_now(), so timeuuids generated by the process never repeat
+static LAST_TICKS: AtomicU64 = AtomicU64::new(0);
+static PROCESS_CLOCK_SEQ_AND_NODE: OnceLock<u64> = OnceLock::new();
+
+// Random clock sequence and node, with the RFC 4122 variant
+fn random_clock_seq_and_node() -> u64 {
+    let (_, random) = Uuid::new_v4().as_u64_pair();
+    (random & 0x3fff_ffff_ffff_ffff) | 0x8000_0000_0000_0000
+}
+
+fn timeuuid(py: Python, ticks: u64, clock_seq_and_node: u64) -> PyResult<Py<PyAny>> {
+    let time_low = ticks & 0xffff_ffff;
+    let time_mid = (ticks >> 32) & 0xffff;
+    let time_hi = (ticks >> 48) & 0x0fff;
+    let msb = (time_low << 32) | (time_mid << 16) | 0x1000 | time_hi;
+    let uuid = Uuid::from_u64_pair(msb, clock_seq_and_node);
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("bytes", uuid.as_bytes().as_slice())?;
+    Ok(py
+        .import("uuid")?
+        .getattr("UUID")?
+        .call((), Some(&kwargs))?
+        .unbind())
+}
+
+// Datetimes, naive ones in UTC, or integers of milliseconds since the epoch, as timestamps
+// are bound
+fn timestamp_to_ticks(val: &Bound<'_, PyAny>) -> PyResult<u64> {
+    let micros = if is_instance_of_class(val, "datetime", "datetime")? {
+        datetime_to_micros(val)?
+    } else if let Ok(millis) = val.extract::<i64>() {
+        millis.checked_mul(1000).ok_or_else(|| out_of_range(val))?
+    } else {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+            "Expected a datetime or milliseconds since the epoch, got {}",
+            val.get_type().name()?
+        )));
+    };
+    micros
+        .checked_mul(TICKS_PER_MICRO)
+        .and_then(|ticks| ticks.checked_add(GREGORIAN_OFFSET))
+        .filter(|ticks| (0..1 << 60).contains(ticks))
+        .map(|ticks| ticks as u64)
+        .ok_or_else(|| out_of_range(val))
+}
+
+fn out_of_range(val: &Bound<'_, PyAny>) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyOverflowError, _>(format!(
+        "{} is out of range for a timeuuid",
+        val
+    ))
+}
+
+fn parse_timeuuid(val: &Bound<'_, PyAny>) -> PyResult<Uuid> {
+    if val.cast::<PyString>().is_err() && !is_instance_of_class(val, "uuid", "UUID")? {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+            "Expected a UUID or str, got {}",
+            val.get_type().name()?
+        )));
+    }
+    let uuid = Uuid::parse_str(val.str()?.to_str()?.trim()).map_err(|_| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid UUID: {}", val))
+    })?;
+    if uuid.get_version_num() != 1 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "{} is not a timeuuid",
+            uuid
+        )));
+    }
+    Ok(uuid)
+}
+
+// New timeuuid of the current time, unique within the process
+#[pyfunction]
+pub fn timeuuid_now(py: Python) -> PyResult<Py<PyAny>> {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let now = (since_epoch.as_nanos() / 100) as u64 + GREGORIAN_OFFSET as u64;
+    // Timeuuids generated within the same tick, or after the clock went back, take the next one
+    let previous = LAST_TICKS
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+            Some(now.max(last + 1))
+        })
+        .unwrap_or_default();
+    let ticks = now.max(previous + 1);
+    let clock_seq_and_node = *PROCESS_CLOCK_SEQ_AND_NODE.get_or_init(random_clock_seq_and_node);
+    timeuuid(py, ticks, clock_seq_and_node)
+}
+
+// New timeuuid of a timestamp, with a random clock sequence and node
+#[pyfunction]
+pub fn timeuuid_from_datetime(py: Python, dt: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+    timeuuid(py, timestamp_to_ticks(dt)?, random_clock_seq_and_node())
+}
+
+// Smallest timeuuid of a timestamp, as minTimeuuid() in CQL
+#[pyfunction]
+pub fn min_timeuuid(py: Python, dt: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+    timeuuid(py, timestamp_to_ticks(dt)?, MIN_CLOCK_SEQ_AND_NODE)
+}
+
+// Largest timeuuid of a timestamp, as maxTimeuuid() in CQL, covering the whole microsecond
+// of a datetime or millisecond of an integer timestamp
+#[pyfunction]
+pub fn max_timeuuid(py: Python, dt: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+    let resolution = if is_instance_of_class(dt, "datetime", "datetime")? {
+        TICKS_PER_MICRO
+    } else {
+        TICKS_PER_MICRO * 1000
+    };
+    let ticks = timestamp_to_ticks(dt)? + resolution as u64 - 1;
+    timeuuid(py, ticks.min((1 << 60) - 1), MAX_CLOCK_SEQ_AND_NODE)
+}
+
+// UTC datetime a timeuuid was generated at, to the microsecond
+#[pyfunction]
+pub fn timeuuid_to_datetime(py: Python, u: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+    let (msb, _) = parse_timeuuid(u)?.as_u64_pair();
+    let ticks = ((msb & 0x0fff) << 48) | (((msb >> 16) & 0xffff) << 32) | (msb >> 32);
+    let micros = (ticks as i64 - GREGORIAN_OFFSET).div_euclid(TICKS_PER_MICRO);
+    let datetime = py.import("datetime")?;
+    let utc = datetime.getattr("timezone")?.getattr("utc")?;
+    let epoch = datetime
+        .getattr("datetime")?
+        .call1((1970, 1, 1, 0, 0, 0, 0, utc))?;
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("microseconds", micros)?;
+    let offset = datetime.getattr("timedelta")?.call((), Some(&kwargs))?;
+    Ok(epoch.add(offset)?.unbind())
+}
@@ -0,0 +1,103 @@
+use pyo3::prelude::*;
+use scylla::observability::tracing::{
+    TracingEvent as ScyllaTracingEvent, TracingInfo as ScyllaTracingInfo,
+};
+use std::collections::HashMap;
+
+#[pyclass]
+#[derive(Clone)]
+pub struct TracingEvent {
+    #[pyo3(get)]
+    event_id: String,
+    #[pyo3(get)]
+    activity: Option<String>,
+    #[pyo3(get)]
+    source: Option<String>,
+    // Microseconds since the start of the traced request
+    #[pyo3(get)]
+    source_elapsed: Option<i32>,
+    #[pyo3(get)]
+    thread: Option<String>,
+}
+
+impl From<&ScyllaTracingEvent> for TracingEvent {
+    fn from(event: &ScyllaTracingEvent) -> Self {
+        TracingEvent {
+            event_id: event.event_id.to_string(),
+            activity: event.activity.clone(),
+            source: event.source.map(|addr| addr.to_string()),
+            source_elapsed: event.source_elapsed,
+            thread: event.thread.clone(),
+        }
+    }
+}
+
+#[pymethods]
+impl TracingEvent {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "TracingEvent(source={:?}, source_elapsed={:?}, activity={:?})",
+            self.source, self.source_elapsed, self.activity
+        )
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct TracingInfo {
+    #[pyo3(get)]
+    client: Option<String>,
+    #[pyo3(get)]
+    command: Option<String>,
+    #[pyo3(get)]
+    coordinator: Option<String>,
+    // Total request duration in microseconds
+    #[pyo3(get)]
+    duration: Option<i32>,
+    #[pyo3(get)]
+    parameters: HashMap<String, String>,
+    #[pyo3(get)]
+    request: Option<String>,
+    // Milliseconds since epoch
+    #[pyo3(get)]
+    started_at: Option<i64>,
+    #[pyo3(get)]
+    events: Vec<TracingEvent>,
+}
+
+impl From<ScyllaTracingInfo> for TracingInfo {
+    fn from(info: ScyllaTracingInfo) -> Self {
+        TracingInfo {
+            client: info.client.map(|addr| addr.to_string()),
+            command: info.command,
+            coordinator: info.coordinator.map(|addr| addr.to_string()),
+            duration: info.duration,
+            parameters: info.parameters.unwrap_or_default(),
+            request: info.request,
+            started_at: info.started_at.map(|ts| ts.0),
+            events: info.events.iter().map(TracingEvent::from).collect(),
+        }
+    }
+}
+
+#[pymethods]
+impl TracingInfo {
+    pub fn nodes(&self) -> Vec<String> {
+        let mut nodes: Vec<String> = Vec::new();
+        for source in self.events.iter().filter_map(|e| e.source.as_ref()) {
+            if !nodes.contains(source) {
+                nodes.push(source.clone());
+            }
+        }
+        nodes
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "TracingInfo(coordinator={:?}, duration={:?}, events={})",
+            self.coordinator,
+            self.duration,
+            self.events.len()
+        )
+    }
+}
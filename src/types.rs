@@ -1,10 +1,42 @@
+use num_bigint::BigInt;
+use pyo3::buffer::PyBuffer;
 use pyo3::conversion::IntoPyObjectExt;
 use pyo3::prelude::*;
-use pyo3::types::{PyBytes, PyDict, PyList};
-use scylla::value::CqlValue;
+use pyo3::types::{
+    PyBool, PyByteArray, PyBytes, PyDict, PyFloat, PyFrozenSet, PyInt, PyList, PyMemoryView, PySet,
+    PyString, PyTuple,
+};
+use scylla::frame::response::result::{CollectionType, ColumnType, NativeType};
+use scylla::serialize::SerializationError;
+use scylla::statement::prepared::PreparedStatement;
+use scylla::value::{
+    Counter, CqlDate, CqlDuration, CqlTime, CqlTimestamp, CqlTimeuuid, CqlValue, CqlVarint,
+};
 use std::collections::HashMap;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::converters;
+use crate::copy::parse_decimal;
+use crate::durations;
+use crate::floats::check_finite;
+use crate::integers::{integer_range, out_of_range, typed_integer};
+use crate::metadata::column_type_to_cql;
+
+// Proleptic Gregorian ordinal of 1970-01-01, as returned by date.toordinal()
+const EPOCH_ORDINAL: i64 = 719_163;
 
 pub fn cql_value_to_py(py: Python, value: &CqlValue) -> PyResult<Py<PyAny>> {
+    let converted = default_cql_value_to_py(py, value)?;
+    if converters::has_decoders() {
+        return converters::decode(py, value, converted);
+    }
+    Ok(converted)
+}
+
+fn default_cql_value_to_py(py: Python, value: &CqlValue) -> PyResult<Py<PyAny>> {
     match value {
         CqlValue::Ascii(s) | CqlValue::Text(s) => Ok(s.clone().into_bound_py_any(py)?.into()),
         CqlValue::Boolean(b) => Ok((*b).into_bound_py_any(py)?.into()),
@@ -19,7 +51,7 @@ pub fn cql_value_to_py(py: Python, value: &CqlValue) -> PyResult<Py<PyAny>> {
         CqlValue::Uuid(u) => Ok(u.to_string().into_bound_py_any(py)?.into()),
         CqlValue::Timeuuid(t) => Ok(t.to_string().into_bound_py_any(py)?.into()),
         CqlValue::Inet(addr) => Ok(addr.to_string().into_bound_py_any(py)?.into()),
-        CqlValue::List(list) => {
+        CqlValue::List(list) | CqlValue::Vector(list) => {
             let py_list = PyList::empty(py);
             for item in list {
                 py_list.append(cql_value_to_py(py, item)?)?;
@@ -43,13 +75,7 @@ pub fn cql_value_to_py(py: Python, value: &CqlValue) -> PyResult<Py<PyAny>> {
         CqlValue::Timestamp(ts) => Ok(ts.0.into_bound_py_any(py)?.into()),
         CqlValue::Date(d) => Ok(d.0.into_bound_py_any(py)?.into()),
         CqlValue::Time(t) => Ok(t.0.into_bound_py_any(py)?.into()),
-        CqlValue::Duration(d) => {
-            let dict = PyDict::new(py);
-            dict.set_item("months", d.months)?;
-            dict.set_item("days", d.days)?;
-            dict.set_item("nanoseconds", d.nanoseconds)?;
-            Ok(dict.into())
-        }
+        CqlValue::Duration(d) => durations::duration_to_py(py, d),
         CqlValue::Varint(v) => {
             // CqlVarint - use Debug representation since fields are private
             Ok(format!("{:?}", v).into_bound_py_any(py)?.into())
@@ -88,74 +114,25 @@ pub fn cql_value_to_py(py: Python, value: &CqlValue) -> PyResult<Py<PyAny>> {
     }
 }
 
-#[allow(dead_code)]
-pub fn py_to_cql_value(obj: &Bound<'_, PyAny>) -> PyResult<CqlValue> {
-    if obj.is_none() {
-        return Ok(CqlValue::Empty);
-    }
-
-    if let Ok(b) = obj.extract::<bool>() {
-        return Ok(CqlValue::Boolean(b));
-    }
-
-    if let Ok(i) = obj.extract::<i32>() {
-        return Ok(CqlValue::Int(i));
-    }
-
-    if let Ok(i) = obj.extract::<i64>() {
-        return Ok(CqlValue::BigInt(i));
-    }
-
-    if let Ok(f) = obj.extract::<f32>() {
-        return Ok(CqlValue::Float(f));
-    }
-
-    if let Ok(f) = obj.extract::<f64>() {
-        return Ok(CqlValue::Double(f));
-    }
-
-    if let Ok(s) = obj.extract::<String>() {
-        return Ok(CqlValue::Text(s));
-    }
-
-    if let Ok(b) = obj.extract::<Vec<u8>>() {
-        return Ok(CqlValue::Blob(b));
-    }
-
-    if let Ok(list) = obj.cast::<PyList>() {
-        let mut values = Vec::new();
-        for item in list.iter() {
-            values.push(py_to_cql_value(&item)?);
-        }
-        return Ok(CqlValue::List(values));
-    }
-
-    if let Ok(dict) = obj.cast::<PyDict>() {
-        let mut map = Vec::new();
-        for (key, val) in dict.iter() {
-            map.push((py_to_cql_value(&key)?, py_to_cql_value(&val)?));
-        }
-        return Ok(CqlValue::Map(map));
-    }
-
-    Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
-        "Cannot convert Python type {:?} to CQL value",
-        obj.get_type()
-    )))
+// What Enum members are bound as
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum EnumBinding {
+    #[default]
+    Value,
+    Name,
 }
 
-#[allow(dead_code)]
-pub fn py_dict_to_values(dict: Option<&Bound<'_, PyDict>>) -> PyResult<HashMap<String, CqlValue>> {
-    let mut values = HashMap::new();
-
-    if let Some(d) = dict {
-        for (key, val) in d.iter() {
-            let key_str = key.extract::<String>()?;
-            values.insert(key_str, py_to_cql_value(&val)?);
+impl EnumBinding {
+    pub fn parse(binding: &str) -> PyResult<Self> {
+        match binding {
+            "value" => Ok(EnumBinding::Value),
+            "name" => Ok(EnumBinding::Name),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid enum binding: {}. Must be 'value' or 'name'",
+                binding
+            ))),
         }
     }
-
-    Ok(values)
 }
 
 // Helper type that can hold different value types for serialization
@@ -165,17 +142,20 @@ pub enum SerializableValue {
     Bool(bool),
     Int(i32),
     BigInt(i64),
-    Float(f32),
     Double(f64),
     Text(String),
     Blob(Vec<u8>),
+    // Contiguous buffer of bytes, serialized from its memory without copying it first
+    Buffer(Arc<PyBuffer<u8>>),
     Timestamp(chrono::DateTime<chrono::Utc>),
     List(Vec<SerializableValue>),
     #[allow(dead_code)]
     Set(Vec<SerializableValue>),
-    // For maps, we use simpler types that scylla can handle directly
-    TextMap(HashMap<String, String>),
-    IntMap(HashMap<String, i64>),
+    // Already converted to the type of the column it is bound to
+    Cql(CqlValue),
+    // Converted once the column type is known, so registered encoders can be applied and
+    // collections take the types of the column
+    Python(Arc<Py<PyAny>>, EnumBinding),
 }
 
 impl scylla::serialize::value::SerializeValue for SerializableValue {
@@ -194,12 +174,41 @@ impl scylla::serialize::value::SerializeValue for SerializableValue {
                 )
             }
             SerializableValue::Bool(b) => b.serialize(_typ, writer),
+            // Counter increments are written as counters, whatever their magnitude
+            SerializableValue::Int(i) if _typ == &ColumnType::Native(NativeType::Counter) => {
+                Counter(*i as i64).serialize(_typ, writer)
+            }
+            SerializableValue::BigInt(i) if _typ == &ColumnType::Native(NativeType::Counter) => {
+                Counter(*i).serialize(_typ, writer)
+            }
+            // Integers may be written into float columns, e.g. as vector components
+            SerializableValue::Int(i) if _typ == &ColumnType::Native(NativeType::Float) => {
+                (*i as f32).serialize(_typ, writer)
+            }
             SerializableValue::Int(i) => i.serialize(_typ, writer),
             SerializableValue::BigInt(i) => i.serialize(_typ, writer),
-            SerializableValue::Float(f) => f.serialize(_typ, writer),
+            // Python floats are doubles, narrowed only when written into float columns
+            SerializableValue::Double(f) if _typ == &ColumnType::Native(NativeType::Float) => {
+                (*f as f32).serialize(_typ, writer)
+            }
+            // Floats bound to timestamp columns are seconds since the epoch
+            SerializableValue::Double(f) if _typ == &ColumnType::Native(NativeType::Timestamp) => {
+                scylla::value::CqlTimestamp((f * 1000.0).round() as i64).serialize(_typ, writer)
+            }
             SerializableValue::Double(f) => f.serialize(_typ, writer),
             SerializableValue::Text(s) => s.serialize(_typ, writer),
             SerializableValue::Blob(b) => b.serialize(_typ, writer),
+            SerializableValue::Buffer(buffer) => Python::attach(|_| {
+                let bytes = match buffer.len_bytes() {
+                    0 => &[][..],
+                    // SAFETY: the buffer is C-contiguous and stays exported, so its memory can't
+                    // be freed or resized, until the value is dropped
+                    len => unsafe {
+                        std::slice::from_raw_parts(buffer.buf_ptr() as *const u8, len)
+                    },
+                };
+                bytes.serialize(_typ, writer)
+            }),
             SerializableValue::Timestamp(dt) => {
                 // Convert to CqlTimestamp (milliseconds since epoch)
                 let timestamp = scylla::value::CqlTimestamp(dt.timestamp_millis());
@@ -210,14 +219,450 @@ impl scylla::serialize::value::SerializeValue for SerializableValue {
                 // Sets are serialized as lists in scylla
                 items.serialize(_typ, writer)
             }
-            SerializableValue::TextMap(map) => map.serialize(_typ, writer),
-            SerializableValue::IntMap(map) => map.serialize(_typ, writer),
+            SerializableValue::Cql(value) => value.serialize(_typ, writer),
+            SerializableValue::Python(value, enums) => {
+                Python::attach(|py| py_value_to_typed(value.bind(py), _typ, *enums))
+                    .map_err(SerializationError::new)?
+                    .serialize(_typ, writer)
+            }
+        }
+    }
+}
+
+fn bind_error(val: &Bound<'_, PyAny>, typ: &ColumnType) -> PyErr {
+    let type_name = val
+        .get_type()
+        .name()
+        .map(|name| name.to_string())
+        .unwrap_or_default();
+    PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+        "Cannot bind a value of type {} to a {} column",
+        type_name,
+        column_type_to_cql(typ)
+    ))
+}
+
+fn invalid_value(val: &Bound<'_, PyAny>, typ: &ColumnType) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+        "Invalid {} value: {}",
+        column_type_to_cql(typ),
+        val
+    ))
+}
+
+// Overflow errors are kept, anything else means the Python type does not fit the column
+fn extract_typed<'py, T>(val: &Bound<'py, PyAny>, typ: &ColumnType) -> PyResult<T>
+where
+    T: for<'a> FromPyObject<'a, 'py, Error = PyErr>,
+{
+    val.extract::<T>().map_err(|err| {
+        if !err.is_instance_of::<pyo3::exceptions::PyOverflowError>(val.py()) {
+            return bind_error(val, typ);
         }
+        let cql_type = column_type_to_cql(typ);
+        match integer_range(&cql_type) {
+            Some((min, max)) => out_of_range(val, &cql_type, min, max),
+            None => err,
+        }
+    })
+}
+
+pub(crate) fn is_instance_of_class(
+    val: &Bound<'_, PyAny>,
+    module: &str,
+    class: &str,
+) -> PyResult<bool> {
+    val.is_instance(&val.py().import(module)?.getattr(class)?)
+}
+
+// Naive datetimes are taken to be in UTC
+pub(crate) fn datetime_to_micros(val: &Bound<'_, PyAny>) -> PyResult<i64> {
+    let datetime = val.py().import("datetime")?;
+    let utc = datetime.getattr("timezone")?.getattr("utc")?;
+    let val = if val.getattr("tzinfo")?.is_none() {
+        let kwargs = PyDict::new(val.py());
+        kwargs.set_item("tzinfo", &utc)?;
+        val.call_method("replace", (), Some(&kwargs))?
+    } else {
+        val.clone()
+    };
+    let epoch = datetime
+        .getattr("datetime")?
+        .call1((1970, 1, 1, 0, 0, 0, 0, utc))?;
+    let kwargs = PyDict::new(val.py());
+    kwargs.set_item("microseconds", 1)?;
+    let microsecond = datetime.getattr("timedelta")?.call((), Some(&kwargs))?;
+    val.sub(epoch)?.floor_div(microsecond)?.extract()
+}
+
+pub(crate) fn datetime_to_millis(val: &Bound<'_, PyAny>) -> PyResult<i64> {
+    Ok(datetime_to_micros(val)?.div_euclid(1000))
+}
+
+// Sequences, sets and arrays can be bound to list, set and vector columns
+pub(crate) fn py_items<'py>(val: &Bound<'py, PyAny>) -> PyResult<Option<Vec<Bound<'py, PyAny>>>> {
+    if val.cast::<PyList>().is_ok()
+        || val.cast::<PyTuple>().is_ok()
+        || val.cast::<PySet>().is_ok()
+        || val.cast::<PyFrozenSet>().is_ok()
+    {
+        return Ok(Some(val.try_iter()?.collect::<PyResult<_>>()?));
+    }
+    match array_to_list(val)? {
+        Some(list) => py_items(&list),
+        None => Ok(None),
     }
 }
 
+fn py_elements_to_cql(
+    val: &Bound<'_, PyAny>,
+    typ: &ColumnType,
+    element_type: &ColumnType,
+    enums: EnumBinding,
+) -> PyResult<Vec<CqlValue>> {
+    let items = py_items(val)?.ok_or_else(|| bind_error(val, typ))?;
+    items
+        .iter()
+        .map(|item| py_element_to_cql(item, element_type, enums))
+        .collect()
+}
+
+fn py_element_to_cql(
+    val: &Bound<'_, PyAny>,
+    typ: &ColumnType,
+    enums: EnumBinding,
+) -> PyResult<CqlValue> {
+    py_value_to_cql(val, typ, enums)?.ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>("Collections cannot contain None")
+    })
+}
+
+fn py_native_to_cql(
+    val: &Bound<'_, PyAny>,
+    typ: &ColumnType,
+    native: &NativeType,
+) -> PyResult<CqlValue> {
+    let value = match native {
+        NativeType::Ascii => CqlValue::Ascii(extract_typed(val, typ)?),
+        NativeType::Text => CqlValue::Text(extract_typed(val, typ)?),
+        NativeType::Boolean => CqlValue::Boolean(extract_typed(val, typ)?),
+        NativeType::TinyInt => CqlValue::TinyInt(extract_typed(val, typ)?),
+        NativeType::SmallInt => CqlValue::SmallInt(extract_typed(val, typ)?),
+        NativeType::Int => CqlValue::Int(extract_typed(val, typ)?),
+        NativeType::BigInt => CqlValue::BigInt(extract_typed(val, typ)?),
+        NativeType::Counter => CqlValue::Counter(Counter(extract_typed(val, typ)?)),
+        NativeType::Float => {
+            let value: f32 = extract_typed(val, typ)?;
+            check_finite(value as f64, "float")?;
+            CqlValue::Float(value)
+        }
+        NativeType::Double => {
+            let value: f64 = extract_typed(val, typ)?;
+            check_finite(value, "double")?;
+            CqlValue::Double(value)
+        }
+        NativeType::Varint => {
+            if val.cast::<PyInt>().is_err() && val.cast::<PyString>().is_err() {
+                return Err(bind_error(val, typ));
+            }
+            let digits = BigInt::from_str(val.str()?.to_str()?.trim())
+                .map_err(|_| invalid_value(val, typ))?;
+            CqlValue::Varint(CqlVarint::from_signed_bytes_be(digits.to_signed_bytes_be()))
+        }
+        NativeType::Decimal => {
+            let is_number = val.cast::<PyInt>().is_ok()
+                || val.cast::<PyFloat>().is_ok()
+                || val.cast::<PyString>().is_ok()
+                || is_instance_of_class(val, "decimal", "Decimal")?;
+            if !is_number {
+                return Err(bind_error(val, typ));
+            }
+            let decimal = parse_decimal(val.str()?.to_str()?.trim())
+                .ok_or_else(|| invalid_value(val, typ))?;
+            CqlValue::Decimal(decimal)
+        }
+        NativeType::Blob => match val.cast::<PyBytes>() {
+            Ok(bytes) => CqlValue::Blob(bytes.as_bytes().to_vec()),
+            Err(_) if val.cast::<PyString>().is_ok() => return Err(bind_error(val, typ)),
+            Err(_) => match PyBuffer::<u8>::get(val) {
+                Ok(buffer) => CqlValue::Blob(buffer.to_vec(val.py())?),
+                Err(_) => CqlValue::Blob(extract_typed(val, typ)?),
+            },
+        },
+        // Integers are milliseconds since the epoch, as timestamps are read back
+        NativeType::Timestamp => {
+            let millis = if is_instance_of_class(val, "datetime", "datetime")? {
+                datetime_to_millis(val)?
+            } else if let Ok(seconds) = val.cast::<PyFloat>() {
+                (seconds.value() * 1000.0).round() as i64
+            } else {
+                extract_typed(val, typ)?
+            };
+            CqlValue::Timestamp(CqlTimestamp(millis))
+        }
+        // Integers are days since the epoch
+        NativeType::Date => {
+            let days = if is_instance_of_class(val, "datetime", "date")? {
+                val.call_method0("toordinal")?.extract::<i64>()? - EPOCH_ORDINAL
+            } else {
+                extract_typed::<i32>(val, typ)? as i64
+            };
+            // Dates are stored as days since the epoch, centered at 2^31
+            CqlValue::Date(CqlDate((days + (1 << 31)) as u32))
+        }
+        // Integers are nanoseconds since midnight, as times are read back
+        NativeType::Time => {
+            if is_instance_of_class(val, "datetime", "time")? {
+                let seconds = val.getattr("hour")?.extract::<i64>()? * 3600
+                    + val.getattr("minute")?.extract::<i64>()? * 60
+                    + val.getattr("second")?.extract::<i64>()?;
+                let micros = val.getattr("microsecond")?.extract::<i64>()?;
+                CqlValue::Time(CqlTime(seconds * 1_000_000_000 + micros * 1000))
+            } else {
+                CqlValue::Time(CqlTime(extract_typed(val, typ)?))
+            }
+        }
+        NativeType::Uuid | NativeType::Timeuuid => {
+            if val.cast::<PyString>().is_err() && !is_instance_of_class(val, "uuid", "UUID")? {
+                return Err(bind_error(val, typ));
+            }
+            let uuid = Uuid::parse_str(val.str()?.to_str()?.trim())
+                .map_err(|_| invalid_value(val, typ))?;
+            if *native == NativeType::Uuid {
+                CqlValue::Uuid(uuid)
+            } else {
+                CqlValue::Timeuuid(CqlTimeuuid::from(uuid))
+            }
+        }
+        NativeType::Inet => {
+            let is_address = val.cast::<PyString>().is_ok()
+                || is_instance_of_class(val, "ipaddress", "IPv4Address")?
+                || is_instance_of_class(val, "ipaddress", "IPv6Address")?;
+            if !is_address {
+                return Err(bind_error(val, typ));
+            }
+            let addr = val.str()?.to_str()?.trim().parse::<IpAddr>();
+            CqlValue::Inet(addr.map_err(|_| invalid_value(val, typ))?)
+        }
+        // Durations are bound from the dict they are read back as, or from a timedelta
+        NativeType::Duration => {
+            if is_instance_of_class(val, "datetime", "timedelta")? {
+                return Ok(CqlValue::Duration(durations::timedelta_to_duration(val)?));
+            }
+            let dict = val.cast::<PyDict>().map_err(|_| bind_error(val, typ))?;
+            let field = |name: &str| -> PyResult<Bound<'_, PyAny>> {
+                dict.get_item(name)?.ok_or_else(|| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Duration is missing {}",
+                        name
+                    ))
+                })
+            };
+            CqlValue::Duration(CqlDuration {
+                months: field("months")?.extract()?,
+                days: field("days")?.extract()?,
+                nanoseconds: field("nanoseconds")?.extract()?,
+            })
+        }
+        _ => return Err(bind_error(val, typ)),
+    };
+    Ok(value)
+}
+
+// Converts a value bound to a column of type `typ`, so the declared type decides the
+// CQL representation rather than the Python type. Registered encoders run first, at
+// every level of collections and user types.
+pub fn py_value_to_cql(
+    val: &Bound<'_, PyAny>,
+    typ: &ColumnType,
+    enums: EnumBinding,
+) -> PyResult<Option<CqlValue>> {
+    if val.is_none() {
+        return Ok(None);
+    }
+    let encoded;
+    let val = if converters::has_encoders() {
+        encoded = converters::encode(val, typ)?;
+        if encoded.is_none() {
+            return Ok(None);
+        }
+        &encoded
+    } else {
+        val
+    };
+    let plain;
+    let val = match plain_value(val, enums)? {
+        Some(value) if value.is_none() => return Ok(None),
+        Some(value) => {
+            plain = value;
+            &plain
+        }
+        None => val,
+    };
+
+    let value = match typ {
+        ColumnType::Native(native) => py_native_to_cql(val, typ, native)?,
+        ColumnType::Collection {
+            typ: collection, ..
+        } => match collection {
+            CollectionType::List(element_type) => {
+                CqlValue::List(py_elements_to_cql(val, typ, element_type, enums)?)
+            }
+            CollectionType::Set(element_type) => {
+                CqlValue::Set(py_elements_to_cql(val, typ, element_type, enums)?)
+            }
+            CollectionType::Map(key_type, value_type) => {
+                let dict = val.cast::<PyDict>().map_err(|_| bind_error(val, typ))?;
+                let mut entries = Vec::with_capacity(dict.len());
+                for (key, value) in dict.iter() {
+                    entries.push((
+                        py_element_to_cql(&key, key_type, enums)?,
+                        py_element_to_cql(&value, value_type, enums)?,
+                    ));
+                }
+                CqlValue::Map(entries)
+            }
+            _ => return Err(bind_error(val, typ)),
+        },
+        ColumnType::Vector {
+            typ: element_type,
+            dimensions,
+        } => {
+            let items = py_elements_to_cql(val, typ, element_type, enums)?;
+            if items.len() != *dimensions as usize {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Expected {} items for a {} column, got {}",
+                    dimensions,
+                    column_type_to_cql(typ),
+                    items.len()
+                )));
+            }
+            CqlValue::Vector(items)
+        }
+        ColumnType::Tuple(element_types) => {
+            if val.cast::<PyList>().is_err() && val.cast::<PyTuple>().is_err() {
+                return Err(bind_error(val, typ));
+            }
+            let items = val.try_iter()?.collect::<PyResult<Vec<_>>>()?;
+            if items.len() != element_types.len() {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Expected {} items for a {} column, got {}",
+                    element_types.len(),
+                    column_type_to_cql(typ),
+                    items.len()
+                )));
+            }
+            CqlValue::Tuple(
+                items
+                    .iter()
+                    .zip(element_types)
+                    .map(|(item, element_type)| py_value_to_cql(item, element_type, enums))
+                    .collect::<PyResult<_>>()?,
+            )
+        }
+        // Fields missing from the dict are bound as null
+        ColumnType::UserDefinedType { definition, .. } => {
+            let dict = val.cast::<PyDict>().map_err(|_| bind_error(val, typ))?;
+            for key in dict.keys() {
+                let key = key.extract::<String>()?;
+                if !definition.field_types.iter().any(|(name, _)| *name == key) {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Unknown field {} for user type {}.{}",
+                        key, definition.keyspace, definition.name
+                    )));
+                }
+            }
+            let mut fields = Vec::with_capacity(definition.field_types.len());
+            for (name, field_type) in &definition.field_types {
+                let value = match dict.get_item(name.as_ref())? {
+                    Some(value) => py_value_to_cql(&value, field_type, enums)?,
+                    None => None,
+                };
+                fields.push((name.to_string(), value));
+            }
+            CqlValue::UserDefinedType {
+                keyspace: definition.keyspace.to_string(),
+                name: definition.name.to_string(),
+                fields,
+            }
+        }
+        _ => return Err(bind_error(val, typ)),
+    };
+    Ok(Some(value))
+}
+
+fn py_value_to_typed(
+    val: &Bound<'_, PyAny>,
+    typ: &ColumnType,
+    enums: EnumBinding,
+) -> PyResult<SerializableValue> {
+    if typ == &ColumnType::Native(NativeType::Blob) && !converters::has_encoders() {
+        if let Some(buffer) = py_buffer(val)? {
+            return Ok(buffer);
+        }
+    }
+    Ok(match py_value_to_cql(val, typ, enums)? {
+        Some(value) => SerializableValue::Cql(value),
+        None => SerializableValue::Null,
+    })
+}
+
+// Values deferred to serialization time, when the column type is known, if encoders are registered
+fn py_value_to_untyped(val: &Bound<'_, PyAny>, enums: EnumBinding) -> PyResult<SerializableValue> {
+    if converters::has_encoders() && !val.is_none() {
+        return Ok(SerializableValue::Python(
+            Arc::new(val.clone().unbind()),
+            enums,
+        ));
+    }
+    py_value_to_serializable(val, enums)
+}
+
+// Bind marker names of a prepared statement with the types of the columns they bind to
+pub fn bind_columns(prepared: &PreparedStatement) -> Vec<(&str, &ColumnType<'static>)> {
+    prepared
+        .get_variable_col_specs()
+        .iter()
+        .map(|spec| (spec.name(), spec.typ()))
+        .collect()
+}
+
+// Values for the named columns are converted to the columns' types, others are guessed from
+// their Python types
+pub fn py_dict_to_typed_values(
+    dict: Option<&Bound<'_, PyDict>>,
+    columns: &[(&str, &ColumnType)],
+    enums: EnumBinding,
+) -> PyResult<HashMap<String, SerializableValue>> {
+    let mut serialized = HashMap::new();
+
+    if let Some(d) = dict {
+        for (key, val) in d.iter() {
+            let key_str = key.extract::<String>()?;
+            let typ = columns
+                .iter()
+                .find(|(name, _)| *name == key_str)
+                .map(|(_, typ)| *typ);
+            let scylla_val = match typ {
+                Some(typ) => py_value_to_typed(&val, typ, enums).map_err(|err| {
+                    let py = val.py();
+                    PyErr::from_type(
+                        err.get_type(py),
+                        format!("Column {}: {}", key_str, err.value(py)),
+                    )
+                })?,
+                None => py_value_to_untyped(&val, enums)?,
+            };
+            serialized.insert(key_str, scylla_val);
+        }
+    }
+
+    Ok(serialized)
+}
+
 pub fn py_dict_to_serialized_values(
     dict: Option<&Bound<'_, PyDict>>,
+    enums: EnumBinding,
 ) -> PyResult<HashMap<String, SerializableValue>> {
     let mut serialized = HashMap::new();
 
@@ -226,7 +671,7 @@ pub fn py_dict_to_serialized_values(
             let key_str = key.extract::<String>()?;
 
             // Convert Python value to SerializableValue
-            let scylla_val = py_value_to_serializable(&val)?;
+            let scylla_val = py_value_to_untyped(&val, enums)?;
 
             serialized.insert(key_str, scylla_val);
         }
@@ -235,10 +680,20 @@ pub fn py_dict_to_serialized_values(
     Ok(serialized)
 }
 
-fn py_value_to_serializable(val: &Bound<'_, PyAny>) -> PyResult<SerializableValue> {
+fn py_value_to_serializable(
+    val: &Bound<'_, PyAny>,
+    enums: EnumBinding,
+) -> PyResult<SerializableValue> {
     if val.is_none() {
         return Ok(SerializableValue::Null);
     }
+    if let Some(value) = plain_value(val, enums)? {
+        return py_value_to_serializable(&value, enums);
+    }
+
+    if let Some(value) = typed_integer(val) {
+        return Ok(SerializableValue::Cql(value));
+    }
 
     // Try bool first (before int, as bool is a subclass of int in Python)
     if let Ok(b) = val.extract::<bool>() {
@@ -275,26 +730,13 @@ fn py_value_to_serializable(val: &Bound<'_, PyAny>) -> PyResult<SerializableValu
         }
         return Ok(SerializableValue::BigInt(i));
     }
-
-    // Try float types
-    if let Ok(f) = val.extract::<f32>() {
-        return Ok(SerializableValue::Float(f));
+    if val.cast::<PyInt>().is_ok() {
+        return Err(out_of_range(val, "bigint", i64::MIN, i64::MAX));
     }
+
+    // Floats are kept as doubles, so NaN, infinities and -0.0 reach the column unchanged
     if let Ok(f) = val.extract::<f64>() {
-        // Check if this is a timestamp (common pattern in Python with datetime.timestamp())
-        // If it's a reasonable timestamp value (between 1970 and 2100), treat it as timestamp
-        if f > 0.0 && f < 4_102_444_800.0 {
-            // Between 1970 and 2100
-            // Convert to datetime
-            use chrono::TimeZone;
-            let dt = chrono::Utc
-                .timestamp_opt(f as i64, (f.fract() * 1_000_000_000.0) as u32)
-                .single()
-                .ok_or_else(|| {
-                    PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid timestamp value")
-                })?;
-            return Ok(SerializableValue::Timestamp(dt));
-        }
+        check_finite(f, "double")?;
         return Ok(SerializableValue::Double(f));
     }
 
@@ -304,6 +746,14 @@ fn py_value_to_serializable(val: &Bound<'_, PyAny>) -> PyResult<SerializableValu
     }
 
     // Try bytes/blob
+    if val.cast::<PyBytes>().is_ok()
+        || val.cast::<PyByteArray>().is_ok()
+        || val.cast::<PyMemoryView>().is_ok()
+    {
+        if let Some(buffer) = py_buffer(val)? {
+            return Ok(buffer);
+        }
+    }
     if let Ok(b) = val.extract::<Vec<u8>>() {
         return Ok(SerializableValue::Blob(b));
     }
@@ -312,56 +762,26 @@ fn py_value_to_serializable(val: &Bound<'_, PyAny>) -> PyResult<SerializableValu
     if let Ok(list) = val.cast::<PyList>() {
         let mut items = Vec::new();
         for item in list.iter() {
-            items.push(py_value_to_serializable(&item)?);
+            items.push(py_value_to_serializable(&item, enums)?);
         }
         return Ok(SerializableValue::List(items));
     }
 
-    // Try dict (as map)
-    if let Ok(dict) = val.cast::<PyDict>() {
-        // Try to detect if it's a text map or int map
-        let mut text_map: HashMap<String, String> = HashMap::new();
-        let mut int_map: HashMap<String, i64> = HashMap::new();
-        let mut is_text_map = true;
-        let mut is_int_map = true;
-
-        for (k, v) in dict.iter() {
-            let key_str = k.extract::<String>().ok();
-
-            if let Some(key) = &key_str {
-                if is_text_map {
-                    if let Ok(val_str) = v.extract::<String>() {
-                        text_map.insert(key.clone(), val_str);
-                    } else {
-                        is_text_map = false;
-                    }
-                }
-
-                if is_int_map {
-                    if let Ok(val_int) = v.extract::<i64>() {
-                        int_map.insert(key.clone(), val_int);
-                    } else {
-                        is_int_map = false;
-                    }
-                }
-            } else {
-                is_text_map = false;
-                is_int_map = false;
-            }
-
-            if !is_text_map && !is_int_map {
-                break;
-            }
-        }
-
-        if is_text_map && !text_map.is_empty() {
-            return Ok(SerializableValue::TextMap(text_map));
-        } else if is_int_map && !int_map.is_empty() {
-            return Ok(SerializableValue::IntMap(int_map));
-        }
+    if let Some(list) = array_to_list(val)? {
+        return py_value_to_serializable(&list, enums);
+    }
 
-        // If neither worked, return an empty text map as fallback
-        return Ok(SerializableValue::TextMap(HashMap::new()));
+    // Maps, sets and timedeltas are converted once the column type is known, so keys, values
+    // and elements of any CQL type can be bound, at any depth
+    if val.cast::<PyDict>().is_ok()
+        || val.cast::<PySet>().is_ok()
+        || val.cast::<PyFrozenSet>().is_ok()
+        || is_instance_of_class(val, "datetime", "timedelta")?
+    {
+        return Ok(SerializableValue::Python(
+            Arc::new(val.clone().unbind()),
+            enums,
+        ));
     }
 
     Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
@@ -369,3 +789,63 @@ fn py_value_to_serializable(val: &Bound<'_, PyAny>) -> PyResult<SerializableValu
         val.get_type()
     )))
 }
+
+// bytes, bytearray, memoryview and other objects exposing their bytes through the buffer
+// protocol. Contiguous buffers are kept and written as they are, others are copied.
+fn py_buffer(val: &Bound<'_, PyAny>) -> PyResult<Option<SerializableValue>> {
+    if val.cast::<PyString>().is_ok() {
+        return Ok(None);
+    }
+    let Ok(buffer) = PyBuffer::<u8>::get(val) else {
+        return Ok(None);
+    };
+    if buffer.is_c_contiguous() {
+        return Ok(Some(SerializableValue::Buffer(Arc::new(buffer))));
+    }
+    Ok(Some(SerializableValue::Blob(buffer.to_vec(val.py())?)))
+}
+
+// numpy arrays (and array.array) are bound as the list of their items, e.g. into vector columns
+fn array_to_list<'py>(obj: &Bound<'py, PyAny>) -> PyResult<Option<Bound<'py, PyAny>>> {
+    if obj.cast::<PyList>().is_ok() || !obj.hasattr("tolist")? {
+        return Ok(None);
+    }
+    let list = obj.call_method0("tolist")?;
+    Ok(list.cast::<PyList>().is_ok().then_some(list))
+}
+
+// Enum members are bound as their value or name, numpy scalars as the Python value they
+// hold, and numpy and pandas NaT as null. pandas Timestamps are datetimes already.
+pub(crate) fn plain_value<'py>(
+    val: &Bound<'py, PyAny>,
+    enums: EnumBinding,
+) -> PyResult<Option<Bound<'py, PyAny>>> {
+    if val.is_exact_instance_of::<PyInt>()
+        || val.is_exact_instance_of::<PyFloat>()
+        || val.is_exact_instance_of::<PyString>()
+        || val.is_exact_instance_of::<PyBool>()
+    {
+        return Ok(None);
+    }
+    if is_instance_of_class(val, "enum", "Enum")? {
+        return Ok(Some(match enums {
+            EnumBinding::Value => val.getattr("value")?,
+            EnumBinding::Name => val.getattr("name")?,
+        }));
+    }
+    let typ = val.get_type();
+    let module = typ.module()?;
+    let module = module.to_str()?;
+    if module.starts_with("pandas") && typ.name()?.to_str()? == "NaTType" {
+        return Ok(Some(val.py().None().into_bound(val.py())));
+    }
+    if module != "numpy" || !is_instance_of_class(val, "numpy", "generic")? {
+        return Ok(None);
+    }
+    // Nanosecond datetimes would come out of item() as integers
+    if is_instance_of_class(val, "numpy", "datetime64")? {
+        let micros = val.call_method1("astype", ("datetime64[us]",))?;
+        return Ok(Some(micros.call_method0("item")?));
+    }
+    Ok(Some(val.call_method0("item")?))
+}
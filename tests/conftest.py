@@ -12,7 +12,7 @@ import pytest
 sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
 
 try:
-    from rsylla import Session
+    from rsylla import AsyncSession
 
     RSYLLA_AVAILABLE = True
 except ImportError:
@@ -55,7 +55,7 @@ async def wait_for_scylla(scylla_connection_string):
 
     for attempt in range(max_retries):
         try:
-            session = await Session.connect([scylla_connection_string])
+            session = await AsyncSession.connect([scylla_connection_string])
             # Try a simple query
             await session.execute("SELECT now() FROM system.local")
             print(f"\nScyllaDB is ready at {scylla_connection_string}")
@@ -75,7 +75,7 @@ async def wait_for_scylla(scylla_connection_string):
 @pytest.fixture(scope="session")
 async def session(scylla_connection_string, wait_for_scylla):
     """Create a session for testing"""
-    session = await Session.connect([scylla_connection_string])
+    session = await AsyncSession.connect([scylla_connection_string])
     yield session
     # Session cleanup happens automatically
 
@@ -50,8 +50,49 @@ class TestBatch:
         result = await session.execute("SELECT * FROM users WHERE id = ?", {"id": 510})
         assert len(result) == 1
 
-    # REMOVED: test_counter_batch - fails due to counter type serialization with named parameters
-    # Counter columns require i64 but small values are serialized as i32
+    async def test_counter_batch(self, session, test_keyspace):
+        """Test counter batch"""
+        await session.execute(
+            "CREATE TABLE IF NOT EXISTS page_stats "
+            "(page text PRIMARY KEY, views counter, likes counter)"
+        )
+        await session.execute("TRUNCATE page_stats")
+
+        batch = Batch.counter()
+        assert batch.batch_type == "counter"
+        batch.append_statement("UPDATE page_stats SET views = views + ? WHERE page = ?")
+        batch.append_statement(
+            "UPDATE page_stats SET views = views + ?, likes = likes - ? WHERE page = ?"
+        )
+
+        await session.batch(
+            batch,
+            [
+                {"views": 3, "page": "/home"},
+                {"views": 2, "likes": 1, "page": "/home"},
+            ],
+        )
+
+        result = await session.execute(
+            "SELECT views, likes FROM page_stats WHERE page = ?", {"page": "/home"}
+        )
+        row = result.first_row()
+        assert row[0] == 5
+        assert row[1] == -1
+
+    def test_counter_batch_rejects_other_statements(self):
+        """Test counter batches only accept counter mutations"""
+        batch = Batch("counter")
+        batch.append_statement("DELETE FROM page_stats WHERE page = ?")
+        with pytest.raises(ValueError):
+            batch.append_statement("INSERT INTO page_stats (page, views) VALUES (?, ?)")
+        with pytest.raises(ValueError):
+            batch.append_statement("UPDATE page_stats SET views = ? WHERE page = ?")
+        assert batch.statements_count() == 1
+
+        logged = Batch("logged")
+        logged.append_statement("INSERT INTO users (id) VALUES (?)")
+        assert logged.batch_type == "logged"
 
     async def test_batch_with_prepared(self, session, users_table):
         """Test batch with prepared statements"""
@@ -101,6 +142,34 @@ class TestBatch:
         result = await session.execute("SELECT * FROM users WHERE id = ?", {"id": 540})
         assert len(result) == 1
 
+    async def test_batch_options(self, session, users_table):
+        """Test batch-level consistency, timestamp and timeout"""
+        timestamp = 1_700_000_000_000_000
+        batch = Batch(
+            "logged",
+            consistency="QUORUM",
+            serial_consistency="LOCAL_SERIAL",
+            timestamp=timestamp,
+            timeout_ms=5000,
+        )
+        assert batch.consistency == "QUORUM"
+        assert batch.serial_consistency == "LOCAL_SERIAL"
+        assert batch.timestamp == timestamp
+        assert batch.timeout_ms == 5000
+        assert Batch().consistency is None
+        assert Batch().with_timeout(250).timeout_ms == 250
+
+        batch.append_statement("INSERT INTO users (id, username) VALUES (?, ?)")
+        await session.batch(batch, [{"id": 545, "username": "opts"}])
+
+        result = await session.execute(
+            "SELECT WRITETIME(username) FROM users WHERE id = ?", {"id": 545}
+        )
+        assert result.first_row()[0] == timestamp
+
+        with pytest.raises(ValueError):
+            Batch(consistency="MOST")
+
     async def test_batch_idempotency(self, session, users_table):
         """Test batch idempotency"""
         batch = Batch("unlogged")
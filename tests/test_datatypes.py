@@ -2,10 +2,17 @@
 Tests for data type conversions
 """
 
+import array
+import enum
+import math
 import time
+import uuid
+from datetime import datetime, timedelta, timezone
 
 import pytest
 
+import rsylla
+
 
 @pytest.mark.integration
 class TestDataTypes:
@@ -106,6 +113,24 @@ class TestDataTypes:
 
         assert row[1] == binary_data
 
+        # bytearray, memoryview and slices of them, unprepared and prepared
+        payload = bytearray(range(256)) * 4096
+        insert = "INSERT INTO test_blob (id, blob_val) VALUES (?, ?)"
+        prepared = await session.prepare(insert)
+        await session.execute(insert, {"id": 2, "blob_val": payload})
+        result = await session.execute(insert, {"id": 3, "blob_val": memoryview(payload)[256:]})
+        assert result.execution_info().request_size == len(insert) + 8 + 4 + len(payload) - 256
+        await session.execute_prepared(prepared, {"id": 4, "blob_val": memoryview(payload)[::2]})
+        await session.execute_prepared(prepared, {"id": 5, "blob_val": array.array("B", b"\x07")})
+        await session.execute_prepared(prepared, {"id": 6, "blob_val": bytearray()})
+
+        select = "SELECT blob_val FROM test_blob WHERE id = ?"
+        assert (await session.execute(select, {"id": 2})).first_row()[0] == bytes(payload)
+        assert (await session.execute(select, {"id": 3})).first_row()[0] == bytes(payload[256:])
+        assert (await session.execute(select, {"id": 4})).first_row()[0] == bytes(payload[::2])
+        assert (await session.execute(select, {"id": 5})).first_row()[0] == b"\x07"
+        assert (await session.execute(select, {"id": 6})).first_row()[0] == b""
+
         await session.execute("DROP TABLE IF EXISTS test_blob")
 
     async def test_timestamp_type(self, session, test_keyspace):
@@ -253,3 +278,505 @@ class TestDataTypes:
 
     # REMOVED: test_counter_type - fails due to counter type serialization with named parameters
     # Counter columns require i64 but small values are serialized as i32
+
+    async def test_vector_type(self, session, test_keyspace):
+        """Test vector type conversion"""
+        await session.execute(
+            """
+            CREATE TABLE IF NOT EXISTS test_vector (
+                id int PRIMARY KEY,
+                embedding vector<float, 3>
+            )
+        """
+        )
+
+        await session.await_schema_agreement()
+
+        await session.execute(
+            "INSERT INTO test_vector (id, embedding) VALUES (?, ?)",
+            {"id": 1, "embedding": [0.5, 1, -2.25]},
+        )
+        prepared = await session.prepare("INSERT INTO test_vector (id, embedding) VALUES (?, ?)")
+        await session.execute_prepared(
+            prepared, {"id": 2, "embedding": array.array("f", [1.5, 0.0, 4.0])}
+        )
+
+        result = await session.execute("SELECT embedding FROM test_vector WHERE id = ?", {"id": 1})
+        assert result.first_row()[0] == [0.5, 1.0, -2.25]
+        result = await session.execute("SELECT embedding FROM test_vector WHERE id = ?", {"id": 2})
+        assert result.first_row()[0] == [1.5, 0.0, 4.0]
+
+        with pytest.raises(Exception):
+            await session.execute_prepared(prepared, {"id": 3, "embedding": [1.0, 2.0]})
+
+        await session.execute("DROP TABLE IF EXISTS test_vector")
+
+    async def test_custom_converters(self, session, test_keyspace):
+        """Test user-registered encoders and decoders"""
+        await session.execute(
+            """
+            CREATE TABLE IF NOT EXISTS test_converters (
+                id int PRIMARY KEY,
+                price int,
+                history list<int>,
+                color ascii
+            )
+        """
+        )
+
+        await session.await_schema_agreement()
+
+        class Money:
+            def __init__(self, cents):
+                self.cents = cents
+
+            def __eq__(self, other):
+                return isinstance(other, Money) and other.cents == self.cents
+
+        class Color(enum.Enum):
+            RED = "red"
+            BLUE = "blue"
+
+        rsylla.register_converter(
+            "int",
+            encode=lambda value: value.cents if isinstance(value, Money) else value,
+            decode=Money,
+        )
+        rsylla.register_converter("ascii", encode=lambda color: color.value, decode=Color)
+        try:
+            await session.execute(
+                "INSERT INTO test_converters (id, price, history, color) VALUES (?, ?, ?, ?)",
+                {"id": 1, "price": Money(1999), "history": [Money(2499), 2199], "color": Color.RED},
+            )
+            result = await session.execute(
+                "SELECT price, history, color FROM test_converters WHERE id = ?", {"id": 1}
+            )
+            assert result.first_row().columns() == [
+                Money(1999),
+                [Money(2499), Money(2199)],
+                Color.RED,
+            ]
+        finally:
+            assert rsylla.unregister_converter("int")
+            assert rsylla.unregister_converter("ascii")
+        assert not rsylla.unregister_converter("ascii")
+
+        result = await session.execute("SELECT price FROM test_converters WHERE id = ?", {"id": 1})
+        assert result.first_row()[0] == 1999
+
+        with pytest.raises(ValueError):
+            rsylla.register_converter("list<int>", decode=list)
+        with pytest.raises(ValueError):
+            rsylla.register_converter("int")
+
+        await session.execute("DROP TABLE IF EXISTS test_converters")
+
+    async def test_prepared_typed_binding(self, session, test_keyspace):
+        """Test that prepared statements bind values by the declared column types"""
+        await session.execute(
+            "CREATE TYPE IF NOT EXISTS typed_point (x double, y double, label text)"
+        )
+        await session.execute(
+            """
+            CREATE TABLE IF NOT EXISTS test_typed (
+                id bigint PRIMARY KEY,
+                tiny tinyint,
+                small smallint,
+                big varint,
+                ratio float,
+                tags set<text>,
+                scores map<text, smallint>,
+                point frozen<typed_point>,
+                created timestamp
+            )
+        """
+        )
+
+        await session.await_schema_agreement()
+
+        prepared = await session.prepare(
+            "INSERT INTO test_typed (id, tiny, small, big, ratio, tags, scores, point, created) "
+            "VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        await session.execute_prepared(
+            prepared,
+            {
+                "id": 1,
+                "tiny": 7,
+                "small": 300,
+                "big": 2**70,
+                "ratio": 1,
+                "tags": {"a", "b"},
+                "scores": {"x": 1},
+                "point": {"x": 1, "y": 2.5},
+                "created": datetime(2024, 1, 2, tzinfo=timezone.utc),
+            },
+        )
+
+        result = await session.execute(
+            "SELECT tiny, small, ratio, tags, scores, point, created FROM test_typed WHERE id = ?",
+            {"id": 1},
+        )
+        assert result.first_row().columns() == [
+            7,
+            300,
+            1.0,
+            ["a", "b"],
+            {"x": 1},
+            {"x": 1.0, "y": 2.5, "label": None},
+            1704153600000,
+        ]
+
+        with pytest.raises(TypeError, match="tiny"):
+            await session.execute_prepared(prepared, {"id": 2, "tiny": "7"})
+        with pytest.raises(OverflowError):
+            await session.execute_prepared(prepared, {"id": 2, "tiny": 300})
+        with pytest.raises(ValueError):
+            await session.execute_prepared(prepared, {"id": 2, "point": {"z": 1}})
+
+        await session.execute("DROP TABLE IF EXISTS test_typed")
+        await session.execute("DROP TYPE IF EXISTS typed_point")
+
+    async def test_typed_integers(self, session, test_keyspace):
+        """Test integer wrappers and range checks for integer columns"""
+        await session.execute(
+            """
+            CREATE TABLE IF NOT EXISTS test_typed_ints (
+                id int PRIMARY KEY,
+                tiny tinyint,
+                small smallint,
+                big bigint
+            )
+        """
+        )
+
+        await session.await_schema_agreement()
+
+        await session.execute(
+            "INSERT INTO test_typed_ints (id, tiny, small, big) VALUES (?, ?, ?, ?)",
+            {
+                "id": rsylla.Int(1),
+                "tiny": rsylla.TinyInt(-5),
+                "small": rsylla.SmallInt(1000),
+                "big": rsylla.BigInt(7),
+            },
+        )
+
+        result = await session.execute(
+            "SELECT tiny, small, big FROM test_typed_ints WHERE id = ?", {"id": 1}
+        )
+        assert result.first_row().columns() == [-5, 1000, 7]
+
+        assert rsylla.SmallInt(3) == 3
+        assert hash(rsylla.TinyInt(3)) == hash(3)
+        assert int(rsylla.BigInt(9)) == 9
+        with pytest.raises(OverflowError, match="smallint"):
+            rsylla.SmallInt(70000)
+
+        prepared = await session.prepare(
+            "INSERT INTO test_typed_ints (id, tiny) VALUES (?, ?)"
+        )
+        with pytest.raises(OverflowError, match="-128 to 127"):
+            await session.execute_prepared(prepared, {"id": 2, "tiny": 300})
+
+        await session.execute("DROP TABLE IF EXISTS test_typed_ints")
+
+    async def test_nested_collections(self, session, test_keyspace):
+        """Test binding maps and nested collections of any CQL type"""
+        await session.execute(
+            """
+            CREATE TABLE IF NOT EXISTS test_nested (
+                id int PRIMARY KEY,
+                by_id map<int, frozen<list<text>>>,
+                ratios map<text, double>,
+                events list<frozen<map<text, int>>>,
+                groups set<frozen<set<int>>>
+            )
+        """
+        )
+
+        await session.await_schema_agreement()
+
+        values = {
+            "id": 1,
+            "by_id": {1: ["a", "b"], 2: []},
+            "ratios": {"x": 0.5},
+            "events": [{"clicks": 3}, {"views": 10}],
+            "groups": {frozenset({1, 2})},
+        }
+        await session.execute(
+            "INSERT INTO test_nested (id, by_id, ratios, events, groups) VALUES (?, ?, ?, ?, ?)",
+            values,
+        )
+
+        result = await session.execute(
+            "SELECT by_id, ratios, events, groups FROM test_nested WHERE id = ?", {"id": 1}
+        )
+        assert result.first_row().columns() == [
+            {1: ["a", "b"], 2: []},
+            {"x": 0.5},
+            [{"clicks": 3}, {"views": 10}],
+            [[1, 2]],
+        ]
+
+        with pytest.raises(Exception, match="double"):
+            await session.execute(
+                "INSERT INTO test_nested (id, ratios) VALUES (?, ?)",
+                {"id": 2, "ratios": {"x": "high"}},
+            )
+
+        await session.execute("DROP TABLE IF EXISTS test_nested")
+
+    async def test_non_finite_floats(self, session, test_keyspace):
+        """Test that NaN, infinities and -0.0 round-trip and the raise policy"""
+        await session.execute(
+            """
+            CREATE TABLE IF NOT EXISTS test_floats (
+                id int PRIMARY KEY,
+                single float,
+                value double
+            )
+        """
+        )
+
+        await session.await_schema_agreement()
+
+        insert = "INSERT INTO test_floats (id, single, value) VALUES (?, ?, ?)"
+        await session.execute(insert, {"id": 1, "single": float("inf"), "value": -0.0})
+        await session.execute(insert, {"id": 2, "single": 1.5, "value": float("nan")})
+        await session.execute(insert, {"id": 3, "single": 0.5, "value": 1234567890.125})
+
+        result = await session.execute("SELECT single, value FROM test_floats WHERE id = 1")
+        single, value = result.first_row().columns()
+        assert single == math.inf
+        assert value == 0.0 and math.copysign(1.0, value) == -1.0
+
+        result = await session.execute("SELECT value FROM test_floats WHERE id = 2")
+        assert math.isnan(result.first_row()[0])
+
+        result = await session.execute("SELECT value FROM test_floats WHERE id = 3")
+        assert result.first_row()[0] == 1234567890.125
+
+        assert rsylla.get_float_policy() == "store"
+        rsylla.set_float_policy("raise")
+        try:
+            with pytest.raises(ValueError, match="Non-finite"):
+                await session.execute(insert, {"id": 4, "single": 1.0, "value": float("-inf")})
+            prepared = await session.prepare(insert)
+            with pytest.raises(ValueError, match="Non-finite"):
+                await session.execute_prepared(
+                    prepared, {"id": 4, "single": float("nan"), "value": 1.0}
+                )
+        finally:
+            rsylla.set_float_policy("store")
+
+        with pytest.raises(ValueError):
+            rsylla.set_float_policy("ignore")
+
+        await session.execute("DROP TABLE IF EXISTS test_floats")
+
+    async def test_duration_modes(self, session, test_keyspace):
+        """Test reading durations as dicts or timedeltas and binding timedeltas"""
+        await session.execute(
+            "CREATE TABLE IF NOT EXISTS test_durations (id int PRIMARY KEY, value duration)"
+        )
+
+        await session.await_schema_agreement()
+
+        insert = "INSERT INTO test_durations (id, value) VALUES (?, ?)"
+        await session.execute(
+            insert, {"id": 1, "value": timedelta(days=1, hours=1, microseconds=5)}
+        )
+        await session.execute(insert, {"id": 2, "value": timedelta(hours=-2)})
+        await session.execute(
+            insert, {"id": 3, "value": {"months": 1, "days": 2, "nanoseconds": 0}}
+        )
+        select = "SELECT value FROM test_durations WHERE id = ?"
+
+        assert rsylla.get_duration_mode() == "dict"
+        result = await session.execute(select, {"id": 1})
+        assert result.first_row()[0] == {"months": 0, "days": 1, "nanoseconds": 3600000005000}
+        result = await session.execute(select, {"id": 2})
+        assert result.first_row()[0] == {"months": 0, "days": 0, "nanoseconds": -7200000000000}
+
+        rsylla.set_duration_mode("timedelta")
+        try:
+            result = await session.execute(select, {"id": 1})
+            assert result.first_row()[0] == timedelta(days=1, hours=1, microseconds=5)
+            result = await session.execute(select, {"id": 2})
+            assert result.first_row()[0] == timedelta(hours=-2)
+            result = await session.execute(select, {"id": 3})
+            assert result.first_row()[0] == {"months": 1, "days": 2, "nanoseconds": 0}
+
+            rsylla.set_duration_mode("timedelta_strict")
+            with pytest.raises(ValueError, match="timedelta"):
+                await session.execute(select, {"id": 3})
+        finally:
+            rsylla.set_duration_mode("dict")
+
+        with pytest.raises(ValueError):
+            rsylla.set_duration_mode("seconds")
+
+        await session.execute("DROP TABLE IF EXISTS test_durations")
+
+    async def test_timeuuid_helpers(self, session, test_keyspace):
+        """Test generating timeuuids and querying ranges of them"""
+        await session.execute(
+            """
+            CREATE TABLE IF NOT EXISTS test_timeuuids (
+                sensor int,
+                id timeuuid,
+                PRIMARY KEY (sensor, id)
+            )
+        """
+        )
+
+        await session.await_schema_agreement()
+
+        start = datetime(2024, 5, 6, 12, 0, 0, 250000, tzinfo=timezone.utc)
+        ids = [rsylla.timeuuid_from_datetime(start + timedelta(minutes=i)) for i in range(5)]
+        for event_id in ids:
+            await session.execute(
+                "INSERT INTO test_timeuuids (sensor, id) VALUES (?, ?)",
+                {"sensor": 1, "id": event_id},
+            )
+
+        assert [rsylla.timeuuid_to_datetime(event_id) for event_id in ids] == [
+            start + timedelta(minutes=i) for i in range(5)
+        ]
+        result = await session.execute(
+            "SELECT id FROM test_timeuuids WHERE sensor = :sensor AND id >= :start AND id <= :end",
+            {
+                "sensor": 1,
+                "start": rsylla.min_timeuuid(start + timedelta(minutes=1)),
+                "end": rsylla.max_timeuuid(start + timedelta(minutes=3)),
+            },
+        )
+        assert [row[0] for row in result] == [str(event_id) for event_id in ids[1:4]]
+
+        now = [rsylla.timeuuid_now() for _ in range(1000)]
+        assert len(set(now)) == 1000
+        assert sorted(now, key=lambda u: u.time) == now
+        assert rsylla.timeuuid_to_datetime(str(now[0])) <= datetime.now(timezone.utc)
+
+        with pytest.raises(ValueError, match="not a timeuuid"):
+            rsylla.timeuuid_to_datetime(uuid.uuid4())
+
+        await session.execute("DROP TABLE IF EXISTS test_timeuuids")
+
+    async def test_numpy_and_pandas_scalars(self, session, test_keyspace):
+        """Test binding numpy scalars and pandas timestamps"""
+        np = pytest.importorskip("numpy")
+        pd = pytest.importorskip("pandas")
+        await session.execute(
+            """
+            CREATE TABLE IF NOT EXISTS test_scalars (
+                id int PRIMARY KEY,
+                small smallint,
+                ratio double,
+                flag boolean,
+                created timestamp,
+                updated timestamp
+            )
+        """
+        )
+
+        await session.await_schema_agreement()
+
+        row = {
+            "id": np.int32(1),
+            "small": np.int64(12),
+            "ratio": np.float32(0.5),
+            "flag": np.bool_(True),
+            "created": pd.Timestamp("2024-01-02T00:00:00.123456789Z"),
+            "updated": np.datetime64("2024-01-02T00:00:01.5"),
+        }
+        insert = (
+            "INSERT INTO test_scalars (id, small, ratio, flag, created, updated) "
+            "VALUES (?, ?, ?, ?, ?, ?)"
+        )
+        prepared = await session.prepare(insert)
+        await session.execute_prepared(prepared, row)
+        await session.execute_prepared(
+            prepared, {**row, "id": np.int64(2), "created": pd.NaT, "updated": None}
+        )
+        await session.execute(
+            "INSERT INTO test_scalars (id, ratio, flag, created) VALUES (?, ?, ?, ?)",
+            {
+                "id": np.int32(3),
+                "ratio": np.float64(0.25),
+                "flag": np.bool_(False),
+                "created": np.datetime64("NaT"),
+            },
+        )
+
+        result = await session.execute(
+            "SELECT small, ratio, flag, created, updated FROM test_scalars WHERE id = 1"
+        )
+        assert result.first_row().columns() == [12, 0.5, True, 1704153600123, 1704153601500]
+
+        result = await session.execute("SELECT created FROM test_scalars WHERE id = 2")
+        assert result.first_row()[0] is None
+
+        result = await session.execute("SELECT ratio, flag, created FROM test_scalars WHERE id = 3")
+        assert result.first_row().columns() == [0.25, False, None]
+
+        await session.execute("DROP TABLE IF EXISTS test_scalars")
+
+    async def test_enum_binding(self, session, test_keyspace, scylla_connection_string):
+        """Test binding Enum members by value and by name"""
+
+        class Status(enum.Enum):
+            ACTIVE = "active"
+            BANNED = "banned"
+
+        class Level(enum.IntEnum):
+            LOW = 1
+            HIGH = 2
+
+        await session.execute(
+            """
+            CREATE TABLE IF NOT EXISTS test_enums (
+                id int PRIMARY KEY,
+                status text,
+                level int,
+                history list<text>
+            )
+        """
+        )
+
+        await session.await_schema_agreement()
+
+        insert = "INSERT INTO test_enums (id, status, level, history) VALUES (?, ?, ?, ?)"
+        values = {
+            "id": 1,
+            "status": Status.ACTIVE,
+            "level": Level.HIGH,
+            "history": [Status.BANNED, Status.ACTIVE],
+        }
+        await session.execute(insert, values)
+        prepared = await session.prepare(insert)
+        await session.execute_prepared(prepared, {**values, "id": 2})
+
+        result = await session.execute("SELECT status, level, history FROM test_enums")
+        for row in result:
+            assert row.columns() == ["active", 2, ["banned", "active"]]
+
+        by_name = await (
+            rsylla.SessionBuilder()
+            .known_node(scylla_connection_string)
+            .use_keyspace(test_keyspace, False)
+            .enum_binding("name")
+            .build()
+        )
+        await by_name.execute(
+            "INSERT INTO test_enums (id, status) VALUES (?, ?)", {"id": 3, "status": Status.BANNED}
+        )
+        result = await session.execute("SELECT status FROM test_enums WHERE id = 3")
+        assert result.first_row()[0] == "BANNED"
+        by_name.close()
+
+        with pytest.raises(ValueError):
+            rsylla.SessionBuilder().enum_binding("label")
+
+        await session.execute("DROP TABLE IF EXISTS test_enums")
@@ -0,0 +1,144 @@
+"""
+Tests for the object mapper
+"""
+
+import time
+from typing import Annotated
+
+import pytest
+
+from rsylla import Mapper, Session
+from rsylla.mapper import ClusteringKey, Column, PartitionKey, model, user_type
+
+
+@model("users")
+class User:
+    id: Annotated[int, PartitionKey]
+    username: str
+    mail: Annotated[str | None, Column("email")] = None
+    age: int | None = None
+
+
+@user_type("mapped_address")
+class Address:
+    street: str
+    zip_code: str | None = None
+
+
+@model("mapped_places", clustering_order={"visit": "DESC"}, options={"comment": "mapped"})
+class Place:
+    owner: Annotated[int, PartitionKey]
+    visit: Annotated[int, ClusteringKey, Column(cql_type="int")]
+    tags: set[str]
+    address: Address
+    previous: list[Address]
+
+
+@model("mapped_places")
+class PlaceWithRating:
+    owner: Annotated[int, PartitionKey]
+    visit: Annotated[int, ClusteringKey, Column(cql_type="int")]
+    rating: float | None = None
+
+
+@pytest.mark.integration
+class TestMapper:
+    """Test mapped classes"""
+
+    async def test_save_get_delete(self, session, users_table):
+        """Test the round trip of a mapped object"""
+        user = User(id=700, username="mapped", mail="mapped@example.com", age=40)
+        await user.save(session)
+
+        loaded = await User.get(session, id=700)
+        assert loaded == user
+
+        await user.delete(session)
+        assert await User.get(session, id=700) is None
+
+        # Statements are prepared once and reused
+        await User.get(session, id=700)
+        assert User.__mapper__.prepared_statements == 3
+
+    async def test_blocking_session(self, scylla_connection_string, test_keyspace, users_table):
+        """Test that a blocking Session gets results instead of awaitables"""
+        blocking = Session.connect([scylla_connection_string])
+        blocking.use_keyspace(test_keyspace, False)
+        user = User(id=702, username="blocking", mail="blocking@example.com")
+        assert user.save(blocking) is None
+        assert User.get(blocking, id=702) == user
+        assert [found.id for found in User.find(blocking, id=702)] == [702]
+        user.delete(blocking)
+        assert User.get(blocking, id=702) is None
+        assert User.sync_table(blocking) == []
+        blocking.close()
+
+    async def test_save_with_ttl(self, session, users_table):
+        """Test saving with a TTL and a write timestamp"""
+        timestamp = int(time.time() * 1_000_000)
+        user = User(id=701, username="expiring", mail="expiring@example.com", age=20)
+        await user.save(session, ttl=600, timestamp=timestamp)
+        await user.save(session, ttl=900, timestamp=timestamp + 1)
+
+        result = await session.execute(
+            "SELECT TTL(username), WRITETIME(username) FROM users WHERE id = ?", {"id": 701}
+        )
+        ttl, written = result.first_row()
+        assert 800 < ttl <= 900
+        assert written == timestamp + 1
+
+    async def test_find(self, session, users_table, sample_users):
+        """Test finding mapped objects by column values"""
+        users = await User.find(session, username="alice", allow_filtering=True)
+        assert [user.id for user in users] == [1]
+        assert users[0].mail == "alice@example.com"
+
+        assert len(await User.find(session, limit=2)) == 2
+
+        with pytest.raises(ValueError):
+            await User.find(session, nickname="alice")
+
+    async def test_mapper_validation(self, session, users_table):
+        """Test that incomplete keys and mappings are rejected"""
+        with pytest.raises(ValueError):
+            Mapper("users", ["id"], partition_key=["user_id"])
+
+        mapper = Mapper("users", ["id", "username"], partition_key=["id"])
+        with pytest.raises(ValueError):
+            await mapper.get(session, {"username": "alice"})
+        with pytest.raises(ValueError):
+            await mapper.save(session, {"id": 1, "nickname": "alice"})
+
+    async def test_sync_table(self, session, test_keyspace):
+        """Test creating and extending a table from a model"""
+        await session.execute("DROP TABLE IF EXISTS mapped_places")
+        await session.execute("DROP TYPE IF EXISTS mapped_address")
+
+        statements = await Place.sync_table(session)
+        assert [s.split(" (")[0] for s in statements] == [
+            f'CREATE TYPE IF NOT EXISTS "{test_keyspace}"."mapped_address"',
+            f'CREATE TABLE IF NOT EXISTS "{test_keyspace}"."mapped_places"',
+        ]
+        assert await Place.sync_table(session) == []
+
+        place = Place(1, 2, {"home"}, Address("Main St 1", "00-001"), [Address("Old St 2")])
+        await place.save(session)
+        assert await Place.get(session, owner=1, visit=2) == place
+        await Place(1, 3, set(), Address("Side St 3"), []).save(session)
+        assert [p.visit for p in await Place.find(session, owner=1)] == [3, 2]
+
+        statements = await PlaceWithRating.sync_table(session)
+        assert statements == [
+            f'ALTER TABLE "{test_keyspace}"."mapped_places" ADD "rating" double'
+        ]
+        metadata = await session.get_keyspace_metadata(test_keyspace)
+        assert metadata.tables["mapped_places"].columns["rating"] == "double"
+
+        with pytest.raises(TypeError):
+
+            @model("invalid")
+            class Invalid:
+                id: Annotated[int, PartitionKey]
+                value: object
+
+            await Invalid.sync_table(session)
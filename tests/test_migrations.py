@@ -0,0 +1,59 @@
+"""
+Tests for schema migrations
+"""
+
+import pytest
+
+from rsylla import MigrationRunner
+
+
+@pytest.mark.integration
+class TestMigrations:
+    """Test MigrationRunner"""
+
+    async def test_run_migrations(self, session, test_keyspace, tmp_path):
+        """Test applying migrations in order and only once"""
+        (tmp_path / "002_add_email.cql").write_text(
+            "ALTER TABLE migrated ADD email text;\n"
+            "-- seed a row; the semicolon in the string must not split it\n"
+            "INSERT INTO migrated (id, name) VALUES (1, 'a;b');\n"
+        )
+        (tmp_path / "001_create_table.cql").write_text(
+            "CREATE TABLE IF NOT EXISTS migrated (id int PRIMARY KEY, name text);"
+        )
+        (tmp_path / "README.md").write_text("not a migration")
+
+        runner = MigrationRunner(session, tmp_path)
+        assert [m.version for m in runner.migrations()] == [1, 2]
+        assert runner.migrations()[1].statements == [
+            "ALTER TABLE migrated ADD email text",
+            "INSERT INTO migrated (id, name) VALUES (1, 'a;b')",
+        ]
+
+        pending = await runner.run(dry_run=True)
+        assert [m.name for m in pending] == ["create_table", "add_email"]
+        assert await runner.applied() == []
+
+        applied = await runner.run(target=1)
+        assert [m.version for m in applied] == [1]
+        applied = await runner.run()
+        assert [m.version for m in applied] == [2]
+        assert await runner.run() == []
+        assert await runner.applied() == [1, 2]
+
+        result = await session.execute("SELECT name, email FROM migrated WHERE id = 1")
+        assert result.first_row()[0] == "a;b"
+
+    async def test_failed_migration(self, session, test_keyspace, tmp_path):
+        """Test that a failing migration is not recorded"""
+        (tmp_path / "1_broken.cql").write_text("CREATE TABLE broken (;")
+        runner = MigrationRunner(session, tmp_path, table="broken_migrations")
+
+        with pytest.raises(Exception) as exc_info:
+            await runner.run()
+        assert "1_broken.cql" in str(exc_info.value.__notes__)
+        assert await runner.applied() == []
+
+        (tmp_path / "1_duplicate.cql").write_text("")
+        with pytest.raises(ValueError):
+            runner.migrations()
@@ -4,7 +4,7 @@ Tests for PreparedStatement
 
 import pytest
 
-from rsylla import ScyllaError
+from rsylla import ScyllaError, SessionBuilder
 
 
 @pytest.mark.integration
@@ -114,3 +114,87 @@ class TestPreparedStatement:
         """Test preparing invalid query"""
         with pytest.raises(ScyllaError):
             await session.prepare("INVALID QUERY SYNTAX")
+
+    async def test_execute_many(self, session, users_table):
+        """Test bulk execution of one prepared statement"""
+        insert = await session.prepare("INSERT INTO users (id, username) VALUES (?, ?)")
+        result = await session.execute_many(
+            insert, ({"id": i, "username": f"bulk{i}"} for i in range(1000, 1200)), concurrency=16
+        )
+        assert result
+        assert result.succeeded == 200
+        assert result.failed == 0
+        assert result.errors == []
+
+        select = await session.prepare("SELECT * FROM users WHERE id = ?")
+        result = await session.execute_many(select, [{"id": 1000}, {"id": "oops"}, {"id": 1001}])
+        assert not result
+        assert result.succeeded == 2
+        assert result.rows == 2
+        assert result.failed == 1
+        assert result.errors[0][0] == 1
+
+        with pytest.raises(TypeError, match=r"params_list\[1\]"):
+            await session.execute_many(select, [{"id": 1000}, [1001]])
+
+    async def test_auto_prepare(self, scylla_connection_string, test_keyspace, sample_users):
+        """Test execute() preparing statements through the LRU cache"""
+        session = (
+            await SessionBuilder()
+            .known_node(scylla_connection_string)
+            .use_keyspace(test_keyspace, False)
+            .auto_prepare(capacity=2)
+            .build()
+        )
+        assert session.prepare_cache_stats().size == 0
+
+        for _ in range(3):
+            result = await session.execute("SELECT * FROM users WHERE id = ?", {"id": 1})
+            assert len(result) == 1
+        stats = session.prepare_cache_stats()
+        assert (stats.hits, stats.misses, stats.evictions, stats.size) == (2, 1, 0, 1)
+
+        await session.execute("SELECT username FROM users WHERE id = ?", {"id": 1})
+        await session.execute("SELECT email FROM users WHERE id = ?", {"id": 1})
+        stats = session.prepare_cache_stats()
+        assert stats.evictions == 1
+        assert stats.size == stats.capacity == 2
+
+        # Schema statements are never cached
+        await session.execute("CREATE TABLE IF NOT EXISTS auto_prepare (id int PRIMARY KEY)")
+        assert session.prepare_cache_stats().misses == 3
+
+        # Dropping the table makes nodes forget the statement, which the driver prepares again on
+        # the connection, so the cached statement stays in use
+        insert = "INSERT INTO auto_prepare (id) VALUES (?)"
+        await session.execute(insert, {"id": 1})
+        await session.execute("DROP TABLE auto_prepare")
+        await session.execute("CREATE TABLE auto_prepare (id int PRIMARY KEY)")
+        await session.execute(insert, {"id": 2})
+        stats = session.prepare_cache_stats()
+        assert stats.reprepares == 0
+        assert stats.misses == 4
+
+        text = session.metrics_prometheus()
+        assert "rsylla_prepare_cache_hits_total 3" in text
+        assert "rsylla_prepare_cache_reprepares_total 0" in text
+
+    async def test_prepared_metadata(self, session, users_table):
+        """Test bind marker and result metadata of a prepared statement"""
+        prepared = await session.prepare("SELECT id, username FROM users WHERE id = :user_id")
+        assert prepared.get_variable_names() == ["user_id"]
+        [marker] = prepared.get_variable_col_specs()
+        assert (marker.name, marker.keyspace, marker.table) == ("user_id", "test_rsylla", "users")
+        assert str(marker.cql_type) == "int"
+        assert prepared.get_partition_key_indexes() == [0]
+        specs = prepared.get_result_col_specs()
+        assert [(spec.name, str(spec.cql_type)) for spec in specs] == [
+            ("id", "int"),
+            ("username", "text"),
+        ]
+        assert not prepared.is_lwt()
+
+        conditional = await session.prepare("UPDATE users SET email = ? WHERE id = ? IF EXISTS")
+        assert conditional.is_lwt()
+        assert conditional.get_variable_names() == ["email", "id"]
+        assert conditional.get_partition_key_indexes() == [1]
@@ -3,10 +3,20 @@ Tests for Query execution
 """
 
 import time
+from datetime import datetime, timedelta, timezone
 
 import pytest
 
-from rsylla import Query, ScyllaError
+from rsylla import (
+    AlreadyExists,
+    CqlSyntaxError,
+    InvalidQuery,
+    Overloaded,
+    Query,
+    RateLimitReached,
+    ScyllaError,
+    Statement,
+)
 
 
 @pytest.mark.integration
@@ -85,6 +95,268 @@ class TestQueryExecution:
         with pytest.raises(ScyllaError):
             await session.execute("INVALID QUERY SYNTAX")
 
+    async def test_execute_with_timestamp(self, session, users_table):
+        """Test explicit write timestamp on execute"""
+        timestamp = int(time.time() * 1_000_000)
+        await session.execute(
+            "INSERT INTO users (id, username) VALUES (?, ?)",
+            {"id": 300, "username": "stamped"},
+            timestamp=timestamp,
+        )
+
+        result = await session.execute(
+            "SELECT WRITETIME(username) FROM users WHERE id = ?", {"id": 300}
+        )
+        assert result.first_row()[0] == timestamp
+
+    def test_rate_limit_error_hierarchy(self):
+        """Test rate limit rejections are a kind of overload"""
+        assert issubclass(RateLimitReached, Overloaded)
+        assert issubclass(Overloaded, ScyllaError)
+
+    async def test_syntax_error_type(self, session, test_keyspace):
+        """Test syntax errors raise CqlSyntaxError"""
+        with pytest.raises(CqlSyntaxError):
+            await session.execute("SELEC * FROM users")
+
+    async def test_already_exists_error(self, session, users_table):
+        """Test AlreadyExists carries keyspace and table"""
+        with pytest.raises(AlreadyExists) as exc_info:
+            await session.execute("CREATE TABLE users (id int PRIMARY KEY)")
+
+        assert isinstance(exc_info.value, InvalidQuery)
+        assert isinstance(exc_info.value, ScyllaError)
+        assert exc_info.value.keyspace == "test_rsylla"
+        assert exc_info.value.table == "users"
+
+    async def test_execute_concurrent(self, session, users_table):
+        """Test concurrent execution returns results and errors in order"""
+        insert = await session.prepare("INSERT INTO users (id, username) VALUES (?, ?)")
+        results = await session.execute_concurrent(
+            [(insert, {"id": i, "username": f"user{i}"}) for i in range(50)], concurrency=8
+        )
+        assert len(results) == 50
+        assert all(success for success, _ in results)
+
+        results = await session.execute_concurrent(
+            [
+                ("SELECT username FROM users WHERE id = ?", {"id": 3}),
+                "SELEC * FROM users",
+                (Statement("SELECT username FROM users WHERE id = ?"), {"id": 4}),
+            ]
+        )
+        assert results[0][0] is True
+        assert results[0][1].first_row()[0] == "user3"
+        assert results[1][0] is False
+        assert isinstance(results[1][1], CqlSyntaxError)
+        assert results[2][1].first_row()[0] == "user4"
+
+        with pytest.raises(ValueError):
+            await session.execute_concurrent([], concurrency=0)
+
+    async def test_execute_future(self, session, users_table, sample_users):
+        """Test executing into a concurrent.futures.Future"""
+        import asyncio
+        from concurrent.futures import Future
+
+        futures = [
+            session.execute_future("SELECT * FROM users WHERE id = ?", {"id": user_id})
+            for user_id in (1, 2)
+        ]
+        assert all(isinstance(future, Future) for future in futures)
+        assert [len(future.result(timeout=10)) for future in futures] == [1, 1]
+
+        result = await asyncio.wrap_future(session.execute_future("SELECT * FROM users"))
+        assert len(result) >= 2
+
+        future = session.execute_future("SELEC * FROM users")
+        with pytest.raises(CqlSyntaxError):
+            future.result(timeout=10)
+
+    async def test_json_helpers(self, session, test_keyspace):
+        """Test INSERT JSON from a dict and SELECT JSON parsed by column types"""
+        import uuid
+
+        await session.execute(
+            """
+            CREATE TABLE IF NOT EXISTS json_docs (
+                id uuid PRIMARY KEY,
+                name text,
+                avatar blob,
+                created timestamp,
+                scores map<int, double>,
+                tags set<text>
+            )
+        """
+        )
+        await session.await_schema_agreement()
+
+        doc_id = uuid.uuid4()
+        await session.insert_json(
+            "json_docs",
+            {
+                "id": doc_id,
+                "name": "Ada",
+                "avatar": b"\x89PNG",
+                "created": datetime(2024, 1, 2, 3, 4, 5, 678000, tzinfo=timezone.utc),
+                "scores": {1: 0.5, 2: 1.5},
+                "tags": {"a", "b"},
+            },
+        )
+
+        docs = await session.select_json(
+            "SELECT JSON id, name, avatar, created, scores, tags FROM json_docs WHERE id = ?",
+            {"id": doc_id},
+        )
+        assert docs == [
+            {
+                "id": str(doc_id),
+                "name": "Ada",
+                "avatar": b"\x89PNG",
+                "created": 1704164645678,
+                "scores": {1: 0.5, 2: 1.5},
+                "tags": ["a", "b"],
+            }
+        ]
+
+        result = await session.insert_json("json_docs", {"id": doc_id}, if_not_exists=True)
+        assert not result.was_applied
+
+        await session.insert_json(
+            f"{test_keyspace}.json_docs", {"id": doc_id, "name": "Bob"}, default_unset=True
+        )
+        docs = await session.select_json(
+            "SELECT JSON name, avatar FROM json_docs WHERE id = ?", {"id": doc_id}
+        )
+        assert docs == [{"name": "Bob", "avatar": b"\x89PNG"}]
+
+        with pytest.raises(TypeError):
+            await session.insert_json("json_docs", {"id": doc_id, "name": object()})
+        with pytest.raises(ValueError):
+            await session.insert_json("", {"id": doc_id})
+
+        await session.execute("DROP TABLE IF EXISTS json_docs")
+
+
+@pytest.mark.integration
+class TestQueryBuilder:
+    """Test the fluent query builder"""
+
+    async def test_select_builder(self, session, users_table, sample_users):
+        """Test generating and executing a SELECT"""
+        select = Query.select("users", ["id", "username"]).where_eq("id", 1).limit(10)
+        assert select.cql == 'SELECT "id", "username" FROM "users" WHERE "id" = :p0 LIMIT 10'
+        assert select.values == {"p0": 1}
+
+        result = await session.execute(select)
+        assert result.first_row_typed()["username"] == "alice"
+
+        result = await session.execute(Query.select("users").where_in("id", [1, 2]))
+        assert len(result) == 2
+
+        with pytest.raises(TypeError):
+            Query.select("users").where_in("username", "alice")
+        with pytest.raises(TypeError):
+            Query.select("users").where_in("id", b"\x01\x02")
+
+    async def test_write_builders(self, session, users_table):
+        """Test INSERT, UPDATE and DELETE builders with TTL and timestamp"""
+        insert = (
+            Query.insert_into("users")
+            .value("id", 300)
+            .value("username", "builder")
+            .if_not_exists()
+            .using_ttl(3600)
+        )
+        assert insert.cql == (
+            'INSERT INTO "users" ("id", "username") VALUES (:p0, :p1) IF NOT EXISTS USING TTL 3600'
+        )
+        await session.execute(insert)
+
+        update = (
+            Query.update("users")
+            .set("email", "builder@example.com")
+            .where_eq("id", 300)
+            .using_timestamp(int(time.time() * 1000000))
+        )
+        await session.execute(update)
+        query, values = update.build()
+        assert values == {"p0": "builder@example.com", "p1": 300}
+        assert query.get_contents() == update.cql
+
+        result = await session.execute(Query.select("users", ["email"]).where_eq("id", 300))
+        assert result.first_row()[0] == "builder@example.com"
+
+        await session.execute(Query.delete_from("users").where_eq("id", 300))
+        result = await session.execute(Query.select("users").where_eq("id", 300))
+        assert len(result) == 0
+
+    async def test_writetime_and_ttl(self, session, users_table):
+        """Test selecting and reading WRITETIME and TTL"""
+        timestamp = int(time.time() * 1_000_000)
+        insert = (
+            Query.insert_into("users")
+            .value("id", 320)
+            .value("username", "fresh")
+            .value("email", "fresh@example.com")
+            .using_ttl(600)
+        )
+        await session.execute(insert)
+        await session.execute(
+            Query.update("users").set("age", 30).where_eq("id", 320).using_timestamp(timestamp)
+        )
+
+        select = (
+            Query.select("users", ["username"])
+            .select_writetime("username")
+            .select_ttl("email")
+            .select_writetime("age")
+            .select_ttl("age")
+            .where_eq("id", 320)
+        )
+        row = (await session.execute(select)).first_row()
+        assert row["username"] == "fresh"
+        assert row.writetime("username").tzinfo == timezone.utc
+        assert abs(row.writetime("username") - datetime.now(timezone.utc)) < timedelta(minutes=1)
+        assert 500 < row.ttl("email") <= 600
+        assert row.writetime("age") == datetime.fromtimestamp(0, timezone.utc) + timedelta(
+            microseconds=timestamp
+        )
+        assert row.ttl("age") is None
+
+        # Unaliased selectors work too
+        result = await session.execute(
+            "SELECT WRITETIME(username), TTL(username) FROM users WHERE id = ?", {"id": 320}
+        )
+        row = result.first_row()
+        assert row.writetime("username") is not None
+        assert 500 < row.ttl("username") <= 600
+        with pytest.raises(KeyError):
+            row.ttl("email")
+
+        with pytest.raises(ValueError):
+            Query.select("users").select_ttl("email")
+
+    async def test_builder_prevents_injection(self, session, users_table, sample_users):
+        """Test that names are quoted and values are always bound"""
+        select = Query.select("users").where_eq("username", "x' OR '1'='1")
+        assert "OR" not in select.cql
+        result = await session.execute(select.allow_filtering())
+        assert len(result) == 0
+
+        assert Query.select('users"; DROP TABLE users').cql.startswith(
+            'SELECT * FROM "users""; DROP TABLE users"'
+        )
+
+        with pytest.raises(ValueError):
+            Query.select("users").where_op("id", "= 1 OR", 1)
+        with pytest.raises(ValueError):
+            Query.select("users").set("id", 1)
+        with pytest.raises(ValueError):
+            _ = Query.delete_from("users").cql
+        with pytest.raises(ValueError):
+            await session.execute(Query.select("users").where_eq("id", 1), {"id": 1})
+
 
 @pytest.mark.integration
 class TestQueryObject:
@@ -104,6 +376,9 @@ class TestQueryObject:
         result = await session.query(query)
         assert len(result) >= 2
 
+        with pytest.raises(ValueError):
+            Query("SELECT * FROM users").with_page_size(0)
+
     async def test_query_with_timeout(self, session, users_table):
         """Test Query with timeout"""
         query = Query("SELECT * FROM users").with_timeout(5000)
@@ -170,3 +445,146 @@ class TestQueryObject:
             # Should not raise error
             result = await session.query(query)
             assert result is not None
+
+    async def test_query_with_routing_key(self, session, users_table, sample_users):
+        """Test routing a simple statement with an explicit routing key"""
+        import struct
+
+        query = Query("SELECT * FROM users WHERE id = 1").with_routing_key(
+            struct.pack(">i", 1), "test_rsylla", "users"
+        )
+        assert query.get_routing_token() is not None
+
+        result = await session.query(query)
+        assert len(result) == 1
+        assert result.execution_info().coordinator_is_replica is True
+
+    async def test_statement_object(self, session, users_table, sample_users):
+        """Test executing a Statement with bundled options"""
+        stmt = Statement(
+            "SELECT * FROM users WHERE id = ?",
+            consistency="local_quorum",
+            timeout_ms=5000,
+            page_size=10,
+            idempotent=True,
+        )
+        assert stmt.consistency == "LOCAL_QUORUM"
+        assert stmt.timeout_ms == 5000
+        assert stmt.page_size == 10
+        assert stmt.idempotent is True
+
+        result = await session.execute(stmt, {"id": 1})
+        assert len(result) == 1
+
+    async def test_statement_ttl(self, session, users_table):
+        """Test TTL on Statement writes, combined with an explicit timestamp"""
+        timestamp = int(time.time() * 1_000_000)
+        insert = Statement(
+            "INSERT INTO users (id, username) VALUES (?, ?) IF NOT EXISTS;",
+            ttl=600,
+        )
+        assert insert.cql.endswith("VALUES (?, ?) IF NOT EXISTS USING TTL 600")
+        await session.execute(insert, {"id": 310, "username": "expiring"})
+
+        update = Statement(
+            f"UPDATE users USING TIMESTAMP {timestamp} SET email = ? WHERE id = ?", ttl=300
+        )
+        assert update.cql.startswith(f"UPDATE users USING TIMESTAMP {timestamp} AND TTL 300 SET")
+        await session.execute(update, {"email": "x@y.z", "id": 310})
+
+        update = Statement(
+            "UPDATE users SET age = ? WHERE id = ?", ttl=100, timestamp=timestamp + 1
+        )
+        await session.execute(update, {"age": 5, "id": 310})
+
+        result = await session.execute(
+            "SELECT TTL(username), TTL(email), TTL(age), WRITETIME(age) FROM users WHERE id = ?",
+            {"id": 310},
+        )
+        username_ttl, email_ttl, age_ttl, age_written = result.first_row()
+        assert 500 < username_ttl <= 600
+        assert 200 < email_ttl <= 300
+        assert 0 < age_ttl <= 100
+        assert age_written == timestamp + 1
+
+        with pytest.raises(ValueError):
+            Statement("SELECT * FROM users", ttl=10)
+        with pytest.raises(ValueError):
+            Statement("INSERT INTO users (id) VALUES (1) USING TTL 5", ttl=10)
+        with pytest.raises(ValueError):
+            Statement("INSERT INTO users (id) VALUES (1)", ttl=-1)
+        with pytest.raises(ValueError):
+            Statement("SELECT * FROM users", page_size=0)
+        with pytest.raises(ValueError):
+            Statement("SELECT * FROM users", page_size=-5)
+
+    async def test_statement_server_timeout(self, session, users_table, sample_users):
+        """Test the server-side timeout added as USING TIMEOUT"""
+        select = Statement(
+            "SELECT * FROM users WHERE id = ?;", server_timeout=timedelta(milliseconds=500)
+        )
+        assert select.cql == "SELECT * FROM users WHERE id = ? USING TIMEOUT 500ms"
+        assert select.server_timeout == timedelta(milliseconds=500)
+        result = await session.execute(select, {"id": 1})
+        assert len(result) == 1
+
+        update = Statement(
+            "UPDATE users SET age = ? WHERE id = ?", ttl=60, server_timeout=timedelta(seconds=2)
+        )
+        assert update.cql.startswith("UPDATE users USING TTL 60 AND TIMEOUT 2s SET")
+        await session.execute(update, {"age": 30, "id": 1})
+
+        delete = Statement(
+            "DELETE FROM users WHERE id = ?", server_timeout=timedelta(microseconds=1500)
+        )
+        assert delete.cql == "DELETE FROM users USING TIMEOUT 1500us WHERE id = ?"
+        await session.execute(delete, {"id": 1})
+
+        assert Statement("SELECT * FROM users").server_timeout is None
+        with pytest.raises(ValueError):
+            Statement("SELECT * FROM users", server_timeout=timedelta(0))
+        with pytest.raises(ValueError):
+            Statement("TRUNCATE users", server_timeout=timedelta(seconds=1))
+        with pytest.raises(ValueError):
+            Statement("SELECT * FROM users USING TIMEOUT 1s", server_timeout=timedelta(seconds=1))
+
+    async def test_statement_bypass_cache(self, session, users_table, sample_users):
+        """Test BYPASS CACHE on reads, ahead of a USING TIMEOUT"""
+        scan = Statement("SELECT * FROM users", bypass_cache=True)
+        assert scan.cql == "SELECT * FROM users BYPASS CACHE"
+        assert scan.bypass_cache is True
+        result = await session.execute(scan)
+        assert len(result) == len(sample_users)
+
+        select = Statement(
+            "SELECT * FROM users WHERE id = ?",
+            bypass_cache=True,
+            server_timeout=timedelta(seconds=1),
+        )
+        assert select.cql == "SELECT * FROM users WHERE id = ? BYPASS CACHE USING TIMEOUT 1s"
+        result = await session.execute(select, {"id": 1})
+        assert len(result) == 1
+
+        assert Statement("SELECT * FROM users").bypass_cache is False
+        with pytest.raises(ValueError):
+            Statement("DELETE FROM users WHERE id = 1", bypass_cache=True)
+        with pytest.raises(ValueError):
+            Statement("SELECT * FROM users BYPASS CACHE", bypass_cache=True)
+
+    async def test_query_tracing_info(self, session, users_table, sample_users):
+        """Test fetching structured tracing info"""
+        query = Query("SELECT * FROM users").with_tracing(True)
+        result = await session.query(query)
+
+        trace = await result.get_tracing_info()
+        assert trace.coordinator is not None
+        assert trace.duration is not None
+        assert len(trace.events) > 0
+        assert trace.nodes()
+
+    async def test_query_tracing_info_disabled(self, session, users_table):
+        """Test tracing info is unavailable without tracing"""
+        result = await session.execute("SELECT * FROM users")
+
+        with pytest.raises(ValueError):
+            await result.get_tracing_info()
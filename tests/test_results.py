@@ -2,6 +2,9 @@
 Tests for QueryResult and Row
 """
 
+import pickle
+import struct
+
 import pytest
 
 
@@ -46,6 +49,24 @@ class TestQueryResult:
         with pytest.raises(Exception):  # Should raise because multiple rows
             result.single_row()
 
+    async def test_result_one_and_scalar(self, session, users_table, sample_users):
+        """Test getting the only row and the first value"""
+        result = await session.execute("SELECT username, email FROM users WHERE id = ?", {"id": 1})
+        assert result.one()["username"] == "alice"
+        assert result.scalar() == "alice"
+
+        result = await session.execute("SELECT COUNT(*) FROM users")
+        assert result.scalar() == 3
+
+        result = await session.execute("SELECT * FROM users")
+        with pytest.raises(ValueError, match="got 3 rows"):
+            result.one()
+
+        empty = await session.execute("SELECT * FROM users WHERE id = -1")
+        with pytest.raises(ValueError, match="No rows"):
+            empty.one()
+        assert empty.scalar() is None
+
     async def test_result_first_row_typed(self, session, users_table, sample_users):
         """Test getting first row as dict"""
         result = await session.execute("SELECT * FROM users WHERE id = ?", {"id": 1})
@@ -64,6 +85,42 @@ class TestQueryResult:
         assert len(rows_dicts) == len(sample_users)
         assert all(isinstance(row, dict) for row in rows_dicts)
 
+    async def test_result_all(self, session, users_table, sample_users):
+        """Test converting all rows to dicts or tuples at once"""
+        result = await session.execute("SELECT id, username FROM users")
+        dicts = result.all()
+        assert sorted(dicts, key=lambda row: row["id"]) == [
+            {"id": user["id"], "username": user["username"]} for user in sample_users
+        ]
+        assert dicts == [row.as_dict() for row in result.rows()]
+        assert result.all(as_dict=False) == [tuple(row.values()) for row in result.rows()]
+
+        restored = pickle.loads(pickle.dumps(result))
+        assert restored.all() == dicts
+
+        empty = await session.execute("SELECT id FROM users WHERE id = -1")
+        assert empty.all() == []
+
+    async def test_result_stream(self, session, test_keyspace):
+        """Test iterating rows deserialized in the background"""
+        await session.execute("CREATE TABLE IF NOT EXISTS stream_rows (id int PRIMARY KEY)")
+        await session.await_schema_agreement()
+        prepared = await session.prepare("INSERT INTO stream_rows (id) VALUES (?)")
+        await session.execute_many(prepared, [{"id": i} for i in range(3000)])
+
+        result = await session.execute("SELECT id FROM stream_rows", page_size=1000)
+        ids = [row["id"] for row in result.stream(buffer=100)]
+        assert sorted(ids) == list(range(3000))
+        assert ids == [row["id"] for row in result]
+
+        streamed = [row async for row in result.stream()]
+        assert len(streamed) == 3000
+
+        restored = pickle.loads(pickle.dumps(result))
+        assert [row["id"] for row in restored.stream()] == ids
+
+        await session.execute("DROP TABLE IF EXISTS stream_rows")
+
     async def test_result_col_specs(self, session, users_table):
         """Test getting column specifications"""
         result = await session.execute("SELECT * FROM users")
@@ -72,6 +129,7 @@ class TestQueryResult:
         assert col_specs is not None
         assert isinstance(col_specs, list)
         assert len(col_specs) > 0
+        assert all(spec.table == "users" for spec in col_specs)
 
     async def test_result_warnings(self, session, users_table):
         """Test getting warnings"""
@@ -107,6 +165,178 @@ class TestQueryResult:
         result_empty = await session.execute("SELECT * FROM users WHERE id = ?", {"id": 9999})
         assert bool(result_empty) is False
 
+    async def test_result_execution_info(self, session, users_table, sample_users):
+        """Test execution info of a prepared statement"""
+        prepared = await session.prepare("SELECT * FROM users WHERE id = ?")
+        result = await session.execute_prepared(prepared, {"id": 1})
+
+        info = result.execution_info()
+        assert info.coordinator
+        assert info.coordinator_is_replica is True
+        assert info.attempts == 1
+        assert info.retries == 0
+        assert info.speculative_executions == 0
+        assert info.latency_ms > 0
+
+    async def test_execution_info_sizes(self, session, users_table, sample_users):
+        """Test request and response sizes, pages and timings"""
+        query = "SELECT * FROM users WHERE id = ?"
+        prepared = await session.prepare(query)
+        one = (await session.execute_prepared(prepared, {"id": 1})).execution_info()
+        missing = (await session.execute_prepared(prepared, {"id": 9999})).execution_info()
+        assert one.request_size == missing.request_size > 4
+        assert one.response_size > missing.response_size == 0
+        assert one.pages == 1
+        assert one.queue_ms >= 0
+        assert 0 < one.network_ms <= one.latency_ms
+
+        result = await session.execute("SELECT * FROM users", page_size=2)
+        info = result.execution_info()
+        assert info.pages == 2
+        assert info.request_size == len("SELECT * FROM users")
+
+        insert = "INSERT INTO users (id, username, email) VALUES (?, ?, ?)"
+        result = await session.execute(
+            insert, {"id": 10, "username": "dave", "email": "dave@example.com"}
+        )
+        info = result.execution_info()
+        assert info.response_size == 0
+        assert info.request_size > len(insert) + len("dave@example.com")
+
+    async def test_pages(self, session, sample_users):
+        """Test iterating over a result page by page"""
+        result = await session.execute("SELECT * FROM users", page_size=2)
+        pages = list(result.pages())
+        assert [len(page) for page in pages] == [2, 1]
+        assert isinstance(pages[0].paging_state, bytes)
+        assert pages[-1].paging_state is None
+        assert sorted(row[0] for page in pages for row in page) == [1, 2, 3]
+
+        result = await session.execute("SELECT * FROM users")
+        (page,) = result.pages()
+        assert len(page.rows) == 3
+        assert page.paging_state is None
+
+    async def test_raw_mode(self, session, users_table, sample_users):
+        """Test returning undeserialized column values"""
+        query = "SELECT id, username, email, created_at FROM users WHERE id = ?"
+        result = await session.execute(query, {"id": 1}, raw=True)
+        row = result.first_row()
+        assert row["id"] == struct.pack(">i", 1)
+        assert row["username"] == b"alice"
+        types = [str(spec.cql_type) for spec in result.col_specs()]
+        assert types == ["int", "text", "text", "timestamp"]
+
+        await session.execute(
+            "INSERT INTO users (id, username) VALUES (?, ?)", {"id": 400, "username": "x"}
+        )
+        prepared = await session.prepare(query)
+        result = await session.execute_prepared(prepared, {"id": 400}, raw=True)
+        assert list(result)[0].values() == [struct.pack(">i", 400), b"x", None, None]
+
+        result = await session.execute("SELECT id FROM users", page_size=2, raw=True)
+        ids = sorted(struct.unpack(">i", row[0])[0] for page in result.pages() for row in page)
+        assert ids == [1, 2, 3, 400]
+
+    async def test_pickle(self, session, sample_users):
+        """Test pickling results and rows"""
+        result = await session.execute("SELECT id, username FROM users")
+        restored = pickle.loads(pickle.dumps(result))
+
+        assert len(restored) == 3
+        assert restored.rows() == result.rows()
+        assert restored.rows_typed() == result.rows_typed()
+        assert restored.execution_info().coordinator == result.execution_info().coordinator
+        assert [len(page) for page in restored.pages()] == [3]
+
+        row = result.first_row()
+        assert pickle.loads(pickle.dumps(row)) == row
+        assert pickle.loads(pickle.dumps(row))["username"] == row["username"]
+
+    async def test_structured_col_specs(self, session):
+        """Test column types of collections and UDTs"""
+        await session.execute("CREATE TYPE IF NOT EXISTS point (x int, y int)")
+        await session.execute(
+            "CREATE TABLE IF NOT EXISTS typed_specs "
+            "(id int PRIMARY KEY, tags map<text, frozen<list<int>>>, origin frozen<point>)"
+        )
+        result = await session.execute("SELECT id, tags, origin FROM typed_specs")
+        id_spec, tags_spec, origin_spec = result.col_specs()
+
+        assert id_spec.name == "id"
+        assert id_spec.cql_type.kind == "int"
+        assert id_spec.cql_type.parameters == []
+
+        tags = tags_spec.cql_type
+        assert str(tags) == "map<text, frozen<list<int>>>"
+        assert tags.kind == "map"
+        assert not tags.frozen
+        key, value = tags.parameters
+        assert key.kind == "text"
+        assert value.kind == "list" and value.frozen
+        assert value.parameters[0].kind == "int"
+
+        origin = origin_spec.cql_type
+        assert origin.kind == "udt" and origin.frozen
+        assert (origin.keyspace, origin.name) == ("test_rsylla", "point")
+        assert origin.field_names == ["x", "y"]
+        assert [str(field) for field in origin.parameters] == ["int", "int"]
+
+    async def test_result_kind(self, session, users_table):
+        """Test the kind of rows, void, USE and schema change results"""
+        result = await session.execute("SELECT * FROM users")
+        assert result.kind == "rows"
+        assert result.set_keyspace is None and result.schema_change is None
+
+        result = await session.execute(
+            "INSERT INTO users (id, username, email, age) VALUES (?, ?, ?, ?)",
+            {"id": 100, "username": "kind", "email": "kind@example.com", "age": 1},
+        )
+        assert result.kind == "void"
+
+        result = await session.execute("USE test_rsylla")
+        assert result.kind == "set_keyspace"
+        assert result.set_keyspace == "test_rsylla"
+
+        result = await session.execute(
+            "CREATE TABLE IF NOT EXISTS result_kind (id int PRIMARY KEY)"
+        )
+        assert result.kind == "schema_change"
+        change = result.schema_change
+        assert (change.change, change.target) == ("CREATED", "TABLE")
+        assert (change.keyspace, change.name) == ("test_rsylla", "result_kind")
+
+        result = await session.execute("DROP TABLE test_rsylla.result_kind")
+        assert result.schema_change.change == "DROPPED"
+        assert pickle.loads(pickle.dumps(result)).schema_change.name == "result_kind"
+
+    async def test_lwt_results(self, session, sample_users):
+        """Test was_applied and existing() of conditional statements"""
+        insert = "INSERT INTO users (id, username, email, age) VALUES (?, ?, ?, ?) IF NOT EXISTS"
+        result = await session.execute(
+            insert, {"id": 200, "username": "new", "email": "new@example.com", "age": 20}
+        )
+        assert result.was_applied
+        assert result.existing() is None
+
+        result = await session.execute(
+            insert, {"id": 200, "username": "other", "email": "other@example.com", "age": 30}
+        )
+        assert not result.was_applied
+        assert result.existing()["username"] == "new"
+        assert "[applied]" not in result.existing()
+
+        result = await session.execute(
+            "UPDATE users SET age = :age WHERE id = :id IF username = :expected",
+            {"age": 21, "id": 200, "expected": "someone"},
+        )
+        assert not result.was_applied
+        assert result.existing() == {"username": "new"}
+
+        result = await session.execute("SELECT * FROM users WHERE id = ?", {"id": 200})
+        with pytest.raises(ValueError):
+            result.was_applied
+
 
 @pytest.mark.integration
 class TestRow:
@@ -173,3 +403,47 @@ class TestRow:
         row_dict = row.as_dict()
         assert row_dict is not None
         assert isinstance(row_dict, dict)
+
+    async def test_row_equality_hash_and_repr(self, session, users_table, sample_users):
+        """Test comparing, hashing and printing rows"""
+        query = "SELECT id, username FROM users WHERE id = ?"
+        alice = (await session.execute(query, {"id": 1})).first_row()
+        again = (await session.execute(query, {"id": 1})).first_row()
+        bob = (await session.execute(query, {"id": 2})).first_row()
+
+        assert alice == again
+        assert alice != bob
+        assert alice != (1, "alice")
+        assert len({alice, again, bob}) == 2
+        assert repr(alice) == "Row(id=1, username='alice')"
+        assert alice.as_dict() == {"id": 1, "username": "alice"}
+
+        long_email = "x" * 100 + "@example.com"
+        await session.execute(
+            "INSERT INTO users (id, email) VALUES (?, ?)", {"id": 500, "email": long_email}
+        )
+        row = (await session.execute("SELECT email FROM users WHERE id = 500")).first_row()
+        assert repr(row) == "Row(email='" + "x" * 36 + "...)"
+
+    async def test_row_mapping(self, session, users_table, sample_users):
+        """Test using a row like a read-only dict"""
+        query = "SELECT id, username, email FROM users WHERE id = ?"
+        row = (await session.execute(query, {"id": 1})).first_row()
+
+        assert row.keys() == ["id", "username", "email"]
+        assert row.values() == [1, "alice", "alice@example.com"]
+        assert row.items() == [("id", 1), ("username", "alice"), ("email", "alice@example.com")]
+        assert row["username"] == "alice"
+        assert row[1] == "alice"
+        assert "email" in row
+        assert "age" not in row
+        assert row.get("age") is None
+        assert row.get("age", 0) == 0
+        assert dict(row) == {"id": 1, "username": "alice", "email": "alice@example.com"}
+
+        def user(id, username, email):
+            return username
+
+        assert user(**row) == "alice"
+        with pytest.raises(KeyError):
+            row["age"]
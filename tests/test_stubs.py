@@ -0,0 +1,46 @@
+"""
+Tests that the type stubs match the compiled module
+"""
+
+import importlib.util
+from pathlib import Path
+
+import pytest
+
+from rsylla import _rsylla
+
+GENERATOR = Path(__file__).resolve().parent.parent / "scripts" / "generate_stubs.py"
+
+
+def load_generator():
+    spec = importlib.util.spec_from_file_location("generate_stubs", GENERATOR)
+    generator = importlib.util.module_from_spec(spec)
+    spec.loader.exec_module(generator)
+    return generator
+
+
+@pytest.mark.unit
+class TestStubs:
+    """Test the stubs against the signatures of the built module"""
+
+    def test_stubs_match_module(self):
+        """Test the shipped stubs are up to date, as scripts/generate_stubs.py --check"""
+        generator = load_generator()
+        stubs = generator.sync(_rsylla, generator.STUB.read_text())
+        assert stubs.problems == []
+        assert generator.EXTENSION_STUB.read_text() == generator.extension_stub(_rsylla)
+
+    def test_outdated_stubs_are_updated(self):
+        """Test regenerating changed signatures and adding missing members"""
+        generator = load_generator()
+        text = generator.STUB.read_text()
+        text = text.replace("def pool_size(self, size: int)", "def pool_size(self, count: int)")
+        text = text.replace("    def statements_count(self) -> int: ...\n", "")
+        stubs = generator.sync(_rsylla, text)
+        assert stubs.problems == [
+            "SessionBuilder.pool_size: parameters differ from the module",
+            "Batch.statements_count: missing from the stubs",
+        ]
+        updated = stubs.render()
+        assert "    def pool_size(self, size: Any) -> SessionBuilder: ..." in updated
+        assert "    def statements_count(self) -> Any: ..." in updated
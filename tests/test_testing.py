@@ -0,0 +1,155 @@
+"""
+Tests for the mock, recording and replay sessions of rsylla.testing
+"""
+
+import re
+
+import pytest
+
+from rsylla import Batch, Query, ReadTimeout, Statement
+from rsylla.testing import (
+    AsyncMockSession,
+    AsyncReplaySession,
+    MockSession,
+    RecordingSession,
+    ReplaySession,
+    UnexpectedQuery,
+    load_recordings,
+)
+
+
+@pytest.mark.unit
+class TestMockSession:
+    """Test MockSession and AsyncMockSession without a cluster"""
+
+    def test_expected_rows(self):
+        """Test answering statements with canned rows"""
+        session = MockSession(keyspace="app")
+        session.expect("SELECT name FROM users WHERE id = :id", [{"name": "alice"}])
+        result = session.execute("SELECT name  FROM users\nWHERE id = :id;", {"id": 1})
+        assert result.one()["name"] == "alice"
+        assert result.scalar() == "alice"
+        assert result.all() == [{"name": "alice"}]
+        assert session.get_keyspace() == "app"
+
+        statement = Statement("SELECT name FROM users WHERE id = :id")
+        assert session.execute(statement).scalar() == "alice"
+        session.expect(re.compile(r"^INSERT INTO users"))
+        assert len(session.execute(Query("INSERT INTO users (id) VALUES (:id)"))) == 0
+
+    def test_expected_values_and_errors(self):
+        """Test matching bound values, limiting uses and raising errors"""
+        session = MockSession()
+        session.expect("SELECT * FROM t WHERE k = :k", [{"v": 1}], values={"k": 1})
+        session.expect("SELECT * FROM t WHERE k = :k", error=ReadTimeout("timed out"), times=1)
+        assert session.execute("SELECT * FROM t WHERE k = :k", {"k": 1}).scalar() == 1
+        with pytest.raises(ReadTimeout):
+            session.execute("SELECT * FROM t WHERE k = :k", {"k": 2})
+        with pytest.raises(UnexpectedQuery):
+            session.execute("SELECT * FROM t WHERE k = :k", {"k": 2})
+        with pytest.raises(UnexpectedQuery):
+            session.execute("DELETE FROM t")
+
+        lenient = MockSession(strict=False)
+        assert lenient.execute("DELETE FROM t").rows() == []
+
+    def test_recorded_calls(self):
+        """Test assertions on executed statements and values"""
+        session = MockSession(strict=False)
+        prepared = session.prepare("INSERT INTO t (k, v) VALUES (:k, :v)")
+        assert prepared.get_variable_names() == ["k", "v"]
+        session.execute_prepared(prepared, {"k": 1, "v": "a"})
+
+        batch = Batch()
+        batch.append_statement("UPDATE t SET v = :v WHERE k = :k")
+        batch.append_statement("DELETE FROM t WHERE k = :k")
+        session.batch(batch, [{"k": 2, "v": "b"}, {"k": 3}])
+
+        call = session.assert_executed("INSERT INTO t (k, v) VALUES (:k, :v)", {"k": 1, "v": "a"})
+        assert call.method == "execute_prepared"
+        session.assert_executed(re.compile(r"^DELETE"), {"k": 3})
+        session.assert_not_executed("TRUNCATE t")
+        with pytest.raises(AssertionError):
+            session.assert_executed("DELETE FROM t WHERE k = :k", {"k": 4})
+        assert [call.method for call in session.calls] == ["execute_prepared", "batch", "batch"]
+
+        result = session.execute_many(prepared, [{"k": 4, "v": "c"}, {"k": 5, "v": "d"}])
+        assert result.succeeded == 2 and result
+        assert len(session.calls_to(re.compile("^INSERT"))) == 3
+        with pytest.raises(TypeError, match=r"params_list\[1\]"):
+            session.execute_many(prepared, [{"k": 6, "v": "e"}, None])
+
+    def test_expectations_met(self):
+        """Test checking that every expectation was used"""
+        session = MockSession()
+        session.expect("SELECT * FROM a", times=2)
+        session.expect("SELECT * FROM b")
+        session.execute("SELECT * FROM a")
+        with pytest.raises(AssertionError, match="Unmet expectations"):
+            session.assert_expectations_met()
+        session.execute("SELECT * FROM a")
+        session.execute("SELECT * FROM b")
+        session.assert_expectations_met()
+
+        session.reset()
+        assert session.calls == [] and session.expectations == []
+        session.close()
+        with pytest.raises(RuntimeError):
+            session.execute("SELECT * FROM a")
+
+    async def test_async_mock_session(self):
+        """Test AsyncMockSession returning awaitables"""
+        async with AsyncMockSession() as session:
+            session.expect("SELECT v FROM t", [{"v": 1}, {"v": 2}])
+            result = await session.execute("SELECT v FROM t")
+            assert [row["v"] for row in result] == [1, 2]
+            prepared = await session.prepare("SELECT v FROM t")
+            assert len(await session.execute_prepared(prepared)) == 2
+            statements = ["SELECT v FROM t", ("SELECT v FROM t", None)]
+            results = await session.execute_concurrent(statements)
+            assert all(ok for ok, _ in results)
+            await session.use_keyspace("app")
+            assert session.keyspace == "app"
+        assert session.closed
+
+    def test_record_and_replay(self, tmp_path):
+        """Test replaying statements recorded through a session"""
+        path = tmp_path / "session.rec"
+        backend = MockSession()
+        backend.expect("SELECT v FROM t WHERE k = :k", [{"v": 1}], values={"k": 1}, times=1)
+        backend.expect("SELECT v FROM t WHERE k = :k", [{"v": 2}], values={"k": 1})
+        backend.expect("SELECT v FROM t WHERE k = :k", error=ReadTimeout("timed out"))
+        with RecordingSession(backend, path) as session:
+            assert session.execute("SELECT v FROM t WHERE k = :k", {"k": 1}).scalar() == 1
+            assert session.execute("SELECT v FROM t WHERE k = :k", {"k": 1}).scalar() == 2
+            with pytest.raises(ReadTimeout):
+                session.execute("SELECT v FROM t WHERE k = :k", {"k": 2})
+            assert session.get_keyspace() is None
+        assert backend.closed
+        assert [recording.values for recording in load_recordings(path)] == [
+            {"k": 1},
+            {"k": 1},
+            {"k": 2},
+        ]
+
+        replay = ReplaySession(path)
+        result = replay.execute("SELECT v FROM t WHERE k = :k", {"k": 1})
+        assert [row["v"] for row in result] == [1]
+        assert replay.execute("SELECT v FROM t WHERE k = :k", {"k": 1}).scalar() == 2
+        assert replay.execute("SELECT v FROM t WHERE k = :k", {"k": 1}).scalar() == 2
+        with pytest.raises(ReadTimeout):
+            replay.execute("SELECT v FROM t WHERE k = :k", {"k": 2})
+        with pytest.raises(UnexpectedQuery):
+            replay.execute("SELECT v FROM t WHERE k = :k", {"k": 3})
+        replay.assert_expectations_met()
+
+    async def test_async_record_and_replay(self, tmp_path):
+        """Test recording an AsyncSession and replaying with AsyncReplaySession"""
+        path = tmp_path / "session.rec"
+        backend = AsyncMockSession()
+        backend.expect("SELECT v FROM t", [{"v": "a"}])
+        async with RecordingSession(backend, path) as session:
+            assert (await session.execute("SELECT v FROM t")).all() == [{"v": "a"}]
+
+        replay = AsyncReplaySession(path)
+        assert (await replay.execute("SELECT v FROM t")).all() == [{"v": "a"}]